@@ -0,0 +1,283 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+const DEFAULT_VERSION: &str = "v1";
+
+/// # Derive HateoasResource
+/// Generates the `HateoasResource` implementation for a type, all of the constants can be set
+/// through the `#[hateoas(...)]` attribute.
+///
+/// * `group` - the group the resource belongs to, this is required and has to be a valid
+///   DNS subdomain, eg. `hateoas.io`.
+/// * `version` - the schema version, defaults to `v1`. Either a kubernetes styled version
+///   (`v1`, `v2beta1`) or a dotted numeric version (`0.0.1`).
+/// * `kind` - defaults to the name of the type.
+/// * `path` - the `URL_PATH_SEGMENT`, defaults to the pluralised snake-case kind.
+///
+/// ```ignore
+/// use hateoas::HateoasResource;
+///
+/// #[derive(HateoasResource)]
+/// #[hateoas(group = "hateoas.io", version = "v2")]
+/// pub struct RubberBullet {}
+///
+/// assert_eq!(RubberBullet::KIND, "RubberBullet");
+/// assert_eq!(RubberBullet::URL_PATH_SEGMENT, "rubber_bullets");
+/// ```
+#[proc_macro_derive(HateoasResource, attributes(hateoas))]
+pub fn derive_hateoas_resource(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct ResourceAttributes {
+    kind: Option<LitStr>,
+    group: Option<LitStr>,
+    version: Option<LitStr>,
+    path: Option<LitStr>,
+}
+
+impl ResourceAttributes {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attributes = ResourceAttributes::default();
+        for attr in input.attrs.iter().filter(|a| a.path().is_ident("hateoas")) {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("kind") {
+                    &mut attributes.kind
+                } else if meta.path.is_ident("group") {
+                    &mut attributes.group
+                } else if meta.path.is_ident("version") {
+                    &mut attributes.version
+                } else if meta.path.is_ident("path") {
+                    &mut attributes.path
+                } else {
+                    return Err(meta.error(
+                        "unknown hateoas attribute, expected one of `kind`, `group`, `version` or `path`",
+                    ));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("duplicate hateoas attribute"));
+                }
+                *slot = Some(meta.value()?.parse()?);
+                Ok(())
+            })?;
+        }
+        Ok(attributes)
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let attributes = ResourceAttributes::parse(&input)?;
+    let ident = &input.ident;
+
+    let group = attributes.group.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            "missing `#[hateoas(group = \"...\")]`, the group of the resource is required",
+        )
+    })?;
+    validate(&group, validate_group)?;
+
+    let version = attributes
+        .version
+        .unwrap_or_else(|| LitStr::new(DEFAULT_VERSION, Span::call_site()));
+    validate(&version, validate_version)?;
+
+    let kind = attributes
+        .kind
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    validate(&kind, validate_kind)?;
+
+    let path = attributes
+        .path
+        .unwrap_or_else(|| LitStr::new(&pluralize(&to_snake_case(&kind.value())), kind.span()));
+    validate(&path, validate_path)?;
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::hateoas::HateoasResource for #ident #ty_generics #where_clause {
+            const KIND: &'static str = #kind;
+            const VERSION: &'static str = #version;
+            const GROUP: &'static str = #group;
+            const URL_PATH_SEGMENT: &'static str = #path;
+        }
+    })
+}
+
+fn validate(lit: &LitStr, validator: fn(&str) -> Result<(), String>) -> syn::Result<()> {
+    validator(&lit.value()).map_err(|e| syn::Error::new(lit.span(), e))
+}
+
+/// The group has to be a valid DNS subdomain (RFC 1123), as it makes up the first part of the
+/// `apiVersion`.
+fn validate_group(group: &str) -> Result<(), String> {
+    if group.is_empty() || group.len() > 253 {
+        return Err(format!(
+            "invalid group `{}`, a group must be between 1 and 253 characters",
+            group
+        ));
+    }
+    for label in group.split('.') {
+        let valid_chars = label
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        let valid_edges = label
+            .chars()
+            .next()
+            .zip(label.chars().last())
+            .map(|(first, last)| first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric())
+            .unwrap_or(false);
+        if label.len() > 63 || !valid_chars || !valid_edges {
+            return Err(format!(
+                "invalid group `{}`, a group must be a lowercase DNS subdomain, eg. `hateoas.io`",
+                group
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Versions are either kubernetes styled (`v1`, `v1alpha1`, `v2beta3`) or dotted numeric (`0.0.1`).
+fn validate_version(version: &str) -> Result<(), String> {
+    let is_numeric = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+    let is_kube_version = version
+        .strip_prefix('v')
+        .map(|rest| {
+            let (major, stage) = match rest.find(|c: char| !c.is_ascii_digit()) {
+                Some(idx) => rest.split_at(idx),
+                None => (rest, ""),
+            };
+            let valid_stage = stage.is_empty()
+                || stage
+                    .strip_prefix("alpha")
+                    .or_else(|| stage.strip_prefix("beta"))
+                    .map(is_numeric)
+                    .unwrap_or(false);
+            is_numeric(major) && !major.starts_with('0') && valid_stage
+        })
+        .unwrap_or(false);
+    let is_dotted_version = version.split('.').all(is_numeric);
+
+    if is_kube_version || is_dotted_version {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid version `{}`, expected a version like `v1`, `v2beta1` or `0.0.1`",
+            version
+        ))
+    }
+}
+
+fn validate_kind(kind: &str) -> Result<(), String> {
+    let starts_with_letter = kind
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic())
+        .unwrap_or(false);
+    if starts_with_letter && kind.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid kind `{}`, a kind must be alphanumeric and start with a letter",
+            kind
+        ))
+    }
+}
+
+fn validate_path(path: &str) -> Result<(), String> {
+    if !path.is_empty()
+        && path
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid path `{}`, a path may only contain alphanumeric characters, `-`, `_`, `.` and `/`",
+            path
+        ))
+    }
+}
+
+fn to_snake_case(s: &str) -> String {
+    let chars = s.chars().collect::<Vec<char>>();
+    let mut snake = String::with_capacity(s.len() + 4);
+    for (idx, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev_lower = idx > 0 && !chars[idx - 1].is_uppercase();
+            let next_lower = chars
+                .get(idx + 1)
+                .map(|n| n.is_lowercase())
+                .unwrap_or(false);
+            if idx > 0 && (prev_lower || next_lower) {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+fn pluralize(s: &str) -> String {
+    let ends_with_consonant_y = s.ends_with('y')
+        && !s[..s.len() - 1].ends_with(|c: char| "aeiou".contains(c))
+        && s.len() > 1;
+    if ends_with_consonant_y {
+        format!("{}ies", &s[..s.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|e| s.ends_with(e)) {
+        format!("{}es", s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn snake_case_conversion() {
+        assert_eq!(to_snake_case("RubberBullet"), "rubber_bullet");
+        assert_eq!(to_snake_case("HTTPRoute"), "http_route");
+        assert_eq!(to_snake_case("Pod"), "pod");
+    }
+
+    #[test]
+    fn pluralized_paths() {
+        assert_eq!(pluralize("rubber_bullet"), "rubber_bullets");
+        assert_eq!(pluralize("policy"), "policies");
+        assert_eq!(pluralize("gateway"), "gateways");
+        assert_eq!(pluralize("address"), "addresses");
+        assert_eq!(pluralize("box"), "boxes");
+    }
+
+    #[test]
+    fn group_validation() {
+        assert!(validate_group("hateoas.io").is_ok());
+        assert!(validate_group("apps").is_ok());
+        assert!(validate_group("").is_err());
+        assert!(validate_group("Hateoas.io").is_err());
+        assert!(validate_group("hateoas/io").is_err());
+        assert!(validate_group("-hateoas.io").is_err());
+    }
+
+    #[test]
+    fn version_validation() {
+        assert!(validate_version("v1").is_ok());
+        assert!(validate_version("v2beta1").is_ok());
+        assert!(validate_version("0.0.1").is_ok());
+        assert!(validate_version("").is_err());
+        assert!(validate_version("v0").is_err());
+        assert!(validate_version("v1/alpha").is_err());
+        assert!(validate_version("v1gamma1").is_err());
+    }
+}
@@ -0,0 +1,5 @@
+#[test]
+fn rejected_attributes() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
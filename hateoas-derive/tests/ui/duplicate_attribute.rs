@@ -0,0 +1,7 @@
+use hateoas_derive::HateoasResource;
+
+#[derive(HateoasResource)]
+#[hateoas(group = "hateoas.io", group = "ammo.hateoas.io")]
+pub struct RubberBullet;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use hateoas_derive::HateoasResource;
+
+#[derive(HateoasResource)]
+#[hateoas(group = "hateoas.io", kind = "Rubber Bullet")]
+pub struct RubberBullet;
+
+fn main() {}
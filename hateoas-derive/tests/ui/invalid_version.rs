@@ -0,0 +1,7 @@
+use hateoas_derive::HateoasResource;
+
+#[derive(HateoasResource)]
+#[hateoas(group = "hateoas.io", version = "v1gamma1")]
+pub struct RubberBullet;
+
+fn main() {}
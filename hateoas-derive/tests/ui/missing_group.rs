@@ -0,0 +1,6 @@
+use hateoas_derive::HateoasResource;
+
+#[derive(HateoasResource)]
+pub struct RubberBullet;
+
+fn main() {}
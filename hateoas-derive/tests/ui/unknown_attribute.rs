@@ -0,0 +1,7 @@
+use hateoas_derive::HateoasResource;
+
+#[derive(HateoasResource)]
+#[hateoas(group = "hateoas.io", plural = "rubber_bullets")]
+pub struct RubberBullet;
+
+fn main() {}
@@ -1,12 +1,21 @@
 use crate::serde::Serialize;
-use crate::RelLinkCollection;
+use crate::{Embedded, EmbeddedError, Hateoas, HateoasResource, RelLinkCollection};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::ops::{Deref, DerefMut};
 
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(rename = "Content_{T}")
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Content<T> {
     content: Option<T>,
     rel: Option<RelLinkCollection>,
+    embedded: Option<Embedded>,
 }
 impl<T> Content<T> {
     /// Setting the content on the Content container
@@ -22,6 +31,7 @@ impl<T> Content<T> {
         Content {
             content: Some(content),
             rel: None,
+            embedded: None,
         }
     }
 
@@ -90,6 +100,92 @@ impl<T> Content<T> {
 
         self.rel.get_or_insert_with(RelLinkCollection::default)
     }
+
+    /// Get the rel without initializing it.
+    ///
+    /// ```
+    /// use hateoas::{Content, RelLink};
+    ///
+    /// let mut content: Content<()> = Content::default();
+    /// assert_eq!(content.rel_links(), None);
+    ///
+    /// content.rel().add(RelLink::GET("self", "/bullets/9"));
+    /// assert_eq!(content.rel_links().and_then(|t| t.get("self")).map(|t| t.href()), Some("/bullets/9"));
+    /// ```
+    pub fn rel_links(&self) -> Option<&RelLinkCollection> {
+        self.rel.as_ref()
+    }
+
+    /// ## Converting the content
+    /// Converting the content into another type while keeping the rel links, if the conversion
+    /// fails the error is returned.
+    ///
+    /// ```
+    /// use hateoas::Content;
+    ///
+    /// let content = Content::new(42u16);
+    /// let converted: Result<Content<u32>, ()> = content.try_map(|t| Ok(t as u32));
+    ///
+    /// assert_eq!(converted, Ok(Content::new(42u32)));
+    /// ```
+    pub fn try_map<U, E, F>(self, f: F) -> Result<Content<U>, E>
+    where
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        Ok(Content {
+            content: self.content.map(f).transpose()?,
+            rel: self.rel,
+            embedded: self.embedded,
+        })
+    }
+
+    /// ## Getting the embedded resources
+    /// Getting the embedded resources, if there are no embedded resources `None` is returned.
+    pub fn embedded(&self) -> Option<&Embedded> {
+        self.embedded.as_ref()
+    }
+
+    /// Get the embedded resources even if not set.
+    pub fn embedded_mut(&mut self) -> &mut Embedded {
+        self.embedded.get_or_insert_with(Embedded::default)
+    }
+
+    /// ## Embedding a resource
+    /// Embedding a related resource under a name, see [`Embedded::insert`].
+    ///
+    /// ```
+    /// use hateoas::{Content, Hateoas, RelLink};
+    ///
+    /// let mut author = Hateoas::OK(Some("Rick".to_string()), None);
+    /// author.spec_mut().rel().add(RelLink::GET("self", "/authors/rick"));
+    ///
+    /// let mut content = Content::new(42u16);
+    /// content.embed("author", author.clone()).unwrap();
+    ///
+    /// assert_eq!(content.get_embedded::<String>("author").unwrap(), Some(author));
+    /// ```
+    pub fn embed<U>(
+        &mut self,
+        name: &str,
+        resource: Hateoas<U>,
+    ) -> Result<Option<Value>, EmbeddedError>
+    where
+        U: HateoasResource + Clone + Serialize,
+    {
+        self.embedded_mut().insert(name, resource)
+    }
+
+    /// ## Getting an embedded resource
+    /// Getting the embedded resource as `U`, see [`Embedded::get`].
+    pub fn get_embedded<U>(&self, name: &str) -> Result<Option<Hateoas<U>>, EmbeddedError>
+    where
+        U: HateoasResource + Clone + DeserializeOwned,
+    {
+        match &self.embedded {
+            Some(embedded) => embedded.get(name),
+            None => Ok(None),
+        }
+    }
 }
 
 impl<T> Default for Content<T> {
@@ -97,6 +193,7 @@ impl<T> Default for Content<T> {
         Content {
             content: None,
             rel: None,
+            embedded: None,
         }
     }
 }
@@ -0,0 +1,420 @@
+use crate::{Hateoas, HateoasResource};
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// # Embedded
+/// Named map of embedded resources, each embedded resource is a full [`Hateoas`] envelope with its
+/// own kind, metadata and rel links. This allows for sending related resources together with the
+/// resource, so the client does not need to fetch them one by one.
+///
+/// The embedded resources are stored type erased, and are serialized as [`EmbeddedEnvelope`]s
+/// carrying the envelope as a nested object. Formats that are not human readable, eg. postcard or
+/// messagepack, carry the envelope encoded as JSON instead, so it survives every format.
+///
+/// ```
+/// use hateoas::{Embedded, Hateoas};
+///
+/// let mut embedded = Embedded::default();
+/// embedded.insert("greeting", Hateoas::OK(Some("hello".to_string()), None)).unwrap();
+///
+/// let greeting: Hateoas<String> = embedded.get("greeting").unwrap().unwrap();
+///
+/// assert_eq!(greeting, Hateoas::OK(Some("hello".to_string()), None));
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[serde(
+    into = "BTreeMap<String, EmbeddedEnvelope>",
+    try_from = "BTreeMap<String, EmbeddedEnvelope>"
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "utoipa",
+    derive(utoipa::ToSchema),
+    schema(value_type = BTreeMap<String, EmbeddedEnvelope>)
+)]
+pub struct Embedded(
+    #[cfg_attr(
+        feature = "schemars",
+        schemars(with = "BTreeMap<String, EmbeddedEnvelope>")
+    )]
+    BTreeMap<String, EmbeddedResource>,
+);
+
+/// An embedded envelope and if it holds a [`crate::ResourceList`], the kind alone does not tell as
+/// resources may have a kind ending in `List`.
+#[derive(PartialEq, Debug, Clone)]
+struct EmbeddedResource {
+    envelope: Value,
+    list: bool,
+}
+
+/// # Embedded envelope
+/// An embedded resource as it is serialized, the kind of the resource and if it is a list next to
+/// the envelope. The envelope is a nested object in human readable formats and a JSON string in
+/// the other formats.
+///
+/// ```
+/// use hateoas::{Embedded, Hateoas};
+///
+/// let mut embedded = Embedded::default();
+/// embedded.insert("greeting", Hateoas::OK(Some("hello".to_string()), None)).unwrap();
+///
+/// let value = serde_json::to_value(&embedded).unwrap();
+/// assert_eq!(value["greeting"]["kind"], "String");
+/// assert_eq!(value["greeting"]["list"], false);
+/// assert_eq!(value["greeting"]["envelope"]["spec"]["content"], "hello");
+/// ```
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct EmbeddedEnvelope {
+    pub kind: Option<String>,
+    #[serde(default)]
+    pub list: bool,
+    #[serde(serialize_with = "serialize_envelope")]
+    #[serde(deserialize_with = "deserialize_envelope")]
+    pub envelope: Value,
+}
+
+/// Human readable formats that do not read a nested object back as it was written, S-expressions
+/// read maps back as lists and query strings read every value back as a string.
+const FLAT_FORMATS: [&str; 2] = ["serde_lexpr::", "serde_qs::"];
+
+/// Checking if the format of the serializer or deserializer `F` can carry a nested object.
+fn is_nested<F>(human_readable: bool) -> bool {
+    let format = std::any::type_name::<F>();
+    human_readable && !FLAT_FORMATS.iter().any(|t| format.contains(t))
+}
+
+fn serialize_envelope<S: Serializer>(envelope: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+    if is_nested::<S>(serializer.is_human_readable()) {
+        envelope.serialize(serializer)
+    } else {
+        serializer.serialize_str(&envelope.to_string())
+    }
+}
+
+/// Envelopes encoded as a JSON string are read in the human readable formats as well.
+fn deserialize_envelope<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+    let envelope = if is_nested::<D>(deserializer.is_human_readable()) {
+        match serde::Deserialize::deserialize(deserializer)? {
+            Value::String(envelope) => envelope,
+            envelope => return Ok(envelope),
+        }
+    } else {
+        <String as serde::Deserialize>::deserialize(deserializer)?
+    };
+    serde_json::from_str(&envelope).map_err(D::Error::custom)
+}
+
+#[derive(Debug, Display)]
+#[non_exhaustive]
+pub enum EmbeddedError {
+    /// The embedded resource is of another kind than the requested
+    #[display(fmt = "Embedded resource is of kind {} but {} was requested", _0, _1)]
+    KindMismatch(String, String),
+
+    #[display(fmt = "Embedded resource could not be serialized/deserialized: {}", _0)]
+    SerializationDeserializationError(serde_json::Error),
+}
+
+impl From<serde_json::Error> for EmbeddedError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::SerializationDeserializationError(e)
+    }
+}
+
+impl Embedded {
+    /// ## Embedding a resource
+    /// Embedding the resource under the name, if there is already a resource with the name it
+    /// will be replaced and the old resource is returned.
+    pub fn insert<U>(
+        &mut self,
+        name: &str,
+        resource: Hateoas<U>,
+    ) -> Result<Option<Value>, EmbeddedError>
+    where
+        U: HateoasResource + Clone + Serialize,
+    {
+        let mut value = serde_json::to_value(resource)?;
+        strip_null(&mut value);
+        Ok(self.insert_raw(name, value, U::IS_LIST))
+    }
+
+    /// ## Getting an embedded resource
+    /// Getting the embedded resource as `U`, if the kind of the embedded resource does not match
+    /// `U` an error is returned.
+    ///
+    /// ```
+    /// use hateoas::{Embedded, EmbeddedError, Hateoas};
+    ///
+    /// let mut embedded = Embedded::default();
+    /// embedded.insert("greeting", Hateoas::OK(Some("hello".to_string()), None)).unwrap();
+    ///
+    /// assert!(embedded.get::<String>("missing").unwrap().is_none());
+    /// assert!(matches!(embedded.get::<u16>("greeting"), Err(EmbeddedError::KindMismatch(_, _))));
+    /// ```
+    pub fn get<U>(&self, name: &str) -> Result<Option<Hateoas<U>>, EmbeddedError>
+    where
+        U: HateoasResource + Clone + DeserializeOwned,
+    {
+        let value = match self.get_raw(name) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        if let Some(kind) = value.get("kind").and_then(|t| t.as_str()) {
+            if kind != U::kind() {
+                return Err(EmbeddedError::KindMismatch(kind.to_string(), U::kind()));
+            }
+        }
+        // Representations like HAL does not carry the kind, these are read as `U`.
+        let mut value = value.clone();
+        if let Value::Object(map) = &mut value {
+            let default: Hateoas<U> = Hateoas::default();
+            map.entry("apiVersion")
+                .or_insert_with(|| Value::from(default.api_version().as_str()));
+            map.entry("kind")
+                .or_insert_with(|| Value::from(default.kind().as_str()));
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    /// Inserting an already serialized envelope.
+    pub(crate) fn insert_raw(&mut self, name: &str, value: Value, list: bool) -> Option<Value> {
+        let resource = EmbeddedResource {
+            envelope: value,
+            list,
+        };
+        self.0
+            .insert(name.to_string(), resource)
+            .map(|t| t.envelope)
+    }
+
+    /// Getting the raw embedded resource.
+    pub fn get_raw(&self, name: &str) -> Option<&Value> {
+        self.0.get(name).map(|t| &t.envelope)
+    }
+
+    /// The raw embedded resources by name, with if they hold a [`crate::ResourceList`].
+    pub(crate) fn resources(&self) -> impl Iterator<Item = (&str, &Value, bool)> {
+        self.0
+            .iter()
+            .map(|(name, t)| (name.as_str(), &t.envelope, t.list))
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        self.0.remove(name).map(|t| t.envelope)
+    }
+
+    /// The names of all the embedded resources.
+    pub fn names(&self) -> Vec<&str> {
+        self.0.keys().map(|t| t.as_str()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Embedded> for BTreeMap<String, Value> {
+    fn from(e: Embedded) -> Self {
+        e.0.into_iter()
+            .map(|(name, t)| (name, t.envelope))
+            .collect()
+    }
+}
+
+impl From<Embedded> for BTreeMap<String, EmbeddedEnvelope> {
+    fn from(e: Embedded) -> Self {
+        e.0.into_iter()
+            .map(|(name, t)| {
+                let envelope = EmbeddedEnvelope {
+                    kind: t.envelope["kind"].as_str().map(|t| t.to_string()),
+                    list: t.list,
+                    envelope: t.envelope,
+                };
+                (name, envelope)
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<BTreeMap<String, EmbeddedEnvelope>> for Embedded {
+    type Error = EmbeddedError;
+
+    fn try_from(envelopes: BTreeMap<String, EmbeddedEnvelope>) -> Result<Self, Self::Error> {
+        envelopes
+            .into_iter()
+            .map(|(name, t)| {
+                let value = t.envelope;
+                match (value["kind"].as_str(), t.kind) {
+                    (Some(kind), Some(tag)) if kind != tag => {
+                        Err(EmbeddedError::KindMismatch(kind.to_string(), tag))
+                    }
+                    _ => Ok((
+                        name,
+                        EmbeddedResource {
+                            envelope: value,
+                            list: t.list,
+                        },
+                    )),
+                }
+            })
+            .collect::<Result<BTreeMap<String, EmbeddedResource>, EmbeddedError>>()
+            .map(Embedded)
+    }
+}
+
+/// Removing the `null` values to keep the envelopes small, the envelope and content fields are all
+/// optional so they will be read back as `None`.
+fn strip_null(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, t| !t.is_null());
+            map.values_mut().for_each(strip_null);
+        }
+        Value::Array(list) => list.iter_mut().for_each(strip_null),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Content, Embedded, Hateoas, HateoasList, RelLink, ResourceList};
+    use serde_json::{json, Value};
+
+    fn bullet() -> Hateoas<String> {
+        let mut author = Hateoas::OK(Some("Rick".to_string()), None);
+        author
+            .spec_mut()
+            .rel()
+            .add(RelLink::GET("self", "/authors/rick"));
+        let mut shells = HateoasList::OK(Some(ResourceList::from(vec![9u16, 12u16])), None);
+        shells.spec_mut().rel().add(RelLink::GET("self", "/shells"));
+
+        let mut content = Content::new("Rubber Bullet".to_string());
+        content.embed("author", author).unwrap();
+        content.embed("shells", shells).unwrap();
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn embedded_serialization() {
+        let value = serde_json::to_value(bullet()).unwrap();
+        let envelope = |name: &str| -> &Value { &value["spec"]["embedded"][name]["envelope"] };
+
+        assert_eq!(value["spec"]["embedded"]["author"]["kind"], "String");
+        assert_eq!(value["spec"]["embedded"]["shells"]["kind"], "U16List");
+        assert_eq!(value["spec"]["embedded"]["shells"]["list"], true);
+        assert_eq!(envelope("shells")["spec"]["rel"][0]["href"], "/shells");
+        assert!(envelope("author").get("metadata").is_none());
+    }
+
+    #[test]
+    fn embedded_json_wire_shape() {
+        let mut embedded = Embedded::default();
+        embedded
+            .insert("author", Hateoas::OK(Some("Rick".to_string()), None))
+            .unwrap();
+
+        let json = serde_json::to_string(&embedded).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&json).unwrap(),
+            json!({
+                "author": {
+                    "kind": "String",
+                    "list": false,
+                    "envelope": {
+                        "apiVersion": Hateoas::<String>::default().api_version(),
+                        "kind": "String",
+                        "spec": {"content": "Rick"},
+                        "status": {"message": "OK", "http_status_code": 200}
+                    }
+                }
+            })
+        );
+        assert_eq!(serde_json::from_str::<Embedded>(&json).unwrap(), embedded);
+    }
+
+    #[test]
+    fn embedded_kind_tag_mismatch() {
+        let value = serde_json::json!({
+            "author": {"kind": "U16", "envelope": r#"{"kind":"String","spec":{"content":"Rick"}}"#}
+        });
+
+        assert!(serde_json::from_value::<Embedded>(value).is_err());
+    }
+
+    #[cfg(feature = "web-framework-support")]
+    #[test]
+    fn embedded_round_trip() {
+        use simple_serde::{Decoded, SimpleDecoder, SimpleEncoder};
+
+        for content_type in [
+            "application/json",
+            "application/x-yaml",
+            "application/x-ron",
+            "application/x-json5",
+            "application/x-cbor",
+            "application/x-pickle",
+            "application/x-toml",
+            "application/x-bson",
+            "application/x-flexbuffers",
+            "application/x-lexpr",
+        ] {
+            let encoded = bullet().encode(content_type).unwrap().to_vec();
+            let decoded: Hateoas<String> = encoded
+                .decode(content_type)
+                .map(|t: Decoded<Hateoas<String>>| t.into())
+                .unwrap_or_else(|e| panic!("{}: {:?}", content_type, e));
+
+            assert_eq!(decoded, bullet(), "{}", content_type);
+            let shells: HateoasList<u16> = decoded
+                .spec()
+                .unwrap()
+                .get_embedded("shells")
+                .unwrap()
+                .unwrap();
+            assert_eq!(shells.kind(), "U16List");
+        }
+    }
+
+    #[cfg(feature = "web-framework-support")]
+    #[test]
+    fn embedded_round_trip_all_formats() {
+        use simple_serde::{Decoded, SimpleDecoder, SimpleEncoder};
+
+        let embedded = bullet().spec().unwrap().embedded().cloned().unwrap();
+        for content_type in [
+            "application/json",
+            "application/x-yaml",
+            "application/x-ron",
+            "application/x-json5",
+            "application/x-cbor",
+            "application/x-pickle",
+            "application/x-toml",
+            "application/x-bson",
+            "application/x-flexbuffers",
+            "application/x-lexpr",
+            "application/x-messagepack",
+            "application/x-postcard",
+            "application/x-url",
+        ] {
+            let encoded = embedded.encode(content_type).unwrap().to_vec();
+            let decoded: Embedded = encoded
+                .decode(content_type)
+                .map(|t: Decoded<Embedded>| t.into())
+                .unwrap_or_else(|e| panic!("{}: {:?}", content_type, e));
+
+            assert_eq!(decoded, embedded, "{}", content_type);
+            let shells: HateoasList<u16> = decoded.get("shells").unwrap().unwrap();
+            assert_eq!(shells.kind(), "U16List");
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use crate::frameworks::actix::config::HateoasConfig;
 use crate::frameworks::actix::error::ActixError;
-use crate::frameworks::payload_control::PayloadControl;
-use crate::{Hateoas, HateoasResource};
+use crate::frameworks::compression;
+use crate::frameworks::media_type::MediaType;
+use crate::{Hateoas, HateoasResource, Representation};
 use actix_web::dev::Payload;
-use actix_web::http::header::CONTENT_LENGTH;
+use actix_web::http::header::{CONTENT_ENCODING, CONTENT_LENGTH};
 use actix_web::HttpRequest;
 use bytes::BytesMut;
 use futures_core::Stream as _;
@@ -14,8 +16,6 @@ use std::ops::Deref;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-const DEFAULT_LIMIT: usize = 41_943_040; // 40 mb
-
 pub enum PayloadBody<T, O> {
     Error(Option<ActixError>),
     Body {
@@ -23,6 +23,14 @@ pub enum PayloadBody<T, O> {
         /// Length as reported by `Content-Length` header, if present.
         length: Option<usize>,
         content_type: ContentType,
+        /// `Content-Encoding` of the payload, the payload is decompressed before it is decoded.
+        content_encoding: Option<String>,
+        /// Hypermedia representation of the payload, these are not decoded by simple_serde.
+        representation: Option<Representation>,
+        /// Only accepting payloads wrapped in the envelope.
+        strict: bool,
+        /// Template the code of problem details is read from.
+        problem_type_template: Option<String>,
         payload: Payload,
         buf: BytesMut,
         _res: PhantomData<T>,
@@ -32,8 +40,9 @@ pub enum PayloadBody<T, O> {
 
 impl<T, O> Unpin for PayloadBody<T, O> {}
 
-impl<T: DeserializeOwned, O: PayloadControl> PayloadBody<T, O> {
-    /// Create a new future to decode a JSON request payload.
+impl<T: DeserializeOwned, O> PayloadBody<T, O> {
+    /// Create a new future to decode a request payload, configured by the [`HateoasConfig`] of the
+    /// request.
     #[allow(clippy::borrow_interior_mutable_const)]
     pub fn new(r: HttpRequest, payload: &mut Payload) -> Self {
         let length = r
@@ -42,47 +51,78 @@ impl<T: DeserializeOwned, O: PayloadControl> PayloadBody<T, O> {
             .ok_or(ActixError::NoPayloadSizeDefinitionInHeader)
             .and_then(|l| l.to_str().map_err(ActixError::from))
             .and_then(|s| s.parse::<usize>().map_err(ActixError::from));
-        let content_type = Ok(r
+        let representation = r
+            .headers()
+            .get("Content-Type")
+            .and_then(|h| h.to_str().ok())
+            .and_then(Representation::from_media_type);
+        let content_encoding = r
             .headers()
-            .get_all("Content-Type")
-            .filter_map(|h| simple_serde::ContentType::try_from(h).ok())
-            .collect::<Vec<ContentType>>())
-        .and_then(|mut t: Vec<ContentType>| {
-            t.reverse();
-            t.pop().ok_or(ActixError::FailedToGetContentTypeFromHeader)
-        });
+            .get_all(&CONTENT_ENCODING)
+            .filter_map(|h| h.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        let content_encoding = (!content_encoding.is_empty()).then_some(content_encoding);
+        let content_type = match representation {
+            Some(_) => Ok(ContentType::Json),
+            None => Ok(r
+                .headers()
+                .get_all("Content-Type")
+                .filter_map(|h| {
+                    simple_serde::ContentType::try_from(h).ok().or_else(|| {
+                        h.to_str()
+                            .ok()
+                            .and_then(|t| MediaType::parse(t).ok()?.vendor_codec())
+                            .and_then(|t| simple_serde::ContentType::try_from(t).ok())
+                    })
+                })
+                .collect::<Vec<ContentType>>())
+            .and_then(|mut t: Vec<ContentType>| {
+                t.reverse();
+                t.pop().ok_or(ActixError::FailedToGetContentTypeFromHeader)
+            }),
+        };
+
+        let config = HateoasConfig::from_req(&r);
+        let header = r
+            .headers()
+            .get("Content-Type")
+            .map(|h| String::from_utf8_lossy(h.as_bytes()).to_string());
 
         let payload = payload.take();
 
-        match (content_type, length) {
-            (Ok(c), Ok(l)) => PayloadBody::Body {
-                limit: O::MAX_PAYLOAD_SIZE.unwrap_or(DEFAULT_LIMIT),
-                content_type: c,
-                length: Some(l),
-                payload,
-                buf: BytesMut::with_capacity(O::BUFFER_CAPACITY.unwrap_or(8192)),
-                _res: PhantomData,
-                _payload_res: PhantomData,
-            },
-            (Ok(c), _) => PayloadBody::Body {
-                limit: O::MAX_PAYLOAD_SIZE.unwrap_or(DEFAULT_LIMIT),
+        match (content_type, header) {
+            (_, None) => PayloadBody::Error(Some(ActixError::ContentType)),
+            (Ok(c), Some(header)) if config.allows(&header) => PayloadBody::Body {
+                limit: config.limit,
                 content_type: c,
-                length: None,
+                content_encoding,
+                representation,
+                strict: config.strict,
+                problem_type_template: config.problem_type_template.clone(),
+                length: length.ok(),
                 payload,
-                buf: BytesMut::with_capacity(O::BUFFER_CAPACITY.unwrap_or(8192)),
+                buf: BytesMut::with_capacity(config.buffer_capacity),
                 _res: PhantomData,
                 _payload_res: PhantomData,
-            },
-            (_, _) => PayloadBody::Error(Some(ActixError::ContentType)),
+            }
+            .limit(config.limit),
+            (_, Some(header)) => {
+                PayloadBody::Error(Some(ActixError::UnsupportedContentType(header)))
+            }
         }
     }
 
-    /// Set maximum accepted payload size. The default limit is 2MB.
+    /// Set maximum accepted payload size. The default limit is 40MB.
     pub fn limit(self, limit: usize) -> Self {
         match self {
             PayloadBody::Body {
                 length,
                 content_type,
+                content_encoding,
+                representation,
+                strict,
+                problem_type_template,
                 payload,
                 buf,
                 ..
@@ -99,6 +139,10 @@ impl<T: DeserializeOwned, O: PayloadControl> PayloadBody<T, O> {
                 PayloadBody::Body {
                     limit,
                     content_type,
+                    content_encoding,
+                    representation,
+                    strict,
+                    problem_type_template,
                     length,
                     payload,
                     buf,
@@ -114,7 +158,6 @@ impl<T: DeserializeOwned, O: PayloadControl> PayloadBody<T, O> {
 impl<T, O> Future for PayloadBody<T, O>
 where
     T: DeserializeOwned + HateoasResource + Clone,
-    O: PayloadControl,
 {
     type Output = Result<Hateoas<T>, ActixError>;
 
@@ -127,6 +170,10 @@ where
                 buf,
                 payload,
                 content_type,
+                content_encoding,
+                representation,
+                strict,
+                problem_type_template,
                 ..
             } => loop {
                 let res = match Pin::new(&mut *payload).poll_next(cx) {
@@ -146,18 +193,34 @@ where
                         }
                     }
                     None => {
-                        let json = buf
-                            .to_vec()
-                            .as_slice()
-                            .decode(content_type.deref())
-                            .map(|d: Decoded<T>| Hateoas::from(d.into()))
-                            .or_else(|_e| {
-                                buf.to_vec()
-                                    .as_slice()
-                                    .decode(content_type.deref())
-                                    .map(|d: Decoded<Hateoas<T>>| d.into())
-                            })
-                            .map_err(ActixError::SerializationDeserializationError)?;
+                        let buf = compression::decompress(
+                            content_encoding.as_deref(),
+                            buf.to_vec(),
+                            *limit,
+                        )?;
+                        if let Some(representation) = representation {
+                            return Poll::Ready(
+                                representation
+                                    .decode_with_type_template(
+                                        &buf,
+                                        problem_type_template.as_deref(),
+                                    )
+                                    .map_err(ActixError::from),
+                            );
+                        }
+                        let envelope = |buf: &[u8]| {
+                            buf.decode(content_type.deref())
+                                .map(|d: Decoded<Hateoas<T>>| d.into())
+                        };
+                        let json = if *strict {
+                            envelope(&buf)
+                        } else {
+                            buf.as_slice()
+                                .decode(content_type.deref())
+                                .map(|d: Decoded<T>| Hateoas::from(d.into()))
+                                .or_else(|_e| envelope(&buf))
+                        }
+                        .map_err(ActixError::SerializationDeserializationError)?;
                         return Poll::Ready(Ok(json));
                     }
                 }
@@ -166,3 +229,26 @@ where
         }
     }
 }
+
+/// Reading the payload as received, the payload is not decompressed by actix so the
+/// `Content-Encoding` is handled the same way as by the [`PayloadBody`]. Payloads larger than the
+/// limit are rejected, before they are read when the `Content-Length` is known.
+pub(crate) async fn read_payload(
+    mut payload: Payload,
+    length: Option<usize>,
+    config: &HateoasConfig,
+) -> Result<Vec<u8>, ActixError> {
+    let limit = config.limit;
+    if let Some(length) = length.filter(|t| *t > limit) {
+        return Err(ActixError::OverflowKnownLength { length, limit });
+    }
+    let mut buf = BytesMut::with_capacity(config.buffer_capacity);
+    while let Some(chunk) = std::future::poll_fn(|cx| Pin::new(&mut payload).poll_next(cx)).await {
+        let chunk = chunk?;
+        if buf.len() + chunk.len() > limit {
+            return Err(ActixError::Overflow { limit });
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    Ok(buf.to_vec())
+}
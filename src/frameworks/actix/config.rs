@@ -0,0 +1,381 @@
+use crate::frameworks::actix::error::ActixError;
+use crate::frameworks::media_type::essence;
+use crate::frameworks::payload_control::PayloadControl;
+use crate::frameworks::rejection::{Rejection, RejectionHandler};
+use crate::Hateoas;
+use actix_web::error::InternalError;
+use actix_web::{web, HttpRequest, Responder};
+use std::sync::Arc;
+
+pub(crate) const DEFAULT_LIMIT: usize = 41_943_040; // 40 mb
+pub(crate) const DEFAULT_BUFFER_CAPACITY: usize = 8192;
+
+type ErrorHandler = Arc<dyn Fn(ActixError, &HttpRequest) -> actix_web::Error + Send + Sync>;
+
+static DEFAULT_CONFIG: HateoasConfig = HateoasConfig {
+    limit: DEFAULT_LIMIT,
+    buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+    content_types: None,
+    strict: false,
+    error_handler: None,
+    rejection_handler: None,
+    problem_type_template: None,
+};
+
+/// # HateoasConfig
+/// Configuration of the [`Hateoas`](crate::Hateoas) extractor, registered with `app_data` on the
+/// app, a scope or a resource. Requests without a config use the defaults, a body limit of 40mb,
+/// all content types supported by `simple_serde` and lenient decoding.
+///
+/// ```
+/// use actix_web::{error, web, App, HttpResponse};
+/// use hateoas::frameworks::actix::config::HateoasConfig;
+///
+/// let app = App::new().service(
+///     web::resource("/bullets")
+///         .app_data(
+///             HateoasConfig::default()
+///                 .limit(4096)
+///                 .content_types(["application/json", "application/yaml"])
+///                 .strict(true)
+///                 .error_handler(|e, _req| {
+///                     error::InternalError::from_response(e, HttpResponse::BadRequest().finish())
+///                         .into()
+///                 }),
+///         )
+///         .route(web::post().to(|| async { HttpResponse::Ok() })),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct HateoasConfig {
+    pub(crate) limit: usize,
+    pub(crate) buffer_capacity: usize,
+    pub(crate) content_types: Option<Vec<String>>,
+    pub(crate) strict: bool,
+    pub(crate) error_handler: Option<ErrorHandler>,
+    pub(crate) rejection_handler: Option<Arc<dyn RejectionHandler>>,
+    pub(crate) problem_type_template: Option<String>,
+}
+
+impl HateoasConfig {
+    /// ## Maximum size of the payload in bytes
+    /// The limit applies to the payload as received and to the decompressed payload.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Initial capacity of the buffer the payload is read into.
+    pub fn buffer_capacity(mut self, buffer_capacity: usize) -> Self {
+        self.buffer_capacity = buffer_capacity;
+        self
+    }
+
+    /// ## Allowed content types
+    /// Payloads with another `Content-Type` are rejected, the parameters of the media types are
+    /// ignored when comparing.
+    pub fn content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.content_types = Some(
+            content_types
+                .into_iter()
+                .map(|t| essence(t.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// ## Strict envelope decoding
+    /// Strict decoding only accepts payloads wrapped in the [`Hateoas`](crate::Hateoas) envelope,
+    /// lenient decoding also accepts the bare resource.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// ## Custom error handler
+    /// The request is rejected with the error returned by the error handler. Without an error
+    /// handler the request is rejected with the [`Hateoas`](crate::Hateoas) error of the
+    /// [`RejectionHandler`].
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(ActixError, &HttpRequest) -> actix_web::Error + Send + Sync + 'static,
+    {
+        self.error_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// ## Mapping the rejections
+    /// The [`RejectionHandler`] creating the error envelope the request is rejected with, not used
+    /// when an error handler is set.
+    pub fn rejection_handler<H: RejectionHandler + 'static>(mut self, handler: H) -> Self {
+        self.rejection_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// ## Problem type template
+    /// The template the `type` URI of `application/problem+json` responses is generated from and
+    /// the code of problem payloads is read from, see
+    /// [`ProblemDetails::type_uri`](crate::ProblemDetails::type_uri).
+    pub fn problem_type_template(mut self, template: &str) -> Self {
+        self.problem_type_template = Some(template.to_string());
+        self
+    }
+
+    /// ## Overriding the config by a resource
+    /// The limit and buffer capacity of the [`PayloadControl`] of the resource are used when set.
+    pub fn payload_control<T: PayloadControl>(mut self) -> Self {
+        self.limit = T::MAX_PAYLOAD_SIZE.unwrap_or(self.limit);
+        self.buffer_capacity = T::BUFFER_CAPACITY.unwrap_or(self.buffer_capacity);
+        self
+    }
+
+    /// Checking if the content type is allowed, every content type is allowed when no content
+    /// types are configured.
+    pub(crate) fn allows(&self, content_type: &str) -> bool {
+        self.content_types
+            .as_ref()
+            .map(|t| t.contains(&essence(content_type)))
+            .unwrap_or(true)
+    }
+
+    pub(crate) fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+        match &self.rejection_handler {
+            Some(handler) => handler.reject(rejection),
+            None => rejection.into(),
+        }
+    }
+
+    /// The error rejecting the request with the response of the rejection.
+    pub(crate) fn reject_request(&self, rejection: Rejection, req: &HttpRequest) -> ActixError {
+        let message = rejection.to_string();
+        let response = self.reject(rejection).respond_to(req);
+        ActixError::Handler(InternalError::from_response(message, response).into())
+    }
+
+    /// The config of the request, registered directly or wrapped in `web::Data`.
+    pub(crate) fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|t| t.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+impl Default for HateoasConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HateoasConfig;
+    use crate::frameworks::payload_control::PayloadControl;
+    use crate::frameworks::rejection::{DefaultRejectionHandler, Rejection, RejectionHandler};
+    use crate::{Hateoas, HateoasResource};
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{error, test, web, App};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct RubberBullet {
+        name: String,
+    }
+
+    impl HateoasResource for RubberBullet {
+        const KIND: &'static str = "RubberBullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "rubber_bullets";
+    }
+
+    impl PayloadControl for RubberBullet {
+        const MAX_PAYLOAD_SIZE: Option<usize> = Some(16);
+        const BUFFER_CAPACITY: Option<usize> = None;
+    }
+
+    async fn echo(bullet: Hateoas<RubberBullet>) -> Hateoas<RubberBullet> {
+        bullet
+    }
+
+    const BULLET: &str = r#"{"name":"Strong bullet"}"#;
+    const ENVELOPE: &str = r#"{"apiVersion":"ammo.hateoas.io/v1","kind":"RubberBullet","spec":{"content":{"name":"Strong bullet"}}}"#;
+
+    fn post(body: &'static str, content_type: &str) -> actix_http::Request {
+        test::TestRequest::post()
+            .uri("/")
+            .insert_header((header::CONTENT_TYPE, content_type))
+            .set_payload(body)
+            .to_request()
+    }
+
+    #[actix_web::test]
+    async fn limit() {
+        let app = test::init_service(
+            App::new()
+                .app_data(HateoasConfig::default().limit(8))
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[actix_web::test]
+    async fn payload_control_override() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(
+                    HateoasConfig::default().payload_control::<RubberBullet>(),
+                ))
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[actix_web::test]
+    async fn content_types() {
+        let app = test::init_service(
+            App::new()
+                .app_data(
+                    HateoasConfig::default().content_types(["Application/JSON; charset=utf-8"]),
+                )
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = test::call_service(&app, post("name: Strong bullet", "application/yaml")).await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[actix_web::test]
+    async fn strict() {
+        let app = test::init_service(
+            App::new()
+                .app_data(HateoasConfig::default().strict(true))
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(ENVELOPE, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let result = test::read_body(res).await;
+        let hateoas = serde_json::from_slice::<Hateoas<RubberBullet>>(&result).unwrap();
+        assert_eq!(
+            hateoas.spec().and_then(|t| t.content().as_ref()),
+            Some(&RubberBullet {
+                name: "Strong bullet".to_string()
+            })
+        );
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn rejection_does_not_reach_the_handler() {
+        let app = test::init_service(
+            App::new()
+                .app_data(HateoasConfig::default().limit(8))
+                .route(
+                    "/",
+                    web::post().to(|_: Hateoas<RubberBullet>| async {
+                        Hateoas::<RubberBullet>::CREATED(None, None)
+                    }),
+                ),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let result = test::read_body(res).await;
+        let hateoas = serde_json::from_slice::<Hateoas<()>>(&result).unwrap();
+        assert!(hateoas
+            .status()
+            .and_then(|t| t.message().as_ref())
+            .is_some());
+    }
+
+    #[actix_web::test]
+    async fn error_handler() {
+        let app = test::init_service(
+            App::new()
+                .app_data(
+                    HateoasConfig::default()
+                        .limit(8)
+                        .error_handler(|e, _req| error::ErrorBadRequest(e.to_string())),
+                )
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn rejection_handler() {
+        let app = test::init_service(
+            App::new()
+                .app_data(HateoasConfig::default().strict(true).rejection_handler(
+                    |rejection: Rejection| match rejection {
+                        Rejection::Decode(e) => Hateoas::UNPROCESSABLE_ENTITY(
+                            None,
+                            Some(format!("{:?} at {:?}", e.format(), e.column())),
+                        ),
+                        rejection => DefaultRejectionHandler.reject(rejection),
+                    },
+                ))
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let res = test::call_service(&app, post(BULLET, "application/json")).await;
+        assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        let result = test::read_body(res).await;
+        let hateoas = serde_json::from_slice::<Hateoas<RubberBullet>>(&result).unwrap();
+        assert_eq!(
+            hateoas.status().and_then(|t| t.message().as_deref()),
+            Some("Some(\"JSON\") at Some(24)")
+        );
+
+        let res = test::call_service(&app, post(BULLET, "text/plain")).await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+    #[actix_web::test]
+    async fn problem_type_template() {
+        let app = test::init_service(
+            App::new()
+                .app_data(
+                    HateoasConfig::default()
+                        .problem_type_template("https://errors.example.com/{code}"),
+                )
+                .route("/", web::post().to(echo)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/")
+            .insert_header((header::CONTENT_TYPE, "application/problem+json"))
+            .insert_header((header::ACCEPT, "application/problem+json"))
+            .set_payload(
+                r#"{"type":"https://errors.example.com/1042","title":"Bad Request","status":400}"#,
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let result = test::read_body(res).await;
+        let problem = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(problem["type"], "https://errors.example.com/1042");
+    }
+}
@@ -0,0 +1,219 @@
+use crate::frameworks::actix::body::read_payload;
+use crate::frameworks::actix::config::HateoasConfig;
+use crate::frameworks::actix::error::ActixError;
+use crate::frameworks::actix::respond;
+use crate::frameworks::compression;
+use crate::frameworks::conversion::{
+    codec, ConversionError, ConversionRegistry, Convertible, Downconverted, RequestedVersion,
+};
+use crate::frameworks::rejection::Rejection;
+use crate::{Hateoas, HateoasResource};
+use actix_web::body::BoxBody;
+use actix_web::error::InternalError;
+use actix_web::http::header;
+use actix_web::web::Data;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder};
+use futures_core::future::LocalBoxFuture;
+use std::fmt::Display;
+use std::future::{ready, Ready};
+
+/// # Converted
+/// Extractor accepting any version of `T` registered in the [`ConversionRegistry`] of the hub,
+/// the payload is converted through the hub into `T`. Payloads without an `apiVersion` are decoded
+/// as `T`.
+///
+/// The registry is read from the app data, the request is rejected with a [`Hateoas`] error the
+/// same way as for the `Hateoas<T>` extractor.
+///
+/// ```ignore
+/// async fn create(Converted(bullet): Converted<BulletV2>) -> Hateoas<BulletV2> {
+///     bullet
+/// }
+///
+/// App::new()
+///     .app_data(web::Data::new(registry))
+///     .route("/bullets", web::post().to(create));
+/// ```
+pub struct Converted<T: Convertible>(pub Hateoas<T>);
+
+impl<T> FromRequest for Converted<T>
+where
+    T: Convertible + TryFrom<T::Hub>,
+    <T as TryFrom<T::Hub>>::Error: Display,
+{
+    type Error = ActixError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut actix_http::Payload) -> Self::Future {
+        let registry = req.app_data::<Data<ConversionRegistry<T::Hub>>>().cloned();
+        let config = HateoasConfig::from_req(req).clone();
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|t| t.to_str().ok())
+            .map(codec);
+        let content_encoding = req
+            .headers()
+            .get_all(header::CONTENT_ENCODING)
+            .filter_map(|t| t.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        let content_encoding = (!content_encoding.is_empty()).then_some(content_encoding);
+        let length = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|t| t.to_str().ok())
+            .and_then(|t| t.parse::<usize>().ok());
+        let payload = payload.take();
+        let req = req.clone();
+
+        Box::pin(async move {
+            let registry = match registry {
+                Some(registry) => registry,
+                None => {
+                    let message = format!("No conversion registry found for kind {}", T::kind());
+                    let response =
+                        Hateoas::<()>::INTERNAL_SERVER_ERROR(None, Some(message.clone()))
+                            .respond_to(&req);
+                    return Err(ActixError::Handler(
+                        InternalError::from_response(message, response).into(),
+                    ));
+                }
+            };
+            let content_type = content_type
+                .ok_or_else(|| config.reject_request(Rejection::MissingContentType, &req))?;
+            let bytes = read_payload(payload, length, &config)
+                .await
+                .map_err(|e| config.reject_request(e.into(), &req))?;
+            let bytes = compression::decompress(content_encoding.as_deref(), bytes, config.limit)
+                .map_err(|e| config.reject_request(e.into(), &req))?;
+
+            registry
+                .decode(
+                    &bytes,
+                    &content_type,
+                    &format!("{}/{}", T::GROUP, T::VERSION),
+                )
+                .and_then(|hub| registry.convert::<T>(hub))
+                .map(Converted)
+                .map_err(|e| config.reject_request(e.into(), &req))
+        })
+    }
+}
+
+impl FromRequest for RequestedVersion {
+    type Error = ActixError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_http::Payload) -> Self::Future {
+        let accept = req
+            .headers()
+            .get_all(header::ACCEPT)
+            .filter_map(|t| t.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        let query = req.query_string();
+        ready(Ok(RequestedVersion::new(
+            (!accept.is_empty()).then_some(accept),
+            (!query.is_empty()).then(|| query.to_string()),
+        )))
+    }
+}
+
+impl Responder for ConversionError {
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        Hateoas::<()>::from(self).respond_to(req)
+    }
+}
+
+impl<H> Responder for Downconverted<H>
+where
+    H: HateoasResource + Clone + Send + Sync + 'static,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        let version = self
+            .api_version()
+            .and_then(|t| t.rsplit('/').next())
+            .unwrap_or(H::VERSION)
+            .to_string();
+        let (hub, _, encode) = self.into_parts();
+        respond(hub, req, &version, encode)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Converted;
+    use crate::frameworks::conversion::test::{registry, BulletHub, BulletV2};
+    use crate::frameworks::conversion::{ConversionRegistry, RequestedVersion};
+    use crate::Hateoas;
+    use actix_web::{http::header, test, web, App};
+
+    async fn echo(
+        registry: web::Data<ConversionRegistry<BulletHub>>,
+        requested: RequestedVersion,
+        Converted(bullet): Converted<BulletV2>,
+    ) -> impl actix_web::Responder {
+        let hub = bullet
+            .try_map(|t| Ok::<BulletHub, String>(t.into()))
+            .unwrap();
+        requested.respond(registry.into_inner(), hub)
+    }
+
+    #[cfg(feature = "gzip")]
+    #[actix_web::test]
+    async fn accepts_compressed_payload() {
+        use crate::frameworks::compression::Encoding;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry()))
+                .route("/bullets", web::post().to(echo)),
+        )
+        .await;
+
+        let payload = r#"{"apiVersion":"ammo.hateoas.io/v2","kind":"Bullet","spec":{"content":{"caliber":9,"material":"lead"}}}"#;
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header(header::ContentType::json())
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .insert_header((header::ACCEPT, "application/json; version=v2"))
+            .set_payload(Encoding::Gzip.compress(payload.as_bytes()).unwrap())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+        let result = test::read_body(res).await;
+
+        let bullet = serde_json::from_slice::<Hateoas<BulletV2>>(&result).unwrap();
+        assert_eq!(bullet.api_version(), "ammo.hateoas.io/v2");
+    }
+
+    #[actix_web::test]
+    async fn failed_conversion_is_returned_as_error() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry()))
+                .route("/bullets", web::post().to(echo)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header(header::ContentType::json())
+            .insert_header((header::ACCEPT, "application/json; version=v1"))
+            .set_payload(r#"{"apiVersion":"ammo.hateoas.io/v1","kind":"Bullet","spec":{"content":{"caliber":9}}}"#)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let result = test::read_body(res).await;
+
+        let bullet = serde_json::from_slice::<Hateoas<()>>(&result).unwrap();
+        assert_eq!(
+            bullet.status().and_then(|t| t.http_status_code().clone()),
+            Some(crate::StatusCode::UnprocessableEntity)
+        );
+    }
+}
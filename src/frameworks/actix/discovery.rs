@@ -0,0 +1,113 @@
+use crate::{APIGroupList, APIResourceList, Hateoas, Representation, ResourceRegistry};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+
+/// # Discovery service configuration
+/// Mounts `/apis` and `/apis/{group}/{version}` serving the discovery documents rendered from the
+/// [`ResourceRegistry`] registered as app data, and `/apis/{group}/{version}/context` serving the
+/// JSON-LD context.
+///
+/// ```ignore
+/// let mut registry = ResourceRegistry::new();
+/// registry.register::<RubberBullet>(vec![HttpMethod::Get]);
+///
+/// App::new()
+///     .app_data(web::Data::new(registry))
+///     .configure(discovery::configure);
+/// ```
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.route("/apis", web::get().to(api_group_list))
+        .route("/apis/{group}/{version}", web::get().to(api_resource_list))
+        .route(
+            "/apis/{group}/{version}/context",
+            web::get().to(json_ld_context),
+        );
+}
+
+async fn api_group_list(registry: web::Data<ResourceRegistry>) -> Hateoas<APIGroupList> {
+    registry.api_group_list()
+}
+
+async fn api_resource_list(
+    registry: web::Data<ResourceRegistry>,
+    path: web::Path<(String, String)>,
+) -> Hateoas<APIResourceList> {
+    let (group, version) = path.into_inner();
+    registry.api_resource_list(&group, &version)
+}
+
+async fn json_ld_context(
+    req: HttpRequest,
+    registry: web::Data<ResourceRegistry>,
+    path: web::Path<(String, String)>,
+) -> HttpResponse {
+    let (group, version) = path.into_inner();
+    match registry.json_ld_context(&group, &version) {
+        Some(context) => HttpResponse::Ok()
+            .content_type(Representation::JsonLd.media_type())
+            .body(context.to_string()),
+        None => Hateoas::<()>::NOT_FOUND(
+            None,
+            Some(format!("No resources registered for {}/{}", group, version)),
+        )
+        .respond_to(&req),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::configure;
+    use crate::{APIGroupList, Hateoas, HttpMethod, ResourceRegistry};
+    use actix_web::{test, web, App};
+
+    #[actix_web::test]
+    async fn api_group_list_route() {
+        let mut registry = ResourceRegistry::new();
+        registry.register::<String>(vec![HttpMethod::Get]);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry))
+                .configure(configure),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/apis").to_request();
+        let res = test::call_service(&app, req).await;
+        let result = test::read_body(res).await;
+
+        let groups = serde_json::from_slice::<Hateoas<APIGroupList>>(&result).unwrap();
+        let content = groups.spec().and_then(|t| t.content().clone()).unwrap();
+        assert_eq!(content.groups[0].name, "hateoas.io");
+    }
+
+    #[actix_web::test]
+    async fn json_ld_context_route() {
+        let mut registry = ResourceRegistry::new();
+        registry.register::<String>(vec![HttpMethod::Get]);
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(registry))
+                .configure(configure),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/apis/hateoas.io/0.0.1/context")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers()
+                .get(actix_web::http::header::CONTENT_TYPE)
+                .unwrap(),
+            "application/ld+json"
+        );
+        let result = test::read_body(res).await;
+
+        let context = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(
+            context["@context"]["String"]["@id"],
+            "/apis/hateoas.io/0.0.1/context#String"
+        );
+    }
+}
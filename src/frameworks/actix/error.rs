@@ -1,6 +1,10 @@
+use crate::frameworks::compression::CompressionError;
+use crate::frameworks::rejection::Rejection;
+use crate::{PaginationError, RepresentationError};
 use actix_web::error::PayloadError;
-use actix_web::http::header::ToStrError;
-use actix_web::ResponseError;
+use actix_web::http::header::{ContentType, ToStrError};
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
 
 #[derive(Debug, Display)]
 #[non_exhaustive]
@@ -21,6 +25,10 @@ pub enum ActixError {
     #[display(fmt = "Content type error")]
     ContentType,
 
+    /// The content type is not supported or not allowed
+    #[display(fmt = "Content type {} is not supported", _0)]
+    UnsupportedContentType(String),
+
     /// Deserialize error
     #[display(fmt = "Deserialize error: {:?}", _0)]
     Deserialize(simple_serde::Error),
@@ -58,9 +66,96 @@ pub enum ActixError {
 
     #[display(fmt = "Failed to get Content-Type from header")]
     FailedToGetContentTypeFromHeader,
+
+    #[display(fmt = "Invalid pagination query: {}", _0)]
+    Pagination(PaginationError),
+
+    #[display(fmt = "Representation error: {}", _0)]
+    Representation(RepresentationError),
+
+    #[display(fmt = "Compression error: {}", _0)]
+    Compression(CompressionError),
+
+    /// Error returned by the error handler of the [`HateoasConfig`](super::config::HateoasConfig)
+    #[display(fmt = "{}", _0)]
+    Handler(actix_web::Error),
 }
 
-impl ResponseError for ActixError {}
+impl ResponseError for ActixError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ActixError::Pagination(_) => StatusCode::BAD_REQUEST,
+            ActixError::OverflowKnownLength { .. } | ActixError::Overflow { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            ActixError::ContentType | ActixError::UnsupportedContentType(_) => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
+            ActixError::Handler(e) => e.as_response_error().status_code(),
+            ActixError::Compression(CompressionError::UnsupportedEncoding(_)) => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
+            ActixError::Compression(CompressionError::Overflow(_)) => StatusCode::PAYLOAD_TOO_LARGE,
+            ActixError::Compression(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            ActixError::Handler(e) => e.error_response(),
+            _ => HttpResponse::build(self.status_code())
+                .insert_header(ContentType::plaintext())
+                .body(self.to_string()),
+        }
+    }
+}
+
+impl From<ActixError> for Rejection {
+    fn from(e: ActixError) -> Self {
+        match e {
+            ActixError::OverflowKnownLength { length, limit } => Rejection::Overflow {
+                length: Some(length),
+                limit,
+            },
+            ActixError::Overflow { limit } => Rejection::Overflow {
+                length: None,
+                limit,
+            },
+            ActixError::ContentType | ActixError::FailedToGetContentTypeFromHeader => {
+                Rejection::MissingContentType
+            }
+            ActixError::UnsupportedContentType(t) => Rejection::UnsupportedContentType(t),
+            ActixError::Deserialize(e)
+            | ActixError::Serialize(e)
+            | ActixError::SerializationDeserializationError(e) => Rejection::from(e),
+            ActixError::Payload(e) => Rejection::Payload(e.to_string()),
+            ActixError::PayloadError(_, e) => Rejection::Payload(e.to_string()),
+            ActixError::Pagination(e) => Rejection::Pagination(e),
+            ActixError::Representation(e) => Rejection::Representation(e),
+            ActixError::Compression(e) => Rejection::Compression(e),
+            e => Rejection::Internal(e.to_string()),
+        }
+    }
+}
+
+impl From<RepresentationError> for ActixError {
+    fn from(e: RepresentationError) -> Self {
+        Self::Representation(e)
+    }
+}
+
+impl From<CompressionError> for ActixError {
+    fn from(e: CompressionError) -> Self {
+        Self::Compression(e)
+    }
+}
+
+impl From<PaginationError> for ActixError {
+    fn from(e: PaginationError) -> Self {
+        Self::Pagination(e)
+    }
+}
 
 impl From<ToStrError> for ActixError {
     fn from(e: ToStrError) -> Self {
@@ -1,6 +1,6 @@
 use crate::frameworks::actix::body::PayloadBody;
+use crate::frameworks::actix::config::HateoasConfig;
 use crate::frameworks::actix::error::ActixError;
-use crate::frameworks::payload_control::PayloadControl;
 use crate::{Hateoas, HateoasResource};
 use actix_web::HttpRequest;
 use serde::de::DeserializeOwned;
@@ -17,7 +17,7 @@ pub struct PayloadFuture<O, T> {
 
 impl<O, T> Unpin for PayloadFuture<O, T> {}
 
-impl<T: DeserializeOwned, O: PayloadControl> PayloadFuture<O, T> {
+impl<T: DeserializeOwned, O> PayloadFuture<O, T> {
     pub(crate) fn new(r: HttpRequest, p: &mut actix_http::Payload) -> PayloadFuture<O, T> {
         PayloadFuture {
             _req: Some(r.clone()),
@@ -27,9 +27,7 @@ impl<T: DeserializeOwned, O: PayloadControl> PayloadFuture<O, T> {
     }
 }
 
-impl<T: DeserializeOwned + HateoasResource + Clone, O: PayloadControl> Future
-    for PayloadFuture<O, T>
-{
+impl<T: DeserializeOwned + HateoasResource + Clone, O> Future for PayloadFuture<O, T> {
     type Output = Result<Hateoas<T>, ActixError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -40,11 +38,15 @@ impl<T: DeserializeOwned + HateoasResource + Clone, O: PayloadControl> Future
                 return std::task::Poll::Pending;
             }
         };
-        Poll::Ready(match res {
-            Err(err) => Ok(Hateoas::from(
-                Err(err.into()) as Result<Hateoas<T>, ActixError>
-            )),
-            Ok(data) => Ok(data),
+        Poll::Ready(match (res, &this._req) {
+            (Err(err), Some(req)) => {
+                let config = HateoasConfig::from_req(req);
+                Err(match &config.error_handler {
+                    Some(error_handler) => ActixError::Handler(error_handler(err, req)),
+                    None => config.reject_request(err.into(), req),
+                })
+            }
+            (res, _) => res,
         })
     }
 }
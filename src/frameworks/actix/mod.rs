@@ -1,21 +1,29 @@
 pub mod body;
+pub mod config;
+pub mod conversion;
+pub mod discovery;
 pub mod error;
 pub mod future;
+pub mod pagination;
+pub mod prefer;
 
+use crate::frameworks::actix::config::HateoasConfig;
 use crate::frameworks::actix::error::ActixError;
 use crate::frameworks::actix::future::PayloadFuture;
-use crate::frameworks::payload_control::PayloadControl;
-use crate::{Hateoas, HateoasResource};
+use crate::frameworks::prefer::Preferences;
+use crate::frameworks::rejection::Rejection;
+use crate::frameworks::{compression, negotiation};
+use crate::header::HeaderKey;
+use crate::{Hateoas, HateoasResource, Status};
 use actix_web::body::BoxBody;
-use actix_web::http::StatusCode;
+use actix_web::http::{header, Method, StatusCode};
 use actix_web::{FromRequest, HttpRequest, HttpResponse, HttpResponseBuilder, Responder};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use simple_serde::{ContentType, SimpleEncoder};
 
 impl<T> FromRequest for Hateoas<T>
 where
-    T: DeserializeOwned + PayloadControl + HateoasResource + Clone,
+    T: DeserializeOwned + HateoasResource + Clone,
 {
     type Error = ActixError;
     type Future = PayloadFuture<T, T>;
@@ -33,31 +41,133 @@ where
     type Body = BoxBody;
 
     fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
-        let mut content_type_collection = req
-            .headers()
-            .get_all("Accept")
-            .filter_map(|h| ContentType::try_from(h).ok())
-            .collect::<Vec<ContentType>>();
-        if content_type_collection.is_empty() {
-            content_type_collection = vec![ContentType::Json];
-        }
+        respond(self, req, T::VERSION, |hateoas, media_type, type_template| {
+            negotiation::encode(hateoas, media_type, type_template)
+                .ok_or(("", StatusCode::INTERNAL_SERVER_ERROR))
+        })
+    }
+}
 
-        let response_code = StatusCode::OK;
-        content_type_collection.reverse();
-        content_type_collection
-            .pop()
-            .ok_or_else(|| HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR))
-            .and_then(|content_type| {
-                self.encode(&content_type)
-                    .map(|t| {
-                        HttpResponseBuilder::new(response_code)
-                            .content_type(content_type)
-                            .body(t.to_vec())
-                    })
-                    .map_err(|_| HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR))
-            })
-            .unwrap_or_else(|e| e)
+/// Responding with the body created by `encode` for the media type negotiated with the request,
+/// the `version` is the version of `T` the body is encoded as.
+pub(crate) fn respond<T, F, E>(
+    mut hateoas: Hateoas<T>,
+    req: &HttpRequest,
+    version: &str,
+    encode: F,
+) -> HttpResponse
+where
+    T: HateoasResource + Clone,
+    F: FnOnce(&Hateoas<T>, &'static str, Option<&str>) -> Result<Vec<u8>, E>,
+    E: Responder,
+{
+    let type_template = HateoasConfig::from_req(req).problem_type_template.as_deref();
+    let status_code = hateoas
+        .status()
+        .and_then(|t| t.http_status_code().as_ref())
+        .and_then(|t| StatusCode::from_u16(u16::from(t)).ok())
+        .unwrap_or(StatusCode::OK);
+    let body_less = matches!(
+        status_code,
+        StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED
+    );
+    if body_less || req.method() == Method::HEAD {
+        hateoas.add_link_header();
     }
+
+    let accept = req
+        .headers()
+        .get_all(header::ACCEPT)
+        .filter_map(|h| h.to_str().ok())
+        .collect::<Vec<&str>>()
+        .join(",");
+    let media_type = match negotiation::select(
+        Some(&accept),
+        status_code.is_client_error() || status_code.is_server_error(),
+    ) {
+        Some(media_type) => media_type,
+        None => {
+            let media_type = negotiation::select(Some(&accept), true).unwrap_or("application/json");
+            return negotiation::encode(&negotiation::not_acceptable(), media_type, type_template)
+                .map(|t| {
+                    HttpResponseBuilder::new(StatusCode::NOT_ACCEPTABLE)
+                        .insert_header((header::VARY, "Accept"))
+                        .content_type(media_type)
+                        .body(t)
+                })
+                .unwrap_or_else(|| HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR));
+        }
+    };
+
+    let prefer = req
+        .headers()
+        .get_all(HeaderKey::Prefer.to_string())
+        .filter_map(|h| h.to_str().ok())
+        .collect::<Vec<&str>>()
+        .join(",");
+    let preference_applied = Preferences::parse(&prefer).apply(req.method().as_str(), &mut hateoas);
+    let accept_encoding = req
+        .headers()
+        .get_all(header::ACCEPT_ENCODING)
+        .filter_map(|h| h.to_str().ok())
+        .collect::<Vec<&str>>()
+        .join(",");
+    let headers = response_headers(&hateoas);
+    encode(&hateoas, media_type, type_template)
+        .map(|t| {
+            let mut response = HttpResponseBuilder::new(status_code);
+            for (name, value) in headers {
+                response.append_header((name, value));
+            }
+            response.append_header((header::VARY, "Accept"));
+            if compression::varies() {
+                response.append_header((header::VARY, "Accept-Encoding"));
+            }
+            if let Some(preference_applied) = preference_applied {
+                response.insert_header((HeaderKey::PreferenceApplied.to_string(), preference_applied));
+                response.append_header((header::VARY, HeaderKey::Prefer.to_string()));
+            }
+            if body_less {
+                return response.finish();
+            }
+            let (encoding, t) = compression::compress(Some(&accept_encoding), t);
+            if encoding != compression::Encoding::Identity {
+                response.insert_header((header::CONTENT_ENCODING, encoding.token()));
+            }
+            response
+                .content_type(negotiation::version_content_type::<T>(
+                    Some(&accept),
+                    media_type,
+                    version,
+                ))
+                .body(t)
+        })
+        .unwrap_or_else(|e| e.respond_to(req).map_into_boxed_body())
+}
+
+/// The headers describing the body are set by the responder and not copied from the response.
+const BODY_HEADERS: [header::HeaderName; 4] = [
+    header::CONTENT_TYPE,
+    header::CONTENT_LENGTH,
+    header::CONTENT_ENCODING,
+    header::TRANSFER_ENCODING,
+];
+
+/// The headers of the metadata and the status of the response, in that order.
+fn response_headers<T: HateoasResource + Clone>(hateoas: &Hateoas<T>) -> header::HeaderMap {
+    [
+        hateoas.metadata().and_then(|t| t.headers().clone()),
+        hateoas.status().and_then(|t| t.headers().clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|t| header::HeaderMap::try_from(t).ok())
+    .flat_map(|t| t.into_iter())
+    .filter(|(name, _)| !BODY_HEADERS.contains(name))
+    .fold(header::HeaderMap::new(), |mut headers, (name, value)| {
+        headers.append(name, value);
+        headers
+    })
 }
 
 impl<T> From<ActixError> for Hateoas<T>
@@ -66,36 +176,18 @@ where
 {
     fn from(e: ActixError) -> Self {
         match e {
-            ActixError::OverflowKnownLength { .. } => Hateoas::PAYLOAD_TOO_LARGE(
+            ActixError::Handler(e) => Hateoas::new(
                 None,
-                Some("Content not matching expected length".to_string()),
+                None,
+                Some(Status::new(
+                    Some(&e.to_string()),
+                    None,
+                    Some(e.as_response_error().status_code().as_u16()),
+                    None,
+                    None,
+                )),
             ),
-            ActixError::Overflow { .. } => {
-                Hateoas::PAYLOAD_TOO_LARGE(None, Some("Payload too large".to_string()))
-            }
-            ActixError::ContentType => {
-                Hateoas::UNPROCESSABLE_ENTITY(None, Some("Unknown content type".to_string()))
-            }
-            ActixError::Deserialize(_) => {
-                Hateoas::UNPROCESSABLE_ENTITY(None, Some("Unknown format".to_string()))
-            }
-            ActixError::Serialize(_) => {
-                Hateoas::UNPROCESSABLE_ENTITY(None, Some("Unknown format".to_string()))
-            }
-            ActixError::Payload(_) => Hateoas::INTERNAL_SERVER_ERROR(None, None),
-            ActixError::PayloadError(_, _) => Hateoas::INTERNAL_SERVER_ERROR(None, None),
-            ActixError::NoPayloadSizeDefinitionInHeader => {
-                Hateoas::INTERNAL_SERVER_ERROR(None, None)
-            }
-            ActixError::FailedToMapHeaderToStr(_) => Hateoas::INTERNAL_SERVER_ERROR(None, None),
-            ActixError::SerializationDeserializationError(_) => {
-                Hateoas::UNPROCESSABLE_ENTITY(None, Some("Unknown format".to_string()))
-            }
-            ActixError::Infallible => Hateoas::INTERNAL_SERVER_ERROR(None, None),
-            ActixError::FailedToParseToInt(_) => Hateoas::INTERNAL_SERVER_ERROR(None, None),
-            ActixError::FailedToGetContentTypeFromHeader => {
-                Hateoas::INTERNAL_SERVER_ERROR(None, None)
-            }
+            e => Rejection::from(e).into(),
         }
     }
 }
@@ -176,6 +268,11 @@ mod test {
         const URL_PATH_SEGMENT: &'static str = "";
     }
 
+    impl crate::frameworks::payload_control::PayloadControl for RubberBullet {
+        const MAX_PAYLOAD_SIZE: Option<usize> = None;
+        const BUFFER_CAPACITY: Option<usize> = None;
+    }
+
     const RICKSPONSE_1: &str = r##"
 
 
@@ -229,6 +326,355 @@ mod test {
         assert_eq!(content, response);
     }
 
+    #[actix_web::test]
+    async fn test_hateoas_list() {
+        let app = test::init_service(App::new().service(web::resource("/strings").route(
+            web::get().to(|| async {
+                crate::HateoasList::OK(
+                    Some(crate::ResourceList::from(vec!["foo".to_string(), "bar".to_string()])),
+                    None,
+                )
+            }),
+        )))
+        .await;
+
+        let req = test::TestRequest::get().uri("/strings").to_request();
+        let res = test::call_service(&app, req).await;
+        let result = test::read_body(res).await;
+
+        let content = serde_json::from_slice::<crate::HateoasList<String>>(&result).unwrap();
+        assert_eq!(content.kind(), "StringList");
+        assert_eq!(
+            content.spec().and_then(|t| t.content().as_ref()).map(|t| t.items().len()),
+            Some(2)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_hal() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/hal+json"))
+            .insert_header((header::ACCEPT, "application/hal+json"))
+            .set_payload(
+                r#"{"name":"Rubber Bullet","title":"The Bullet","chapter":"1","_links":{"self":{"href":"/bullets/1"}}}"#,
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/hal+json"
+        );
+        let result = test::read_body(res).await;
+
+        let value = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(value["chapter"], "1");
+        assert_eq!(value["_links"]["self"]["href"], "/bullets/1");
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_json_api() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/vnd.api+json"))
+            .insert_header((header::ACCEPT, "application/vnd.api+json"))
+            .set_payload(
+                r#"{"data":{"type":"","id":"1","attributes":{"name":"Rubber Bullet","title":"The Bullet","chapter":"1"},"links":{"self":"/bullets/1"}}}"#,
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.api+json"
+        );
+        let result = test::read_body(res).await;
+
+        let value = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(value["data"]["id"], "1");
+        assert_eq!(value["data"]["attributes"]["chapter"], "1");
+        assert_eq!(value["data"]["links"]["self"], "/bullets/1");
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_siren() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/vnd.siren+json"))
+            .insert_header((header::ACCEPT, "application/vnd.siren+json"))
+            .set_payload(
+                r#"{"properties":{"name":"Rubber Bullet","title":"The Bullet","chapter":"1"},"links":[{"rel":["self"],"href":"/bullets/1"}],"actions":[{"name":"fire","method":"POST","href":"/bullets/1/fire","type":"application/json"}]}"#,
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.siren+json"
+        );
+        let result = test::read_body(res).await;
+
+        let value = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(value["properties"]["chapter"], "1");
+        assert_eq!(value["links"][0]["href"], "/bullets/1");
+        assert_eq!(
+            value["actions"][0],
+            serde_json::json!({"name": "fire", "method": "POST", "href": "/bullets/1/fire", "type": "application/json"})
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_collection_json() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/vnd.collection+json"))
+            .insert_header((header::ACCEPT, "application/vnd.collection+json"))
+            .set_payload(
+                r#"{"template":{"data":[{"name":"name","value":"Rubber Bullet"},{"name":"title","value":"The Bullet"},{"name":"chapter","value":"1"}]}}"#,
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.collection+json"
+        );
+        let result = test::read_body(res).await;
+
+        let value = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(
+            value["collection"]["items"][0]["data"][2],
+            serde_json::json!({"name": "chapter", "value": "1"})
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_json_ld() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::get().to(|| async { Hateoas::OK(Some(RubberBullet::default()), None) }),
+        )))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/bullets")
+            .insert_header((header::ACCEPT, "application/ld+json"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/ld+json"
+        );
+        let result = test::read_body(res).await;
+
+        let value = serde_json::from_slice::<serde_json::Value>(&result).unwrap();
+        assert_eq!(value["@type"], "");
+        assert_eq!(value["chapter"], "A Rubber Bullet Hurts");
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_head_link_header() {
+        let app = test::init_service(App::new().service(web::resource("/bullets/9").route(
+            web::head().to(|| async {
+                let mut response = Hateoas::OK(Some(RubberBullet::default()), None);
+                response
+                    .spec_mut()
+                    .rel()
+                    .add(crate::RelLink::GET("self", "/bullets/9"));
+                response
+            }),
+        )))
+        .await;
+
+        let req = test::TestRequest::default()
+            .method(actix_web::http::Method::HEAD)
+            .uri("/bullets/9")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(
+            res.headers().get(header::LINK).unwrap(),
+            r#"</bullets/9>; rel="self""#
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_problem_details() {
+        let app = test::init_service(App::new().service(web::resource("/bullets/9").route(
+            web::get().to(|| async {
+                Hateoas::<RubberBullet>::NOT_FOUND(None, Some("No bullet with id 9".to_string()))
+            }),
+        )))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/bullets/9")
+            .insert_header((header::ACCEPT, "application/hal+json, application/problem+xml"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/problem+xml"
+        );
+        let result = test::read_body(res).await;
+
+        let raw_str = std::str::from_utf8(&result).unwrap();
+        assert!(raw_str.contains("<status>404</status><detail>No bullet with id 9</detail>"));
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_not_acceptable() {
+        let app = test::init_service(App::new().service(web::resource("/bullets/9").route(
+            web::get().to(|| async { Hateoas::OK(Some(RubberBullet::default()), None) }),
+        )))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/bullets/9")
+            .insert_header((header::ACCEPT, "text/html"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept");
+        let result = test::read_body(res).await;
+
+        let content = serde_json::from_slice::<Hateoas<()>>(&result).unwrap();
+        assert!(content
+            .status()
+            .and_then(|t| t.message().as_ref())
+            .unwrap()
+            .contains("application/vnd.siren+json"));
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_quality_negotiation() {
+        let app = test::init_service(App::new().service(web::resource("/bullets/9").route(
+            web::get().to(|| async { Hateoas::OK(Some(RubberBullet::default()), None) }),
+        )))
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/bullets/9")
+            .insert_header((
+                header::ACCEPT,
+                "application/json;q=0.4, application/hal+json;q=0.8, */*;q=0.1",
+            ))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/hal+json"
+        );
+        assert_eq!(res.headers().get(header::VARY).unwrap(), "Accept");
+    }
+
+    #[cfg(feature = "compression")]
+    #[actix_web::test]
+    async fn test_hateoas_compression() {
+        use crate::frameworks::compression::Encoding;
+
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let chapter = "A Rubber Bullet Hurts. ".repeat(100);
+        let payload = serde_json::to_vec(&RubberBullet {
+            chapter: chapter.clone(),
+            ..RubberBullet::default()
+        })
+        .unwrap();
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header(header::ContentType::json())
+            .insert_header((header::CONTENT_ENCODING, "gzip"))
+            .insert_header((header::ACCEPT_ENCODING, "gzip;q=0.5, zstd"))
+            .set_payload(Encoding::Gzip.compress(&payload).unwrap())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::OK);
+        assert_eq!(res.headers().get(header::CONTENT_ENCODING).unwrap(), "zstd");
+        assert_eq!(
+            res.headers()
+                .get_all(header::VARY)
+                .map(|t| t.to_str().unwrap())
+                .collect::<Vec<&str>>(),
+            vec!["Accept", "Accept-Encoding"]
+        );
+        let result = test::read_body(res).await;
+
+        let content = Encoding::Zstd.decompress(&result, usize::MAX).unwrap();
+        let content = serde_json::from_slice::<Hateoas<RubberBullet>>(&content).unwrap();
+        assert_eq!(
+            content.spec().and_then(|t| t.content().as_ref()).map(|t| &t.chapter),
+            Some(&chapter)
+        );
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct AmmoBullet {
+        name: String,
+    }
+
+    impl crate::HateoasResource for AmmoBullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    #[actix_web::test]
+    async fn test_hateoas_vendor_media_type() {
+        let app = test::init_service(App::new().service(web::resource("/bullets").route(
+            web::post().to(|bullet: Hateoas<AmmoBullet>| async move { bullet }),
+        )))
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/vnd.ammo.bullet.v1+json"))
+            .insert_header((header::ACCEPT, "application/vnd.ammo.bullet.v1+yaml"))
+            .set_payload(r#"{"name":"Rubber Bullet","title":"The Bullet","chapter":"1"}"#)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/vnd.ammo.bullet.v1+yaml"
+        );
+        let result = test::read_body(res).await;
+
+        let yaml = String::from_utf8(result.to_vec()).unwrap();
+        assert!(yaml.contains("name: Rubber Bullet"));
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header((header::CONTENT_TYPE, "application/vnd.ammo.bullet.v1+json"))
+            .insert_header((header::ACCEPT, "application/vnd.ammo.shell.v2+yaml"))
+            .set_payload(r#"{"name":"Rubber Bullet"}"#)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/yaml"
+        );
+    }
+
     #[actix_web::test]
     async fn test_for_automated_impl_hateoas() {
         let rickhateoas: Hateoas<String> = Hateoas::OK(Some("test".to_string()), None);
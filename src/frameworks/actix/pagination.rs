@@ -0,0 +1,84 @@
+use crate::frameworks::actix::config::HateoasConfig;
+use crate::frameworks::actix::error::ActixError;
+use crate::frameworks::rejection::Rejection;
+use crate::{PaginationParams, PaginationQuery};
+use actix_web::{FromRequest, HttpRequest};
+use std::future::{ready, Ready};
+
+/// Extracting the pagination parameters from the query string of the request, invalid parameters
+/// are rejected with a `BAD_REQUEST` [`Hateoas`](crate::Hateoas) error through the [`HateoasConfig`] of the
+/// request.
+///
+/// ```ignore
+/// async fn list(pagination: PaginationQuery<PageParams>) -> HateoasList<Bullet> {
+///     let mut list = ResourceList::from(bullets(&pagination.params));
+///     let links = pagination.links(&PageInfo::Total(total));
+///     ...
+/// }
+/// ```
+impl<P> FromRequest for PaginationQuery<P>
+where
+    P: PaginationParams,
+{
+    type Error = ActixError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_http::Payload) -> Self::Future {
+        ready(
+            PaginationQuery::from_uri(&req.uri().to_string()).map_err(|e| {
+                HateoasConfig::from_req(req).reject_request(Rejection::Pagination(e), req)
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Hateoas, HateoasList, PageInfo, PageParams, PaginationQuery, ResourceList};
+    use actix_web::http::StatusCode;
+    use actix_web::{test, web, App};
+
+    async fn list(pagination: PaginationQuery<PageParams>) -> HateoasList<u32> {
+        let mut list = HateoasList::OK(Some(ResourceList::default()), None);
+        *list.spec_mut().rel() = pagination.links(&PageInfo::HasMore(true));
+        list
+    }
+
+    #[actix_web::test]
+    async fn pagination_links_from_query() {
+        let app = test::init_service(App::new().route("/numbers", web::get().to(list))).await;
+
+        let req = test::TestRequest::get()
+            .uri("/numbers?page=2&per_page=10")
+            .to_request();
+        let result = test::call_and_read_body(&app, req).await;
+
+        let list = serde_json::from_slice::<HateoasList<u32>>(&result).unwrap();
+        let mut spec = list.spec().cloned().unwrap();
+        let rel = spec.rel();
+        assert_eq!(
+            rel.get("prev").unwrap().href(),
+            "/numbers?page=1&per_page=10"
+        );
+        assert_eq!(
+            rel.get("next").unwrap().href(),
+            "/numbers?page=3&per_page=10"
+        );
+    }
+
+    #[actix_web::test]
+    async fn invalid_pagination_query() {
+        let app = test::init_service(App::new().route("/numbers", web::get().to(list))).await;
+
+        let req = test::TestRequest::get().uri("/numbers?page=x").to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let result = test::read_body(res).await;
+        let error = serde_json::from_slice::<Hateoas<()>>(&result).unwrap();
+        assert_eq!(
+            error.status().and_then(|t| t.http_status_code().clone()),
+            Some(crate::StatusCode::BadRequest)
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::frameworks::actix::error::ActixError;
+use crate::frameworks::prefer::Preferences;
+use actix_web::{FromRequest, HttpRequest};
+use std::future::{ready, Ready};
+
+/// Extracting the preferences of the `Prefer` headers of the request, requests without the header
+/// have no preferences.
+///
+/// ```ignore
+/// async fn create(preferences: Preferences, bullet: Hateoas<Bullet>) -> Hateoas<Bullet> {
+///     if preferences.handling() == Some(Handling::Strict) {
+///         ...
+///     }
+/// }
+/// ```
+impl FromRequest for Preferences {
+    type Error = ActixError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut actix_http::Payload) -> Self::Future {
+        let prefer = req
+            .headers()
+            .get_all("prefer")
+            .filter_map(|t| t.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        ready(Ok(Preferences::parse(&prefer)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frameworks::prefer::{Preferences, ReturnPreference};
+    use crate::Hateoas;
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{test, web, App};
+
+    async fn create(preferences: Preferences) -> Hateoas<String> {
+        let content = match preferences.return_preference() {
+            Some(ReturnPreference::Minimal) => "minimal",
+            _ => "bullet",
+        };
+        Hateoas::CREATED(Some(content.to_string()), None)
+    }
+
+    #[actix_web::test]
+    async fn return_minimal() {
+        let app = test::init_service(App::new().route("/bullets", web::post().to(create))).await;
+
+        let req = test::TestRequest::post()
+            .uri("/bullets")
+            .insert_header(("Prefer", "respond-async, return=minimal"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get("preference-applied").unwrap(),
+            "return=minimal"
+        );
+        assert!(res.headers().get_all(header::VARY).any(|t| t == "Prefer"));
+        let result = test::read_body(res).await;
+
+        let content = serde_json::from_slice::<Hateoas<String>>(&result).unwrap();
+        assert_eq!(content.spec().and_then(|t| t.content().as_ref()), None);
+    }
+
+    #[actix_web::test]
+    async fn without_preferences() {
+        let app = test::init_service(App::new().route("/bullets", web::post().to(create))).await;
+
+        let req = test::TestRequest::post().uri("/bullets").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CREATED);
+        assert!(res.headers().get("preference-applied").is_none());
+        let result = test::read_body(res).await;
+
+        let content = serde_json::from_slice::<Hateoas<String>>(&result).unwrap();
+        assert_eq!(
+            content.spec().and_then(|t| t.content().as_ref()),
+            Some(&"bullet".to_string())
+        );
+    }
+}
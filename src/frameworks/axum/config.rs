@@ -0,0 +1,308 @@
+use crate::frameworks::media_type::essence;
+use crate::frameworks::rejection::{Rejection, RejectionHandler};
+use crate::Hateoas;
+use axum::http::Request;
+use std::sync::Arc;
+
+/// # HateoasExtractorConfig
+/// Configuration of the [`Hateoas`](crate::Hateoas) extractor, read from the request extensions,
+/// eg. added with an `Extension` layer on the router or on a route.
+/// Without a config the body limit of axum applies, all content types supported by `simple_serde`
+/// are accepted, the `Content-Type` header is required and bare resources are accepted.
+///
+/// ```
+/// use axum::routing::post;
+/// use axum::{Extension, Router};
+/// use hateoas::frameworks::axum::config::HateoasExtractorConfig;
+/// use hateoas::Hateoas;
+///
+/// let app: Router = Router::new()
+///     .route("/", post(|t: Hateoas<String>| async move { t }))
+///     .layer(Extension(
+///         HateoasExtractorConfig::default()
+///             .limit(4096)
+///             .content_types(["application/json", "application/yaml"])
+///             .default_content_type("application/json")
+///             .strict(true),
+///     ));
+/// ```
+#[derive(Clone, Default)]
+pub struct HateoasExtractorConfig {
+    pub(crate) limit: Option<usize>,
+    pub(crate) content_types: Option<Vec<String>>,
+    pub(crate) default_content_type: Option<String>,
+    pub(crate) strict: bool,
+    pub(crate) rejection_handler: Option<Arc<dyn RejectionHandler>>,
+    pub(crate) problem_type_template: Option<String>,
+}
+
+impl HateoasExtractorConfig {
+    /// ## Maximum size of the payload in bytes
+    /// The limit applies to the payload as received and to the decompressed payload, larger
+    /// payloads are rejected with `PAYLOAD_TOO_LARGE`.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// ## Allowed content types
+    /// Payloads with another `Content-Type` are rejected with `UNSUPPORTED_MEDIA_TYPE`, the
+    /// parameters of the media types are ignored when comparing.
+    pub fn content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.content_types = Some(
+            content_types
+                .into_iter()
+                .map(|t| essence(t.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// ## Content type of requests without a Content-Type header
+    /// The payload is decoded as the default content type instead of rejecting the request.
+    pub fn default_content_type(mut self, content_type: &str) -> Self {
+        self.default_content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// ## Strict envelope decoding
+    /// Strict decoding only accepts payloads wrapped in the [`Hateoas`](crate::Hateoas) envelope,
+    /// lenient decoding also accepts the bare resource.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// ## Mapping the rejections
+    /// The [`RejectionHandler`] creating the error envelope of rejected requests.
+    pub fn rejection_handler<H: RejectionHandler + 'static>(mut self, handler: H) -> Self {
+        self.rejection_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// ## Problem type template
+    /// The template the `type` URI of `application/problem+json` responses is generated from and
+    /// the code of problem payloads is read from, see
+    /// [`ProblemDetails::type_uri`](crate::ProblemDetails::type_uri). Responses read the template
+    /// from the config in the request extensions, captured by the
+    /// [`NegotiationLayer`](crate::frameworks::axum::layer::NegotiationLayer), so the `Extension`
+    /// layer has to be added after the negotiation layer.
+    pub fn problem_type_template(mut self, template: &str) -> Self {
+        self.problem_type_template = Some(template.to_string());
+        self
+    }
+
+    pub(crate) fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+        match &self.rejection_handler {
+            Some(handler) => handler.reject(rejection),
+            None => rejection.into(),
+        }
+    }
+
+    /// Checking if the content type is allowed, every content type is allowed when no content
+    /// types are configured.
+    pub(crate) fn allows(&self, content_type: &str) -> bool {
+        self.content_types
+            .as_ref()
+            .map(|t| t.contains(&essence(content_type)))
+            .unwrap_or(true)
+    }
+
+    /// The config of the request extensions, the default config when there is none.
+    pub(crate) fn from_request<B>(req: &Request<B>) -> Self {
+        req.extensions().get::<Self>().cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HateoasExtractorConfig;
+    use crate::frameworks::axum::layer::NegotiationLayer;
+    use crate::frameworks::rejection::{DefaultRejectionHandler, Rejection, RejectionHandler};
+    use crate::{Hateoas, HateoasResource};
+    use axum::body::{Body, HttpBody};
+    use axum::http::{header, Method, Request, StatusCode};
+    use axum::routing::post;
+    use axum::{Extension, Router};
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt;
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct RubberBullet {
+        name: String,
+    }
+
+    impl HateoasResource for RubberBullet {
+        const KIND: &'static str = "RubberBullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "rubber_bullets";
+    }
+
+    const BULLET: &str = r#"{"name":"Strong bullet"}"#;
+    const ENVELOPE: &str = r#"{"apiVersion":"ammo.hateoas.io/v1","kind":"RubberBullet","spec":{"content":{"name":"Strong bullet"}}}"#;
+
+    fn app(config: HateoasExtractorConfig) -> Router {
+        Router::new()
+            .route(
+                "/",
+                post(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+            )
+            .layer(Extension(config))
+    }
+
+    fn post_request(body: &'static str, content_type: Option<&str>) -> Request<Body> {
+        let mut request = Request::builder().method(Method::POST).uri("/");
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+        request.body(Body::from(body)).unwrap()
+    }
+
+    async fn status(app: Router, request: Request<Body>) -> StatusCode {
+        match app.oneshot(request).await {
+            Ok(response) => response.status(),
+            Err(e) => match e {},
+        }
+    }
+
+    #[tokio::test]
+    async fn limit() {
+        let config = HateoasExtractorConfig::default().limit(8);
+
+        let request = post_request(BULLET, Some("application/json"));
+        assert_eq!(
+            status(app(config.clone()), request).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+
+        let mut request = post_request(BULLET, Some("application/json"));
+        request.headers_mut().insert(
+            header::CONTENT_LENGTH,
+            header::HeaderValue::from_static("24"),
+        );
+        assert_eq!(
+            status(app(config), request).await,
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+    }
+
+    #[tokio::test]
+    async fn limit_of_route() {
+        let app = Router::new().route(
+            "/",
+            post(|bullet: Hateoas<RubberBullet>| async move { bullet })
+                .layer(Extension(HateoasExtractorConfig::default().limit(8))),
+        );
+
+        let request = post_request(BULLET, Some("application/json"));
+        assert_eq!(status(app, request).await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn content_types() {
+        let config =
+            HateoasExtractorConfig::default().content_types(["Application/JSON; charset=utf-8"]);
+
+        let request = post_request(BULLET, Some("application/json"));
+        assert_eq!(status(app(config.clone()), request).await, StatusCode::OK);
+
+        let request = post_request("name: Strong bullet", Some("application/yaml"));
+        assert_eq!(
+            status(app(config), request).await,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    }
+
+    #[tokio::test]
+    async fn default_content_type() {
+        let request = post_request(BULLET, None);
+        assert_eq!(
+            status(app(HateoasExtractorConfig::default()), request).await,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+
+        let config = HateoasExtractorConfig::default().default_content_type("application/json");
+        let request = post_request(BULLET, None);
+        assert_eq!(status(app(config), request).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn strict() {
+        let config = HateoasExtractorConfig::default().strict(true);
+
+        let mut response = app(config.clone())
+            .oneshot(post_request(ENVELOPE, Some("application/json")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let data = response.data().await.unwrap().unwrap();
+        let hateoas = serde_json::from_slice::<Hateoas<RubberBullet>>(&data).unwrap();
+        assert_eq!(
+            hateoas.spec().and_then(|t| t.content().as_ref()),
+            Some(&RubberBullet {
+                name: "Strong bullet".to_string()
+            })
+        );
+
+        let request = post_request(BULLET, Some("application/json"));
+        assert_eq!(status(app(config), request).await, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn rejection_handler() {
+        let config = HateoasExtractorConfig::default().rejection_handler(|rejection: Rejection| {
+            match rejection {
+                Rejection::MissingContentType => {
+                    Hateoas::BAD_REQUEST(None, Some("Content-Type is required".to_string()))
+                }
+                rejection => DefaultRejectionHandler.reject(rejection),
+            }
+        });
+
+        let mut response = app(config.clone())
+            .oneshot(post_request(BULLET, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let data = response.data().await.unwrap().unwrap();
+        let hateoas = serde_json::from_slice::<Hateoas<()>>(&data).unwrap();
+        assert_eq!(
+            hateoas.status().and_then(|t| t.message().as_deref()),
+            Some("Content-Type is required")
+        );
+
+        let request = post_request("{", Some("application/json"));
+        assert_eq!(status(app(config), request).await, StatusCode::BAD_REQUEST);
+    }
+    #[tokio::test]
+    async fn problem_type_template() {
+        let config = HateoasExtractorConfig::default()
+            .problem_type_template("https://errors.example.com/{code}");
+
+        let mut request = post_request(
+            r#"{"type":"https://errors.example.com/1042","title":"Bad Request","status":400}"#,
+            Some("application/problem+json"),
+        );
+        request.headers_mut().insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static("application/problem+json"),
+        );
+        let app = Router::new()
+            .route(
+                "/",
+                post(|bullet: Hateoas<RubberBullet>| async move { bullet }),
+            )
+            .layer(NegotiationLayer)
+            .layer(Extension(config));
+        let mut response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let data = response.data().await.unwrap().unwrap();
+        let problem = serde_json::from_slice::<serde_json::Value>(&data).unwrap();
+        assert_eq!(problem["type"], "https://errors.example.com/1042");
+    }
+}
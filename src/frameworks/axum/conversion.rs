@@ -0,0 +1,265 @@
+use crate::frameworks::axum::config::HateoasExtractorConfig;
+use crate::frameworks::axum::{read_payload, respond};
+use crate::frameworks::conversion::{
+    codec, ConversionError, ConversionRegistry, Convertible, Downconverted, RequestedVersion,
+};
+use crate::{Hateoas, HeaderMap};
+use axum::async_trait;
+use axum::body::HttpBody;
+use axum::extract::{FromRequest, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::Request;
+use axum::response::{IntoResponse, Response};
+use axum_core::BoxError;
+use http::header;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// # Converted
+/// Extractor accepting any version of `T` registered in the [`ConversionRegistry`] of the hub,
+/// the payload is converted through the hub into `T`. Payloads without an `apiVersion` are decoded
+/// as `T`.
+///
+/// The registry is read from the request extensions, eg. added with
+/// `Extension(Arc::new(registry))`.
+///
+/// ```ignore
+/// async fn create(Converted(bullet): Converted<BulletV2>) -> Hateoas<BulletV2> {
+///     bullet
+/// }
+///
+/// let app = Router::new()
+///     .route("/bullets", post(create))
+///     .layer(Extension(Arc::new(registry)));
+/// ```
+pub struct Converted<T: Convertible>(pub Hateoas<T>);
+
+#[async_trait]
+impl<S, B, T> FromRequest<S, B> for Converted<T>
+where
+    T: Convertible + TryFrom<T::Hub>,
+    <T as TryFrom<T::Hub>>::Error: Display,
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = Hateoas<()>;
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        let config = HateoasExtractorConfig::from_request(&req);
+        let registry = req
+            .extensions()
+            .get::<Arc<ConversionRegistry<T::Hub>>>()
+            .cloned()
+            .ok_or_else(|| {
+                Hateoas::INTERNAL_SERVER_ERROR(
+                    None,
+                    Some(format!(
+                        "No conversion registry found for kind {}",
+                        T::kind()
+                    )),
+                )
+            })?;
+        let headers = HeaderMap::from(req.headers());
+        let (content_type, bytes) = read_payload(req, state, &config, &headers).await?;
+        registry
+            .decode_with_strictness(
+                &bytes,
+                &codec(&content_type),
+                &format!("{}/{}", T::GROUP, T::VERSION),
+                config.strict,
+            )
+            .and_then(|hub| registry.convert::<T>(hub))
+            .map(Converted)
+            .map_err(|e| config.reject(e.into()))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequestedVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let accept = parts
+            .headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|t| t.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        let accept = (!accept.is_empty()).then_some(accept);
+        Ok(RequestedVersion::new(
+            accept,
+            parts.uri.query().map(|t| t.to_string()),
+        ))
+    }
+}
+
+impl IntoResponse for ConversionError {
+    fn into_response(self) -> Response {
+        Hateoas::<()>::from(self).into_response()
+    }
+}
+
+impl<H> IntoResponse for Downconverted<H>
+where
+    H: crate::HateoasResource + Clone + Send + Sync + 'static,
+{
+    fn into_response(self) -> Response {
+        let version = self
+            .api_version()
+            .and_then(|t| t.rsplit('/').next())
+            .unwrap_or(H::VERSION)
+            .to_string();
+        let (hub, accept, encode) = self.into_parts();
+        respond(hub, accept, &version, encode)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Converted;
+    use crate::frameworks::axum::config::HateoasExtractorConfig;
+    use crate::frameworks::conversion::test::{registry, BulletHub, BulletV1};
+    use crate::frameworks::conversion::{ConversionRegistry, RequestedVersion};
+    use crate::Hateoas;
+    use axum::body::{Body, HttpBody};
+    use axum::http::{header, Request, StatusCode};
+    use axum::routing::post;
+    use axum::{Extension, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    async fn echo(
+        Extension(registry): Extension<Arc<ConversionRegistry<BulletHub>>>,
+        requested: RequestedVersion,
+        Converted(bullet): Converted<BulletV1>,
+    ) -> impl axum::response::IntoResponse {
+        let hub = bullet
+            .try_map(|t| Ok::<BulletHub, String>(t.into()))
+            .unwrap();
+        requested.respond(registry, hub)
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/bullets", post(echo))
+            .layer(Extension(Arc::new(registry())))
+    }
+
+    #[tokio::test]
+    async fn accepts_v2_and_responds_v1() {
+        let payload = r#"{"apiVersion":"ammo.hateoas.io/v2","kind":"Bullet","spec":{"content":{"caliber":9,"material":"lead"}}}"#;
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/bullets?apiVersion=v1")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+
+        let data = response.data().await.unwrap().unwrap();
+        let bullet = serde_json::from_slice::<Hateoas<BulletV1>>(&data).unwrap();
+        assert_eq!(bullet.api_version(), "ammo.hateoas.io/v1");
+        assert_eq!(
+            bullet.spec().and_then(|t| t.content().clone()),
+            Some(BulletV1 { caliber: 9 })
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn accepts_compressed_payload() {
+        use crate::frameworks::compression::Encoding;
+
+        let payload = r#"{"apiVersion":"ammo.hateoas.io/v2","kind":"Bullet","spec":{"content":{"caliber":9,"material":"lead"}}}"#;
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/bullets")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::CONTENT_ENCODING, "gzip")
+                    .body(Body::from(
+                        Encoding::Gzip.compress(payload.as_bytes()).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let data = response.data().await.unwrap().unwrap();
+        let bullet = serde_json::from_slice::<Hateoas<BulletV1>>(&data).unwrap();
+        assert_eq!(
+            bullet.spec().and_then(|t| t.content().clone()),
+            Some(BulletV1 { caliber: 9 })
+        );
+    }
+
+    #[tokio::test]
+    async fn uses_extractor_config() {
+        let app = app().layer(Extension(
+            HateoasExtractorConfig::default()
+                .content_types(["application/json"])
+                .default_content_type("application/json")
+                .strict(true),
+        ));
+        let request = |content_type: Option<&str>, payload: &'static str| {
+            let mut request = Request::builder()
+                .method("POST")
+                .uri("/bullets?apiVersion=v1");
+            if let Some(content_type) = content_type {
+                request = request.header(header::CONTENT_TYPE, content_type);
+            }
+            request.body(Body::from(payload)).unwrap()
+        };
+
+        let response = app
+            .clone()
+            .oneshot(request(Some("application/yaml"), "caliber: 9"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let response = app
+            .clone()
+            .oneshot(request(None, r#"{"caliber":9}"#))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let payload = r#"{"apiVersion":"ammo.hateoas.io/v1","kind":"Bullet","spec":{"content":{"caliber":9}}}"#;
+        let response = app.oneshot(request(None, payload)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn down_conversion_failure() {
+        let payload = r#"{"caliber":9}"#;
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/bullets")
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::ACCEPT, "application/json; version=v2")
+                    .body(Body::from(payload))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
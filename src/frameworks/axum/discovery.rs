@@ -0,0 +1,137 @@
+use crate::{APIGroupList, APIResourceList, Hateoas, Representation, ResourceRegistry};
+use axum::extract::{Path, State};
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use std::sync::Arc;
+
+/// # Discovery router
+/// Creates a router mounting `/apis` and `/apis/:group/:version` serving the discovery documents
+/// rendered from the [`ResourceRegistry`], and `/apis/:group/:version/context` serving the JSON-LD
+/// context. The router can be merged into the application router.
+///
+/// ```ignore
+/// let mut registry = ResourceRegistry::new();
+/// registry.register::<RubberBullet>(vec![HttpMethod::Get]);
+///
+/// let app = Router::new().merge(discovery::router(registry));
+/// ```
+pub fn router<S>(registry: ResourceRegistry) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/apis", get(api_group_list))
+        .route("/apis/:group/:version", get(api_resource_list))
+        .route("/apis/:group/:version/context", get(json_ld_context))
+        .with_state(Arc::new(registry))
+}
+
+async fn api_group_list(State(registry): State<Arc<ResourceRegistry>>) -> Hateoas<APIGroupList> {
+    registry.api_group_list()
+}
+
+async fn api_resource_list(
+    State(registry): State<Arc<ResourceRegistry>>,
+    Path((group, version)): Path<(String, String)>,
+) -> Hateoas<APIResourceList> {
+    registry.api_resource_list(&group, &version)
+}
+
+async fn json_ld_context(
+    State(registry): State<Arc<ResourceRegistry>>,
+    Path((group, version)): Path<(String, String)>,
+) -> Response {
+    match registry.json_ld_context(&group, &version) {
+        Some(context) => (
+            [(header::CONTENT_TYPE, Representation::JsonLd.media_type())],
+            context.to_string(),
+        )
+            .into_response(),
+        None => Hateoas::<()>::NOT_FOUND(
+            None,
+            Some(format!("No resources registered for {}/{}", group, version)),
+        )
+        .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::router;
+    use crate::{APIResourceList, Hateoas, HttpMethod, ResourceRegistry};
+    use axum::body::{Body, HttpBody};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn registry() -> ResourceRegistry {
+        let mut registry = ResourceRegistry::new();
+        registry.register::<String>(vec![HttpMethod::Get]);
+        registry
+    }
+
+    #[tokio::test]
+    async fn api_resource_list_route() {
+        let app = router::<()>(registry());
+
+        let mut response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/apis/hateoas.io/0.0.1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let data = response.data().await.unwrap().unwrap();
+        let list = serde_json::from_slice::<Hateoas<APIResourceList>>(&data).unwrap();
+        let content = list.spec().and_then(|t| t.content().clone()).unwrap();
+        assert_eq!(content.resources[0].kind, "String");
+    }
+
+    #[tokio::test]
+    async fn json_ld_context_route() {
+        let app = router::<()>(registry());
+
+        let mut response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/apis/hateoas.io/0.0.1/context")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[axum::http::header::CONTENT_TYPE],
+            "application/ld+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let context = serde_json::from_slice::<serde_json::Value>(&data).unwrap();
+        assert_eq!(
+            context["@context"]["@vocab"],
+            "/apis/hateoas.io/0.0.1/context#"
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_group_version_route() {
+        let app = router::<()>(registry());
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/apis/hateoas.io/v9")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,223 @@
+//! Negotiation layer
+//!
+//! `IntoResponse` has no access to the request, so the [`NegotiationLayer`] captures the method
+//! and the negotiation headers of the request for the duration of the handler, which the
+//! [`Hateoas`](crate::Hateoas) responses are then encoded from.
+use crate::frameworks::axum::config::HateoasExtractorConfig;
+use axum::http::header::{self, HeaderName};
+use axum::http::{Method, Request};
+use std::task::{Context, Poll};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// The request headers used when encoding the response.
+const NEGOTIATION_HEADERS: [HeaderName; 5] = [
+    header::ACCEPT,
+    header::ACCEPT_CHARSET,
+    header::ACCEPT_ENCODING,
+    header::ACCEPT_LANGUAGE,
+    HeaderName::from_static("prefer"),
+];
+
+/// The parts of the request captured by the [`NegotiationLayer`].
+#[derive(Debug)]
+pub struct CapturedRequest {
+    method: Method,
+    headers: http::HeaderMap,
+    problem_type_template: Option<String>,
+}
+
+tokio::task_local! {
+    static REQUEST: CapturedRequest;
+}
+
+/// # NegotiationLayer
+/// Layer negotiating the responses of the handlers with the request.
+///
+/// The method and the `Accept` and `Prefer` headers of the request are captured, so the responses
+/// are encoded in the format the client asked for. Without the layer the responses are negotiated
+/// against the `Accept` header the handler set on the status object.
+///
+/// ```
+/// use axum::routing::get;
+/// use axum::Router;
+/// use hateoas::frameworks::axum::layer::NegotiationLayer;
+/// use hateoas::Hateoas;
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { Hateoas::from("hello".to_string()) }))
+///     .layer(NegotiationLayer);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NegotiationLayer;
+
+impl<S> Layer<S> for NegotiationLayer {
+    type Service = Negotiation<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Negotiation { inner }
+    }
+}
+
+/// # Negotiation
+/// The service created by the [`NegotiationLayer`].
+#[derive(Clone, Debug)]
+pub struct Negotiation<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for Negotiation<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<CapturedRequest, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let mut headers = http::HeaderMap::new();
+        for name in NEGOTIATION_HEADERS {
+            for value in req.headers().get_all(&name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        let request = CapturedRequest {
+            method: req.method().clone(),
+            headers,
+            problem_type_template: req
+                .extensions()
+                .get::<HateoasExtractorConfig>()
+                .and_then(|t| t.problem_type_template.clone()),
+        };
+        REQUEST.scope(request, self.inner.call(req))
+    }
+}
+
+/// The method of the captured request, `None` outside of the [`NegotiationLayer`].
+pub(crate) fn request_method() -> Option<Method> {
+    REQUEST.try_with(|request| request.method.clone()).ok()
+}
+
+/// The problem type template of the [`HateoasExtractorConfig`] in the extensions of the captured
+/// request.
+pub(crate) fn problem_type_template() -> Option<String> {
+    REQUEST
+        .try_with(|request| request.problem_type_template.clone())
+        .ok()
+        .flatten()
+}
+
+/// The values of the captured request header joined as a list, `None` outside of the
+/// [`NegotiationLayer`] or when the request did not have the header.
+pub(crate) fn request_header<K: header::AsHeaderName>(name: K) -> Option<String> {
+    REQUEST
+        .try_with(|request| {
+            let values = request
+                .headers
+                .get_all(name)
+                .iter()
+                .filter_map(|t| t.to_str().ok())
+                .collect::<Vec<&str>>();
+            (!values.is_empty()).then(|| values.join(", "))
+        })
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use super::NegotiationLayer;
+    use crate::{Hateoas, RelLink, RelLinkCollection};
+    use axum::body::{Body, HttpBody};
+    use axum::http::{header, Method, Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use simple_serde::{Decoded, SimpleDecoder};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new().route("/", get(|| async { Hateoas::from("hello".to_string()) }))
+    }
+
+    #[tokio::test]
+    async fn negotiates_request_accept() {
+        let mut response = app()
+            .layer(NegotiationLayer)
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header(header::ACCEPT, "application/json;q=0.5")
+                    .header(header::ACCEPT, "application/yaml")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/yaml");
+
+        let data = response.data().await.unwrap().unwrap();
+        let hateoas: Hateoas<String> = data
+            .to_vec()
+            .decode("application/yaml")
+            .map(|t: Decoded<Hateoas<String>>| t.into())
+            .unwrap();
+        assert_eq!(
+            hateoas.spec().and_then(|t| t.content().as_ref()),
+            Some(&"hello".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn head_has_link_header() {
+        let app = Router::new().route(
+            "/bullets/9",
+            get(|| async {
+                let mut hateoas = Hateoas::from("bullet".to_string());
+                hateoas
+                    .spec_mut()
+                    .rel()
+                    .add(RelLink::GET("self", "/bullets/9"));
+                hateoas
+            }),
+        );
+        let mut response = app
+            .layer(NegotiationLayer)
+            .oneshot(
+                Request::builder()
+                    .method(Method::HEAD)
+                    .uri("/bullets/9")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let links =
+            RelLinkCollection::from_link_header(response.headers()[header::LINK].to_str().unwrap())
+                .unwrap();
+        assert_eq!(links.get("self").map(|t| t.href()), Some("/bullets/9"));
+        assert!(response.data().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn without_layer() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/")
+                    .header(header::ACCEPT, "application/yaml")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    }
+}
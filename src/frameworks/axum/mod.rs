@@ -1,61 +1,164 @@
+use crate::frameworks::axum::config::HateoasExtractorConfig;
+use crate::frameworks::conversion::codec;
+use crate::frameworks::prefer::Preferences;
+use crate::frameworks::rejection::Rejection;
+use crate::frameworks::{compression, negotiation};
 use crate::header::HeaderKey;
-use crate::{status_code, Hateoas, HateoasResource, HeaderMap};
+use crate::{status_code, Hateoas, HateoasResource, HeaderMap, Representation};
 use axum::async_trait;
 use axum::body::HttpBody;
 use axum::extract::FromRequest;
 use axum::http::Request;
 use axum::response::{IntoResponse, Response};
 use axum_core::BoxError;
-use bytes::Bytes;
+use bytes::{Buf, Bytes};
+use http::header::HeaderName;
 use http::{header, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use simple_serde::{Decoded, Error, SimpleDecoder, SimpleEncoder};
+use simple_serde::{Decoded, SimpleDecoder};
 use tracing::{event, span, Level};
 
-const RESPONSE_MISSING_STATUS_CODE: &'static str = r##"{"apiVersion": "v1","kind": "Error","spec": null,"status": {"message":"Missing status code response","http_status_code": 500}}"##;
-const RESPONSE_MISSING_CONTENT_TYPE: &'static str = r##"{"apiVersion": "v1","kind": "Error","spec": null,"status": {"message":"Missing status code response","http_status_code": 500}}"##;
-
+pub mod config;
+pub mod conversion;
+pub mod discovery;
+pub mod layer;
+pub mod pagination;
+pub mod prefer;
 #[cfg(test)]
 pub mod test_helpers;
 
 impl<T: Serialize + HateoasResource + Clone> IntoResponse for Hateoas<T> {
     fn into_response(self) -> Response {
-        span!(Level::TRACE, "Hateoas Into Response");
-        let tmp_response = Response::new(());
-        let request_headers = tmp_response.extensions().get::<HeaderMap>();
-        event!(Level::TRACE, "extracting http status code");
-        let code: http::StatusCode = self
-            .status()
-            .and_then(|t| t.http_status_code.clone())
-            .unwrap_or(status_code::OK)
-            .into();
-        event!(Level::TRACE, "Extracting Headers from status object");
-        let headers = self
-            .status()
-            .and_then(|t| t.header.clone())
-            .and_then(|t| http::HeaderMap::try_from(t).ok())
-            .unwrap_or(http::HeaderMap::new());
-        event!(
-            Level::TRACE,
-            "Extracting Accept content type from request extension"
-        );
-        let accept_content_type = request_headers
-            .and_then(|t| t.get(&HeaderKey::Accept).map(|t| t.to_string()))
-            .or_else(|| {
-                event!(Level::WARN, "Failed to find Accept key in request header extension, falling back to custom header from status");
-                headers.get(&HeaderKey::Accept.to_string()).and_then(|t| t.to_str().ok().map(|t|t.to_string()))
-            })
-            .unwrap_or("application/json".to_string());
+        respond(self, None, T::VERSION, |hateoas, media_type, type_template| {
+            negotiation::encode(hateoas, media_type, type_template)
+                .ok_or((StatusCode::INTERNAL_SERVER_ERROR, ""))
+        })
+    }
+}
 
-        self.encode(accept_content_type)
-            .map(|t| {
-                let mut response = (code, t.to_vec()).into_response();
-                *response.headers_mut() = headers.clone();
-                response
-            })
-            .unwrap_or((StatusCode::INTERNAL_SERVER_ERROR, "").into_response())
+/// Responding with the body created by `encode` for the negotiated media type. The `accept` is
+/// used when the `Accept` header of the request was not captured by the negotiation layer, the
+/// `version` is the version of `T` the body is encoded as.
+pub(crate) fn respond<T, F, E>(
+    mut hateoas: Hateoas<T>,
+    accept: Option<String>,
+    version: &str,
+    encode: F,
+) -> Response
+where
+    T: HateoasResource + Clone,
+    F: FnOnce(&Hateoas<T>, &'static str, Option<&str>) -> Result<Vec<u8>, E>,
+    E: IntoResponse,
+{
+    span!(Level::TRACE, "Hateoas Into Response");
+    event!(Level::TRACE, "extracting http status code");
+    let code: http::StatusCode = hateoas
+        .status()
+        .and_then(|t| t.http_status_code.clone())
+        .unwrap_or(status_code::OK)
+        .into();
+    if code == StatusCode::NO_CONTENT
+        || code == StatusCode::NOT_MODIFIED
+        || layer::request_method() == Some(http::Method::HEAD)
+    {
+        event!(Level::TRACE, "Adding Link header to body-less response");
+        hateoas.add_link_header();
     }
+    event!(Level::TRACE, "Extracting Headers from status object");
+    let headers = hateoas
+        .status()
+        .and_then(|t| t.header.clone())
+        .and_then(|t| http::HeaderMap::try_from(t).ok())
+        .unwrap_or_default();
+    event!(
+        Level::TRACE,
+        "Extracting Accept content type from the negotiation layer"
+    );
+    let accept = layer::request_header(header::ACCEPT)
+        .or(accept)
+        .or_else(|| {
+            event!(Level::WARN, "Failed to find Accept header captured by the negotiation layer, falling back to custom header from status");
+            headers.get(HeaderKey::Accept.to_string()).and_then(|t| t.to_str().ok().map(|t|t.to_string()))
+        });
+
+    let media_type = match negotiation::select(
+        accept.as_deref(),
+        code.is_client_error() || code.is_server_error(),
+    ) {
+        Some(media_type) => media_type,
+        None => {
+            event!(Level::TRACE, "No acceptable media type");
+            let mut response = negotiation::not_acceptable().into_response();
+            response
+                .headers_mut()
+                .append(header::VARY, http::HeaderValue::from_static("Accept"));
+            return response;
+        }
+    };
+    let accept_encoding = layer::request_header(header::ACCEPT_ENCODING).or_else(|| {
+        headers
+            .get(header::ACCEPT_ENCODING)
+            .and_then(|t| t.to_str().ok().map(|t| t.to_string()))
+    });
+    let prefer = layer::request_header(HeaderKey::Prefer.to_string()).or_else(|| {
+        headers
+            .get(HeaderKey::Prefer.to_string())
+            .and_then(|t| t.to_str().ok().map(|t| t.to_string()))
+    });
+    // without the negotiation layer the method is unknown, the preference is then applied
+    let method = layer::request_method();
+    let preference_applied = prefer.and_then(|t| {
+        Preferences::parse(&t).apply(method.as_ref().map_or("", |t| t.as_str()), &mut hateoas)
+    });
+    let content_type =
+        negotiation::version_content_type::<T>(accept.as_deref(), media_type, version);
+    event!(Level::TRACE, "Encoding as {}", content_type);
+    let type_template = layer::problem_type_template();
+    encode(&hateoas, media_type, type_template.as_deref())
+        .map(|t| {
+            let (encoding, t) = compression::compress(accept_encoding.as_deref(), t);
+            let mut response = (code, t).into_response();
+            *response.headers_mut() = headers;
+            response.headers_mut().insert(
+                header::CONTENT_TYPE,
+                http::HeaderValue::from_str(&content_type)
+                    .unwrap_or(http::HeaderValue::from_static(media_type)),
+            );
+            response
+                .headers_mut()
+                .append(header::VARY, http::HeaderValue::from_static("Accept"));
+            if compression::varies() {
+                response.headers_mut().append(
+                    header::VARY,
+                    http::HeaderValue::from_static("Accept-Encoding"),
+                );
+            }
+            if let Some(preference_applied) = preference_applied {
+                if let Ok(name) = HeaderName::try_from(HeaderKey::PreferenceApplied.to_string()) {
+                    response
+                        .headers_mut()
+                        .append(name, http::HeaderValue::from_static(preference_applied));
+                }
+                if let Ok(prefer) = http::HeaderValue::try_from(HeaderKey::Prefer.to_string()) {
+                    response.headers_mut().append(header::VARY, prefer);
+                }
+            }
+            response.headers_mut().remove(header::CONTENT_LENGTH);
+            match encoding {
+                compression::Encoding::Identity => {
+                    response.headers_mut().remove(header::CONTENT_ENCODING);
+                }
+                _ => {
+                    response.headers_mut().insert(
+                        header::CONTENT_ENCODING,
+                        http::HeaderValue::from_static(encoding.token()),
+                    );
+                }
+            }
+            response
+        })
+        .unwrap_or_else(|e| e.into_response())
 }
 
 // impl<T: DeserializeOwned + HateoasResource, S, B> FromRequest<S, B> for Hateoas<T> {
@@ -76,122 +179,121 @@ where
         span!(Level::TRACE, "Extracting Hateoas object from request");
         let headers = HeaderMap::from(req.headers());
         req.extensions_mut().insert(headers.clone());
-        let content_type = headers
-            .get_first(&HeaderKey::ContentType)
-            .map(|t| t.to_owned())
-            .ok_or(Hateoas::UNSUPPORTED_MEDIA_TYPE(
-                None,
-                Some("Content-Type not found in request".to_string()),
-            ))?;
-
-        let bytes = Bytes::from_request(req, state)
-            .await
-            .map_err(Hateoas::from)?
-            .to_vec();
+        let config = HateoasExtractorConfig::from_request(&req);
+        let (content_type, bytes) = read_payload(req, state, &config, &headers).await?;
+        if let Some(representation) = Representation::from_media_type(&content_type) {
+            return representation
+                .decode_with_type_template(&bytes, config.problem_type_template.as_deref())
+                .map_err(|e| config.reject(e.into()))
+                .map(|mut t| {
+                    *t.metadata_mut().headers_mut() = Some(headers.uncommon_extract());
+                    t
+                });
+        }
+        let content_type = codec(&content_type);
         bytes
             .decode(&content_type)
             .map(|t: Decoded<Hateoas<T>>| t.into())
             .or_else(|t| {
+                if config.strict {
+                    return Err(t);
+                }
                 event!(Level::WARN, "Failed to decode the payload, will try again.");
                 bytes
                     .decode(&content_type)
                     .map(|t: Decoded<T>| Hateoas::from(t.into()))
             })
-            .map_err(|e| e.into())
+            .map_err(|e| config.reject(e.into()))
             .map(|mut t| {
                 *t.metadata_mut().headers_mut() = Some(headers.uncommon_extract());
-                *t.status_mut().headers_mut() = Some(headers);
                 t
             })
     }
 }
 
+/// Reading the payload of the request as configured by the [`HateoasExtractorConfig`], the
+/// `Content-Type` is checked against the config and the payload is decompressed. Returns the
+/// content type and the payload.
+pub(crate) async fn read_payload<B, S>(
+    req: Request<B>,
+    state: &S,
+    config: &HateoasExtractorConfig,
+    headers: &HeaderMap,
+) -> Result<(String, Vec<u8>), Hateoas<()>>
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    let content_type = headers
+        .get_first(&HeaderKey::ContentType)
+        .map(|t| t.to_owned())
+        .or_else(|| config.default_content_type.clone())
+        .ok_or_else(|| config.reject(Rejection::MissingContentType))?;
+    if !config.allows(&content_type) {
+        return Err(config.reject(Rejection::UnsupportedContentType(content_type)));
+    }
+
+    let bytes = match config.limit {
+        Some(limit) => read_body(req, limit)
+            .await
+            .map_err(|e| config.reject(e))?,
+        None => Bytes::from_request(req, state)
+            .await
+            .map_err(|e| config.reject(Rejection::Payload(e.to_string())))?
+            .to_vec(),
+    };
+    let bytes = compression::decompress(
+        headers
+            .get_first(&HeaderKey::ContentEncoding)
+            .map(|t| t.as_str()),
+        bytes,
+        config
+            .limit
+            .unwrap_or(compression::DEFAULT_DECOMPRESSED_LIMIT),
+    )
+    .map_err(|e| config.reject(e.into()))?;
+    Ok((content_type, bytes))
+}
+
+/// Reading the body of the request, payloads larger than the limit are rejected before they are
+/// read when the `Content-Length` is known.
+async fn read_body<B>(req: Request<B>, limit: usize) -> Result<Vec<u8>, Rejection>
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+{
+    let length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|t| t.to_str().ok())
+        .and_then(|t| t.parse::<usize>().ok());
+    if length.map(|t| t > limit).unwrap_or(false) {
+        return Err(Rejection::Overflow { length, limit });
+    }
+    let mut body = Box::pin(req.into_body());
+    let mut bytes = Vec::with_capacity(length.unwrap_or_default());
+    while let Some(chunk) = body.data().await {
+        let mut chunk =
+            chunk.map_err(|_| Rejection::Payload("Failed to read the body".to_string()))?;
+        if bytes.len() + chunk.remaining() > limit {
+            return Err(Rejection::Overflow { length, limit });
+        }
+        bytes.extend_from_slice(&chunk.copy_to_bytes(chunk.remaining()));
+    }
+    Ok(bytes)
+}
+
 impl From<axum_core::extract::rejection::BytesRejection> for Hateoas<()> {
     fn from(e: axum_core::extract::rejection::BytesRejection) -> Self {
-        Hateoas::BAD_REQUEST(None, Some("Corrupted payload".to_string()))
+        Rejection::Payload(e.to_string()).into()
     }
 }
 
 impl From<simple_serde::Error> for Hateoas<()> {
     fn from(e: simple_serde::Error) -> Self {
-        match e {
-            Error::Infallible => {
-                Hateoas::INTERNAL_SERVER_ERROR(None, Some("How did you end up here?".to_string()))
-            }
-            Error::ByteToUTF8ConversionFailure(_) => Hateoas::BAD_REQUEST(
-                None,
-                Some("Payload data not readable into UTF8".to_string()),
-            ),
-            Error::UnknownContentTypeMatchFromStr(_) => {
-                Hateoas::BAD_REQUEST(None, Some("Unknown content type given".to_string()))
-            }
-            Error::BsonSerializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("BSON processing error: {}", e)))
-            }
-            Error::BsonDeserializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("BSON processing error: {}", e)))
-            }
-            Error::CborFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("CBOR processing error: {}", e)))
-            }
-            Error::FlexBuffersSerializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Flexbuffer processing error: {}", e)))
-            }
-            Error::FlexBuffersDeserializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Flexbuffer processing error: {}", e)))
-            }
-            Error::JsonError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("JSON processing error: {}", e)))
-            }
-            Error::Json5Error(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("JSON5 processing error: {}", e)))
-            }
-            Error::LexprError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Lexpr processing error: {}", e)))
-            }
-            Error::MessagePackEncodeError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Message Pack processing error: {}", e)))
-            }
-            Error::MessagePackDecodeError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Message Pack processing error: {}", e)))
-            }
-            Error::PickleError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Pickle processing error: {}", e)))
-            }
-            Error::PostcardError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Postcard processing error: {}", e)))
-            }
-            Error::RonError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("Ron processing error: {}", e)))
-            }
-            Error::TomlSerializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("TOML processing error: {}", e)))
-            }
-            Error::TomlDeserializationFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("TOML processing error: {}", e)))
-            }
-            Error::UrlEncodingFailure(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("URL Encoded processing error: {:?}", e)))
-            }
-            Error::YamlError(e) => {
-                Hateoas::BAD_REQUEST(None, Some(format!("YAML processing error: {}", e)))
-            }
-            Error::TypeDoesNotSupportSerialization(_) => Hateoas::BAD_REQUEST(
-                None,
-                Some(format!(
-                    "Payload datatype does not support De-/Serialization"
-                )),
-            ),
-            Error::FailedConvertingHeaderValueToContentType(_) => Hateoas::BAD_REQUEST(
-                None,
-                Some(format!("Header for typecontent is not supported")),
-            ),
-            Error::InvalidHeaderValue(_) => Hateoas::BAD_REQUEST(
-                None,
-                Some(format!("Header for typecontent is not supported")),
-            ),
-            _ => Hateoas::BAD_REQUEST(None, Some(format!("Unknown payload content"))),
-        }
+        Rejection::from(e).into()
     }
 }
 
@@ -210,6 +312,7 @@ mod tests {
     use http::Method;
     use serde::Deserialize;
     use serde_json::{json, Value};
+    use simple_serde::SimpleEncoder;
     use std::net::{SocketAddr, TcpListener};
     use std::ops::Deref;
     use tower::Service; // for `call`
@@ -255,6 +358,7 @@ mod tests {
                     hateoas
                 }),
             )
+            .layer(layer::NegotiationLayer)
     }
 
     #[tokio::test]
@@ -287,4 +391,409 @@ mod tests {
             serde_json::to_string(&Hateoas::<()>::INTERNAL_SERVER_ERROR(None, None)).unwrap()
         );
     }
+
+    #[tokio::test]
+    async fn list_into_response() {
+        let app = Router::new().route(
+            "/strings",
+            get(|| async {
+                crate::HateoasList::OK(Some(crate::ResourceList::from(vec!["foo".to_string()])), None)
+            }),
+        );
+
+        let mut response = app
+            .oneshot(Request::builder().uri("/strings").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let data = response.data().await.unwrap().unwrap();
+        let list = serde_json::from_slice::<crate::HateoasList<String>>(&data).unwrap();
+        assert_eq!(list.kind(), "StringList");
+        assert_eq!(
+            list.spec().and_then(|t| t.content().as_ref()).map(|t| t.items().len()),
+            Some(1)
+        );
+    }
+
+    #[tokio::test]
+    async fn hal_negotiation() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/hal+json")
+                    .header("Content-Type", "application/hal+json")
+                    .body(Body::from(
+                        r#"{"foo":"bar","_links":{"self":{"href":"/input/bar"}}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/hal+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({"foo": "bar", "_links": {"self": {"href": "/input/bar"}}})
+        );
+    }
+
+    #[tokio::test]
+    async fn json_api_negotiation() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/vnd.api+json")
+                    .header("Content-Type", "application/vnd.api+json")
+                    .body(Body::from(
+                        r#"{"data":{"type":"test-input","id":"bar","attributes":{"foo":"bar"}}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.api+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({"data": {"type": "test-input", "id": "bar", "attributes": {"foo": "bar"}}})
+        );
+    }
+
+    #[tokio::test]
+    async fn siren_negotiation() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/vnd.siren+json")
+                    .header("Content-Type", "application/vnd.siren+json")
+                    .body(Body::from(
+                        r#"{"properties":{"foo":"bar"},"actions":[{"name":"delete","method":"DELETE","href":"/input/bar"}]}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.siren+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "class": ["test-input"],
+                "properties": {"foo": "bar"},
+                "actions": [{"name": "delete", "method": "DELETE", "href": "/input/bar"}]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_json_template_submission() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/vnd.collection+json")
+                    .header("Content-Type", "application/vnd.collection+json")
+                    .body(Body::from(
+                        r#"{"template":{"data":[{"name":"foo","value":"bar"}]}}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.collection+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({"collection": {"version": "1.0", "items": [{"data": [{"name": "foo", "value": "bar"}]}]}})
+        );
+    }
+
+    #[tokio::test]
+    async fn json_ld_negotiation() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/ld+json")
+                    .header("Content-Type", "application/ld+json")
+                    .body(Body::from(
+                        r#"{"@context":"/apis/test/0/context","@id":"/input/bar","@type":"test-input","foo":"bar"}"#,
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/ld+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({"@context": "/apis/test/0/context", "@id": "/input/bar", "@type": "test-input", "foo": "bar"})
+        );
+    }
+
+    #[tokio::test]
+    async fn no_content_link_header() {
+        let app = Router::new().route(
+            "/input/bar",
+            delete(|| async {
+                let mut hateoas: Hateoas<Input> = Hateoas::NO_CONTENT(None, None);
+                hateoas
+                    .spec_mut()
+                    .rel()
+                    .add(crate::RelLink::GET("collection", "/input"));
+                hateoas
+            }),
+        );
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::DELETE)
+                    .uri("/input/bar")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let links = crate::RelLinkCollection::from_link_header(
+            response.headers()[http::header::LINK].to_str().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(links.get("collection").map(|t| t.href()), Some("/input"));
+    }
+
+    #[tokio::test]
+    async fn problem_details_for_errors() {
+        let app = Router::new()
+            .route(
+                "/input",
+                post(|_: Hateoas<Input>| async move {
+                    let hateoas: Hateoas<Input> =
+                        Hateoas::CONFLICT(None, Some("Input foo already exists".to_string()));
+                    hateoas
+                }),
+            )
+            .layer(layer::NegotiationLayer);
+
+        let mut response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/input")
+                    .header("Accept", "application/json, application/problem+json")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(
+            value,
+            json!({"title": "Conflict", "status": 409, "detail": "Input foo already exists"})
+        );
+    }
+
+    #[tokio::test]
+    async fn request_headers_are_not_echoed() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/json")
+                    .header("Authorization", "Bearer secret")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(http::header::AUTHORIZATION).is_none());
+    }
+
+    #[tokio::test]
+    async fn not_acceptable() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "text/html, application/json;q=0")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        assert_eq!(response.headers()[http::header::VARY], "Accept");
+
+        let data = response.data().await.unwrap().unwrap();
+        let hateoas = serde_json::from_slice::<Hateoas<()>>(&data).unwrap();
+        assert!(hateoas
+            .status()
+            .and_then(|t| t.message().as_ref())
+            .unwrap()
+            .contains("application/yaml"));
+    }
+
+    #[tokio::test]
+    async fn quality_negotiation() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept", "application/json;q=0.5, application/*;q=0.9")
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/yaml"
+        );
+        assert_eq!(response.headers()[http::header::VARY], "Accept");
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn compression() {
+        use crate::frameworks::compression::Encoding;
+
+        let foo = "bar".repeat(1000);
+        let payload = serde_json::to_vec(&json!({ "foo": foo })).unwrap();
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Accept-Encoding", "gzip;q=0.8, br, identity;q=0.1")
+                    .header("Content-Type", "application/json")
+                    .header("Content-Encoding", "deflate")
+                    .body(Body::from(Encoding::Deflate.compress(&payload).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[http::header::CONTENT_ENCODING], "br");
+        assert_eq!(
+            response
+                .headers()
+                .get_all(http::header::VARY)
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["Accept", "Accept-Encoding"]
+        );
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.data().await {
+            data.extend_from_slice(&chunk.unwrap());
+        }
+        let data = Encoding::Brotli.decompress(&data, usize::MAX).unwrap();
+        let hateoas = serde_json::from_slice::<Hateoas<Input>>(&data).unwrap();
+        assert_eq!(
+            hateoas.spec().and_then(|t| t.content().as_ref()).map(|t| &t.foo),
+            Some(&foo)
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_content_encoding() {
+        let response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header("Content-Type", "application/json")
+                    .header("Content-Encoding", "compress")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn vendor_media_type() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/")
+                    .header(
+                        "Accept",
+                        "application/vnd.test.test-input+yaml; version=0; profile=short, application/json;q=0.5",
+                    )
+                    .header("Content-Type", "application/vnd.test.test-input.v0+json")
+                    .body(Body::from(r#"{"foo":"bar"}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "application/vnd.test.test-input+yaml; version=0; profile=short"
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let yaml = String::from_utf8(data.to_vec()).unwrap();
+        assert!(yaml.contains("foo: bar"));
+    }
 }
@@ -0,0 +1,89 @@
+use crate::{Hateoas, PaginationParams, PaginationQuery};
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+
+/// Extracting the pagination parameters from the query string of the request, invalid parameters
+/// are rejected with `BAD_REQUEST`.
+///
+/// ```ignore
+/// async fn list(pagination: PaginationQuery<OffsetParams>) -> HateoasList<Bullet> {
+///     let mut list = ResourceList::from(bullets(&pagination.params));
+///     let links = pagination.links(&PageInfo::Total(total));
+///     ...
+/// }
+/// ```
+#[async_trait]
+impl<S, P> FromRequestParts<S> for PaginationQuery<P>
+where
+    P: PaginationParams,
+    S: Send + Sync,
+{
+    type Rejection = Hateoas<()>;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        PaginationQuery::from_uri(&parts.uri.to_string())
+            .map_err(|e| Hateoas::BAD_REQUEST(None, Some(e.to_string())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{HateoasList, OffsetParams, PageInfo, PaginationQuery, ResourceList};
+    use axum::body::{Body, HttpBody};
+    use axum::http::{Request, StatusCode};
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    async fn list(pagination: PaginationQuery<OffsetParams>) -> HateoasList<u32> {
+        let items = (pagination.params.offset..100)
+            .take(pagination.params.limit as usize)
+            .map(|t| t as u32)
+            .collect::<Vec<u32>>();
+        let mut list = HateoasList::OK(Some(ResourceList::from(items)), None);
+        *list.spec_mut().rel() = pagination.links(&PageInfo::Total(100));
+        list
+    }
+
+    #[tokio::test]
+    async fn pagination_links_from_query() {
+        let app = Router::new().route("/numbers", get(list));
+
+        let mut response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/numbers?offset=90&limit=5&sort=desc")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let data = response.data().await.unwrap().unwrap();
+        let list = serde_json::from_slice::<HateoasList<u32>>(&data).unwrap();
+        let mut spec = list.spec().cloned().unwrap();
+        assert_eq!(spec.content().as_ref().unwrap().items().len(), 5);
+        assert_eq!(
+            spec.rel().get("next").unwrap().href(),
+            "/numbers?sort=desc&offset=95&limit=5"
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_pagination_query() {
+        let app = Router::new().route("/numbers", get(list));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/numbers?limit=0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,120 @@
+use crate::frameworks::prefer::Preferences;
+use axum::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use std::convert::Infallible;
+
+/// Extracting the preferences of the `Prefer` headers of the request, requests without the header
+/// have no preferences.
+///
+/// ```ignore
+/// async fn create(preferences: Preferences, bullet: Hateoas<Bullet>) -> Hateoas<Bullet> {
+///     if preferences.handling() == Some(Handling::Strict) {
+///         ...
+///     }
+/// }
+/// ```
+#[async_trait]
+impl<S> FromRequestParts<S> for Preferences
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let prefer = parts
+            .headers
+            .get_all("prefer")
+            .iter()
+            .filter_map(|t| t.to_str().ok())
+            .collect::<Vec<&str>>()
+            .join(",");
+        Ok(Preferences::parse(&prefer))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frameworks::axum::layer::NegotiationLayer;
+    use crate::frameworks::prefer::{Handling, Preferences};
+    use crate::{Hateoas, HeaderMap};
+    use axum::body::{Body, HttpBody};
+    use axum::http::{header, Method, Request, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route(
+                "/bullets",
+                post(|preferences: Preferences| async move {
+                    let mut response = Hateoas::CREATED(Some("bullet".to_string()), None);
+                    if preferences.handling() == Some(Handling::Lenient) {
+                        response
+                            .status_mut()
+                            .headers_mut()
+                            .get_or_insert_with(HeaderMap::new)
+                            .append("Preference-Applied", "handling=lenient");
+                    }
+                    response
+                }),
+            )
+            .layer(NegotiationLayer)
+    }
+
+    #[tokio::test]
+    async fn return_minimal() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/bullets")
+                    .header("Prefer", "return=minimal")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["preference-applied"], "return=minimal");
+        assert!(response
+            .headers()
+            .get_all(header::VARY)
+            .iter()
+            .any(|t| t == "Prefer"));
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(value["spec"]["content"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn return_representation() {
+        let mut response = app()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri("/bullets")
+                    .header("Prefer", "handling=lenient")
+                    .header("Prefer", "return=representation")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            response
+                .headers()
+                .get_all("preference-applied")
+                .iter()
+                .collect::<Vec<_>>(),
+            vec!["handling=lenient", "return=representation"]
+        );
+
+        let data = response.data().await.unwrap().unwrap();
+        let value = serde_json::from_slice::<Value>(&data).unwrap();
+        assert_eq!(value["spec"]["content"], "bullet");
+    }
+}
@@ -0,0 +1,381 @@
+//! Response compression
+//!
+//! Compressing the encoded responses with the content coding negotiated from the
+//! `Accept-Encoding` header of the request, and decompressing request payloads sent with a
+//! `Content-Encoding`. The codings are enabled by the `gzip`, `deflate`, `brotli` and `zstd`
+//! features, or all of them by the `compression` feature. Without any of them only `identity`
+//! is available and responses are sent uncompressed.
+use crate::{Hateoas, HateoasResource};
+use std::io::Read;
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli"))]
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Responses smaller than this are not compressed, unless changed by [`set_min_size`].
+pub const DEFAULT_MIN_SIZE: usize = 1024;
+
+/// The limit of decompressed request payloads when the framework has no limit configured.
+pub const DEFAULT_DECOMPRESSED_LIMIT: usize = 41_943_040; // 40 mb
+
+static MIN_SIZE: AtomicUsize = AtomicUsize::new(DEFAULT_MIN_SIZE);
+
+#[derive(Debug, Display, PartialEq)]
+#[non_exhaustive]
+pub enum CompressionError {
+    /// The content coding is unknown or the feature enabling it is not enabled
+    #[display(fmt = "Unsupported content coding: {}", _0)]
+    UnsupportedEncoding(String),
+
+    #[display(fmt = "Failed to decompress {} payload: {}", _0, _1)]
+    Decompression(&'static str, String),
+
+    /// The decompressed payload is larger than the limit
+    #[display(
+        fmt = "Decompressed payload is larger than allowed (limit: {} bytes).",
+        _0
+    )]
+    Overflow(usize),
+}
+
+impl<T> From<CompressionError> for Hateoas<T>
+where
+    T: HateoasResource + Clone,
+{
+    fn from(e: CompressionError) -> Self {
+        match e {
+            CompressionError::UnsupportedEncoding(_) => {
+                Hateoas::UNSUPPORTED_MEDIA_TYPE(None, Some(e.to_string()))
+            }
+            CompressionError::Decompression(_, _) => {
+                Hateoas::BAD_REQUEST(None, Some(e.to_string()))
+            }
+            CompressionError::Overflow(_) => Hateoas::PAYLOAD_TOO_LARGE(None, Some(e.to_string())),
+        }
+    }
+}
+
+/// # Encoding
+/// The content codings of responses and request payloads.
+///
+/// ```
+/// use hateoas::frameworks::compression::Encoding;
+///
+/// assert_eq!(Encoding::from_token("identity"), Ok(Encoding::Identity));
+/// assert_eq!(Encoding::Identity.token(), "identity");
+/// assert_eq!(Encoding::available().last(), Some(&Encoding::Identity));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Encoding {
+    Identity,
+    #[cfg(feature = "brotli")]
+    Brotli,
+    #[cfg(feature = "zstd")]
+    Zstd,
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "deflate")]
+    Deflate,
+}
+
+impl Encoding {
+    /// ## The enabled codings
+    /// In order of preference, `identity` is always available and the least preferred.
+    #[allow(clippy::vec_init_then_push)]
+    pub fn available() -> Vec<Encoding> {
+        let mut available = Vec::new();
+        #[cfg(feature = "brotli")]
+        available.push(Encoding::Brotli);
+        #[cfg(feature = "zstd")]
+        available.push(Encoding::Zstd);
+        #[cfg(feature = "gzip")]
+        available.push(Encoding::Gzip);
+        #[cfg(feature = "deflate")]
+        available.push(Encoding::Deflate);
+        available.push(Encoding::Identity);
+        available
+    }
+
+    /// The token of the coding used in `Accept-Encoding` and `Content-Encoding`.
+    pub const fn token(&self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => "br",
+            #[cfg(feature = "zstd")]
+            Encoding::Zstd => "zstd",
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => "gzip",
+            #[cfg(feature = "deflate")]
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// ## Finding the coding of a token
+    /// Tokens are case-insensitive and `x-gzip` is an alias of `gzip`.
+    pub fn from_token(token: &str) -> Result<Self, CompressionError> {
+        let token = token.trim().to_ascii_lowercase();
+        let token = match token.as_str() {
+            "x-gzip" => "gzip",
+            t => t,
+        };
+        Encoding::available()
+            .into_iter()
+            .find(|t| t.token() == token)
+            .ok_or_else(|| CompressionError::UnsupportedEncoding(token.to_string()))
+    }
+
+    pub fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            Encoding::Identity => Ok(data.to_vec()),
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+                writer.write_all(data)?;
+                writer.flush()?;
+                Ok(writer.into_inner())
+            }
+            #[cfg(feature = "zstd")]
+            Encoding::Zstd => zstd::stream::encode_all(data, 0),
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            #[cfg(feature = "deflate")]
+            Encoding::Deflate => {
+                let mut encoder =
+                    flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+
+    /// ## Decompressing a payload
+    /// Decompressing stops with [`CompressionError::Overflow`] when the decompressed payload is
+    /// larger than the limit.
+    pub fn decompress(&self, data: &[u8], limit: usize) -> Result<Vec<u8>, CompressionError> {
+        let reader: Box<dyn Read + '_> = match self {
+            Encoding::Identity => Box::new(data),
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => Box::new(brotli::Decompressor::new(data, 4096)),
+            #[cfg(feature = "zstd")]
+            Encoding::Zstd => Box::new(
+                zstd::stream::read::Decoder::new(data)
+                    .map_err(|e| CompressionError::Decompression(self.token(), e.to_string()))?,
+            ),
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => Box::new(flate2::read::GzDecoder::new(data)),
+            #[cfg(feature = "deflate")]
+            Encoding::Deflate => Box::new(flate2::read::ZlibDecoder::new(data)),
+        };
+        let mut decompressed = Vec::new();
+        reader
+            .take((limit as u64).saturating_add(1))
+            .read_to_end(&mut decompressed)
+            .map_err(|e| CompressionError::Decompression(self.token(), e.to_string()))?;
+        if decompressed.len() > limit {
+            return Err(CompressionError::Overflow(limit));
+        }
+        Ok(decompressed)
+    }
+}
+
+/// ## Setting the minimum size of compressed responses
+/// Returns the previous minimum size.
+///
+/// ```
+/// use hateoas::frameworks::compression::{min_size, set_min_size, DEFAULT_MIN_SIZE};
+///
+/// assert_eq!(set_min_size(512), DEFAULT_MIN_SIZE);
+/// assert_eq!(min_size(), 512);
+/// # set_min_size(DEFAULT_MIN_SIZE);
+/// ```
+pub fn set_min_size(size: usize) -> usize {
+    MIN_SIZE.swap(size, Ordering::Relaxed)
+}
+
+pub fn min_size() -> usize {
+    MIN_SIZE.load(Ordering::Relaxed)
+}
+
+/// Responses only vary by `Accept-Encoding` when there is more than `identity` to choose from.
+pub fn varies() -> bool {
+    Encoding::available().len() > 1
+}
+
+/// ## Negotiating the content coding
+/// Selecting the available coding with the highest `q` value, ties are broken by the order of
+/// [`Encoding::available`]. Codings not listed get the quality of `*`, and `identity` is
+/// acceptable unless it is excluded with a quality of 0. Without an `Accept-Encoding` header, or
+/// when nothing is acceptable, the response is not compressed.
+///
+/// ```
+/// use hateoas::frameworks::compression::{negotiate, Encoding};
+///
+/// assert_eq!(negotiate(None), Encoding::Identity);
+/// assert_eq!(negotiate(Some("unknown")), Encoding::Identity);
+/// ```
+pub fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+    let codings = match accept_encoding {
+        Some(accept_encoding) => parse_accept_encoding(accept_encoding),
+        None => return Encoding::Identity,
+    };
+    let quality_of = |token: &str| {
+        codings
+            .iter()
+            .find(|(t, _)| t == token || (token == "gzip" && t == "x-gzip"))
+            .or_else(|| codings.iter().find(|(t, _)| t == "*"))
+            .map(|(_, q)| *q)
+    };
+    Encoding::available()
+        .into_iter()
+        .filter_map(|encoding| {
+            let quality = match (encoding, quality_of(encoding.token())) {
+                (_, Some(quality)) => quality,
+                (Encoding::Identity, None) => 1.0,
+                _ => 0.0,
+            };
+            (quality > 0.0).then_some((encoding, quality))
+        })
+        .fold(
+            None,
+            |best: Option<(Encoding, f32)>, (encoding, quality)| match best {
+                Some((_, best_quality)) if best_quality >= quality => best,
+                _ => Some((encoding, quality)),
+            },
+        )
+        .map(|(encoding, _)| encoding)
+        .unwrap_or(Encoding::Identity)
+}
+
+fn parse_accept_encoding(accept_encoding: &str) -> Vec<(String, f32)> {
+    accept_encoding
+        .split(',')
+        .filter_map(|coding| {
+            let mut parts = coding.split(';');
+            let token = parts.next()?.trim().to_ascii_lowercase();
+            if token.is_empty() {
+                return None;
+            }
+            let quality = parts
+                .filter_map(|t| t.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                .map(|(_, value)| value.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0)
+                .clamp(0.0, 1.0);
+            Some((token, quality))
+        })
+        .collect()
+}
+
+/// ## Compressing a response body
+/// The body is compressed with the negotiated coding when it is at least [`min_size`] bytes,
+/// the returned coding is the `Content-Encoding` of the response.
+pub fn compress(accept_encoding: Option<&str>, body: Vec<u8>) -> (Encoding, Vec<u8>) {
+    if body.len() < min_size() {
+        return (Encoding::Identity, body);
+    }
+    let encoding = negotiate(accept_encoding);
+    match encoding {
+        Encoding::Identity => (encoding, body),
+        _ => match encoding.compress(&body) {
+            Ok(compressed) => (encoding, compressed),
+            Err(_) => (Encoding::Identity, body),
+        },
+    }
+}
+
+/// ## Decompressing a request payload
+/// The codings of the `Content-Encoding` header are removed in the reverse order they were
+/// applied.
+///
+/// ```
+/// use hateoas::frameworks::compression::{decompress, CompressionError};
+///
+/// assert_eq!(decompress(None, b"{}".to_vec(), 1024), Ok(b"{}".to_vec()));
+/// assert_eq!(
+///     decompress(Some("unknown"), b"{}".to_vec(), 1024),
+///     Err(CompressionError::UnsupportedEncoding("unknown".to_string()))
+/// );
+/// ```
+pub fn decompress(
+    content_encoding: Option<&str>,
+    body: Vec<u8>,
+    limit: usize,
+) -> Result<Vec<u8>, CompressionError> {
+    let encodings = content_encoding
+        .unwrap_or_default()
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .map(Encoding::from_token)
+        .collect::<Result<Vec<Encoding>, CompressionError>>()?;
+    encodings
+        .into_iter()
+        .rev()
+        .try_fold(body, |body, encoding| match encoding {
+            Encoding::Identity => Ok(body),
+            _ => encoding.decompress(&body, limit),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decompress, negotiate, CompressionError, Encoding};
+
+    #[test]
+    fn identity_quality() {
+        assert_eq!(negotiate(Some("identity;q=0, *;q=0")), Encoding::Identity);
+        assert_eq!(negotiate(Some("")), Encoding::Identity);
+        assert_eq!(
+            Encoding::Identity.decompress(&[0; 16], 8),
+            Err(CompressionError::Overflow(8))
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn quality_values() {
+        assert_eq!(negotiate(Some("gzip, deflate, br")), Encoding::Brotli);
+        assert_eq!(
+            negotiate(Some("gzip;q=1.0, br;q=0.5, zstd;q=0.1")),
+            Encoding::Gzip
+        );
+        assert_eq!(negotiate(Some("x-gzip")), Encoding::Gzip);
+        assert_eq!(negotiate(Some("*, br;q=0")), Encoding::Zstd);
+        assert_eq!(
+            negotiate(Some("deflate;q=0.4, identity")),
+            Encoding::Identity
+        );
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn round_trip() {
+        let body = r#"{"apiVersion":"hateoas.io/0.0.1-beta","kind":"String"}"#.repeat(64);
+        for encoding in Encoding::available() {
+            let compressed = encoding.compress(body.as_bytes()).unwrap();
+            assert_eq!(
+                decompress(Some(encoding.token()), compressed, body.len()),
+                Ok(body.as_bytes().to_vec())
+            );
+        }
+        let compressed = Encoding::Gzip
+            .compress(&Encoding::Brotli.compress(body.as_bytes()).unwrap())
+            .unwrap();
+        assert_eq!(
+            decompress(Some("br, gzip"), compressed, body.len()),
+            Ok(body.as_bytes().to_vec())
+        );
+        assert_eq!(
+            decompress(Some("gzip"), body.as_bytes().to_vec(), body.len()),
+            Err(CompressionError::Decompression(
+                "gzip",
+                "invalid gzip header".to_string()
+            ))
+        );
+    }
+}
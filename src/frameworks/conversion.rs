@@ -0,0 +1,724 @@
+use crate::frameworks::media_type::MediaType;
+use crate::frameworks::negotiation::{self, MediaRange};
+use crate::{Hateoas, HateoasResource, StatusCode};
+use percent_encoding::percent_decode_str;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use simple_serde::{Decoded, SimpleDecoder};
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// # Convertible
+/// A version of a kind that can be converted through a hub type. All versions of a kind are
+/// converted to and from the hub (hub-and-spoke), this way `v1` can be converted into `v2` by
+/// going through the hub without `v1` knowing anything about `v2`.
+///
+/// The conversions themselves are declared with `From`/`TryFrom` between the version and the hub.
+pub trait Convertible:
+    HateoasResource + Clone + Serialize + DeserializeOwned + Send + Sync + 'static
+{
+    type Hub: HateoasResource + Clone + Send + Sync + 'static;
+}
+
+#[derive(Debug, Display)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The apiVersion is not registered for the kind
+    #[display(fmt = "apiVersion {} is not registered for kind {}", _1, _0)]
+    UnknownVersion(String, String),
+
+    /// The kind of the payload does not match the kind of the hub
+    #[display(fmt = "Kind {} does not match the expected kind {}", _0, _1)]
+    KindMismatch(String, String),
+
+    /// The conversion between a version and the hub failed
+    #[display(fmt = "Failed to convert to {}: {}", _0, _1)]
+    Conversion(String, String),
+
+    #[display(fmt = "Serializer/Deserializer error: {}", _0)]
+    SerializationDeserializationError(simple_serde::Error),
+
+    /// The converted version could not be encoded as the media type
+    #[display(fmt = "Failed to encode as {}", _0)]
+    Encoding(String),
+}
+
+impl From<simple_serde::Error> for ConversionError {
+    fn from(e: simple_serde::Error) -> Self {
+        Self::SerializationDeserializationError(e)
+    }
+}
+
+impl<T> From<ConversionError> for Hateoas<T>
+where
+    T: HateoasResource + Clone,
+{
+    fn from(e: ConversionError) -> Self {
+        match e {
+            ConversionError::UnknownVersion(_, _) | ConversionError::KindMismatch(_, _) => {
+                Hateoas::BAD_REQUEST(None, Some(e.to_string()))
+            }
+            ConversionError::Conversion(_, _) => {
+                Hateoas::UNPROCESSABLE_ENTITY(None, Some(e.to_string()))
+            }
+            ConversionError::SerializationDeserializationError(_) => {
+                Hateoas::UNPROCESSABLE_ENTITY(None, Some("Unknown format".to_string()))
+            }
+            ConversionError::Encoding(_) => {
+                Hateoas::INTERNAL_SERVER_ERROR(None, Some(e.to_string()))
+            }
+        }
+    }
+}
+
+type DecodeFn<H> =
+    Box<dyn Fn(&[u8], &str, bool) -> Result<Hateoas<H>, ConversionError> + Send + Sync>;
+type EncodeFn<H> =
+    Box<dyn Fn(Hateoas<H>, &str, Option<&str>) -> Result<Vec<u8>, ConversionError> + Send + Sync>;
+
+struct VersionConverter<H: HateoasResource + Clone> {
+    api_version: String,
+    decode: DecodeFn<H>,
+    encode: EncodeFn<H>,
+}
+
+/// Used for reading the `apiVersion` and `kind` of a payload before decoding the content.
+#[derive(Deserialize)]
+struct EnvelopeProbe {
+    #[serde(rename = "apiVersion")]
+    api_version: Option<String>,
+    kind: Option<String>,
+}
+
+/// # ConversionRegistry
+/// The registry of all the versions of a kind, the registry is used by the extractors for
+/// accepting any registered version of the kind and by the responses for down-converting the hub
+/// into the version requested by the client.
+///
+/// ```ignore
+/// let mut registry = ConversionRegistry::<BulletHub>::new();
+/// registry.register::<BulletV1>().register::<BulletV2>();
+///
+/// let hub = registry.decode(&body, "application/json")?;
+/// let v2: Hateoas<BulletV2> = registry.convert(hub)?;
+/// ```
+pub struct ConversionRegistry<H: HateoasResource + Clone> {
+    versions: Vec<VersionConverter<H>>,
+}
+
+impl<H> ConversionRegistry<H>
+where
+    H: HateoasResource + Clone + Send + Sync + 'static,
+{
+    pub fn new() -> Self {
+        Self { versions: vec![] }
+    }
+
+    /// ## Register a version of the kind
+    /// The version has to be convertible to and from the hub, registering the same apiVersion
+    /// twice will replace the previous registration.
+    pub fn register<V>(&mut self) -> &mut Self
+    where
+        V: Convertible<Hub = H> + TryFrom<H>,
+        H: TryFrom<V>,
+        <V as TryFrom<H>>::Error: Display,
+        <H as TryFrom<V>>::Error: Display,
+    {
+        let api_version = format!("{}/{}", V::GROUP, V::VERSION);
+        self.versions.retain(|t| t.api_version != api_version);
+        self.versions.push(VersionConverter {
+            api_version,
+            decode: Box::new(|bytes, content_type, strict| {
+                bytes
+                    .decode(content_type)
+                    .map(|t: Decoded<Hateoas<V>>| t.into())
+                    .or_else(|e| {
+                        if strict {
+                            return Err(e);
+                        }
+                        bytes
+                            .decode(content_type)
+                            .map(|t: Decoded<V>| Hateoas::from(t.into()))
+                    })
+                    .map_err(ConversionError::from)
+                    .and_then(to_hub::<V, H>)
+            }),
+            encode: Box::new(|hub, media_type, type_template| {
+                from_hub::<V, H>(hub).and_then(|t| {
+                    negotiation::encode(&t, media_type, type_template)
+                        .ok_or_else(|| ConversionError::Encoding(media_type.to_string()))
+                })
+            }),
+        });
+        self
+    }
+
+    /// ## Registered apiVersions
+    /// ```ignore
+    /// assert_eq!(registry.api_versions(), vec!["ammo.hateoas.io/v1", "ammo.hateoas.io/v2"]);
+    /// ```
+    pub fn api_versions(&self) -> Vec<&str> {
+        self.versions
+            .iter()
+            .map(|t| t.api_version.as_str())
+            .collect()
+    }
+
+    pub fn contains(&self, api_version: &str) -> bool {
+        self.versions.iter().any(|t| t.api_version == api_version)
+    }
+
+    /// ## Decoding a payload of any registered version
+    /// Decoding the payload into the hub, the version is taken from the `apiVersion` of the
+    /// payload. Payloads without an `apiVersion`, eg. a bare resource, are decoded as
+    /// `default_api_version`.
+    pub fn decode(
+        &self,
+        bytes: &[u8],
+        content_type: &str,
+        default_api_version: &str,
+    ) -> Result<Hateoas<H>, ConversionError> {
+        self.decode_with_strictness(bytes, content_type, default_api_version, false)
+    }
+
+    /// Decoding a payload of any registered version, strict decoding only accepts payloads
+    /// wrapped in the envelope.
+    pub(crate) fn decode_with_strictness(
+        &self,
+        bytes: &[u8],
+        content_type: &str,
+        default_api_version: &str,
+        strict: bool,
+    ) -> Result<Hateoas<H>, ConversionError> {
+        let probe: Option<EnvelopeProbe> = bytes
+            .decode(content_type)
+            .map(|t: Decoded<EnvelopeProbe>| t.into())
+            .ok();
+        if let Some(kind) = probe.as_ref().and_then(|t| t.kind.as_ref()) {
+            if *kind != H::kind() {
+                return Err(ConversionError::KindMismatch(kind.to_string(), H::kind()));
+            }
+        }
+        let api_version = probe
+            .and_then(|t| t.api_version)
+            .unwrap_or_else(|| default_api_version.to_string());
+
+        self.versions
+            .iter()
+            .find(|t| t.api_version == api_version)
+            .ok_or_else(|| ConversionError::UnknownVersion(H::kind(), api_version))
+            .and_then(|t| (t.decode)(bytes, content_type, strict))
+    }
+
+    /// ## Converting the hub into a version
+    /// Only registered versions are converted into, other versions are rejected with
+    /// [`ConversionError::UnknownVersion`].
+    pub fn convert<T>(&self, hub: Hateoas<H>) -> Result<Hateoas<T>, ConversionError>
+    where
+        T: Convertible<Hub = H> + TryFrom<H>,
+        <T as TryFrom<H>>::Error: Display,
+    {
+        let api_version = format!("{}/{}", T::GROUP, T::VERSION);
+        if !self.contains(&api_version) {
+            return Err(ConversionError::UnknownVersion(H::kind(), api_version));
+        }
+        from_hub::<T, H>(hub)
+    }
+
+    /// ## Encoding the hub as a version
+    /// Down-converting the hub into the requested `api_version` and encoding it as the media
+    /// type, the [`Representation`](crate::Representation)s included.
+    pub fn encode(
+        &self,
+        hub: Hateoas<H>,
+        api_version: &str,
+        media_type: &str,
+    ) -> Result<Vec<u8>, ConversionError> {
+        self.encode_with_type_template(hub, api_version, media_type, None)
+    }
+
+    /// Encoding the hub as a version, problem details use the `type_template` for the `type` URI.
+    pub(crate) fn encode_with_type_template(
+        &self,
+        hub: Hateoas<H>,
+        api_version: &str,
+        media_type: &str,
+        type_template: Option<&str>,
+    ) -> Result<Vec<u8>, ConversionError> {
+        self.versions
+            .iter()
+            .find(|t| t.api_version == api_version)
+            .ok_or_else(|| ConversionError::UnknownVersion(H::kind(), api_version.to_string()))
+            .and_then(|t| (t.encode)(hub, media_type, type_template))
+    }
+}
+
+impl<H> Default for ConversionRegistry<H>
+where
+    H: HateoasResource + Clone + Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn to_hub<V, H>(hateoas: Hateoas<V>) -> Result<Hateoas<H>, ConversionError>
+where
+    V: HateoasResource + Clone,
+    H: HateoasResource + Clone + TryFrom<V>,
+    <H as TryFrom<V>>::Error: Display,
+{
+    hateoas.try_map(|t| {
+        H::try_from(t).map_err(|e| ConversionError::Conversion(H::kind(), e.to_string()))
+    })
+}
+
+fn from_hub<V, H>(hateoas: Hateoas<H>) -> Result<Hateoas<V>, ConversionError>
+where
+    V: HateoasResource + Clone + TryFrom<H>,
+    H: HateoasResource + Clone,
+    <V as TryFrom<H>>::Error: Display,
+{
+    hateoas.try_map(|t| {
+        V::try_from(t).map_err(|e| {
+            ConversionError::Conversion(format!("{}/{}", V::GROUP, V::VERSION), e.to_string())
+        })
+    })
+}
+
+/// # Requested apiVersion
+/// Finding the apiVersion requested by the client, either through the `apiVersion` query
+/// parameter or the version of the `Accept` header, the query parameter takes precedence. The
+/// version of the `Accept` header is the `version` parameter or the version of a vendor media
+/// type, eg. `application/vnd.ammo.hateoas.io.bullet.v2+json`. Only the media ranges accepted
+/// with a quality above 0 are read, and vendor media types only when they are the resource. A
+/// bare version is prefixed with the group.
+///
+/// ```ignore
+/// let version = requested_api_version::<Bullet>(Some("application/json; version=v2"), None);
+///
+/// assert_eq!(version, Some("ammo.hateoas.io/v2".to_string()));
+/// ```
+pub fn requested_api_version<H: HateoasResource>(
+    accept: Option<&str>,
+    query: Option<&str>,
+) -> Option<String> {
+    let from_query = query.and_then(|q| {
+        q.split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(key, _)| *key == "apiVersion")
+            .map(|(_, value)| percent_decode_str(value).decode_utf8_lossy().to_string())
+    });
+    let from_accept = || {
+        accept.and_then(|a| {
+            MediaRange::parse_accept(a)
+                .iter()
+                .filter(|t| t.quality() > 0.0)
+                .filter_map(|t| t.media_type())
+                .filter(|t| t.vendor().is_none() || t.is_resource::<H>())
+                .find_map(|t| t.api_version::<H>())
+        })
+    };
+
+    from_query
+        .map(|version| {
+            if version.contains('/') || H::GROUP.is_empty() {
+                version
+            } else {
+                format!("{}/{}", H::GROUP, version)
+            }
+        })
+        .or_else(from_accept)
+}
+
+/// # RequestedVersion
+/// The version negotiation information of a request, used for down-converting a response into
+/// the version the client asked for.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestedVersion {
+    accept: Option<String>,
+    query: Option<String>,
+}
+
+impl RequestedVersion {
+    pub fn new(accept: Option<String>, query: Option<String>) -> Self {
+        Self { accept, query }
+    }
+
+    /// ## The requested apiVersion for the kind
+    /// See [`requested_api_version`].
+    pub fn api_version<H: HateoasResource>(&self) -> Option<String> {
+        requested_api_version::<H>(self.accept.as_deref(), self.query.as_deref())
+    }
+
+    /// ## Responding with the requested version
+    /// Creating a response that will down-convert the hub into the requested version, if no
+    /// version is requested the first registered version of the kind is used. The media type of
+    /// the response is negotiated the same way as for a [`Hateoas`] response.
+    pub fn respond<H>(
+        &self,
+        registry: Arc<ConversionRegistry<H>>,
+        hateoas: Hateoas<H>,
+    ) -> Downconverted<H>
+    where
+        H: HateoasResource + Clone + Send + Sync + 'static,
+    {
+        let api_version = self
+            .api_version::<H>()
+            .or_else(|| registry.api_versions().first().map(|t| t.to_string()));
+        Downconverted {
+            hateoas,
+            registry,
+            api_version,
+            accept: self.accept.clone(),
+        }
+    }
+}
+
+/// # Downconverted
+/// A response holding the hub which will be converted into the requested version when the
+/// response is send.
+pub struct Downconverted<H>
+where
+    H: HateoasResource + Clone + Send + Sync + 'static,
+{
+    hateoas: Hateoas<H>,
+    registry: Arc<ConversionRegistry<H>>,
+    api_version: Option<String>,
+    accept: Option<String>,
+}
+
+impl<H> Downconverted<H>
+where
+    H: HateoasResource + Clone + Send + Sync + 'static,
+{
+    pub fn api_version(&self) -> Option<&str> {
+        self.api_version.as_deref()
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        self.hateoas
+            .status()
+            .and_then(|t| t.http_status_code().clone())
+            .unwrap_or(StatusCode::OK)
+    }
+
+    /// ## Encoding the response
+    /// Converting the hub into the requested version and encoding it as the media type.
+    pub fn encode(self, media_type: &str) -> Result<Vec<u8>, ConversionError> {
+        let (hub, _, encode) = self.into_parts();
+        encode(&hub, media_type, None)
+    }
+
+    /// The hub, the `Accept` header of the request and the encoder down-converting the hub, used
+    /// by the integrations for responding through the same path as a [`Hateoas`].
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn into_parts(
+        self,
+    ) -> (
+        Hateoas<H>,
+        Option<String>,
+        impl FnOnce(&Hateoas<H>, &str, Option<&str>) -> Result<Vec<u8>, ConversionError>,
+    ) {
+        let Downconverted {
+            hateoas,
+            registry,
+            api_version,
+            accept,
+        } = self;
+        let encode = move |hub: &Hateoas<H>, media_type: &str, type_template: Option<&str>| {
+            let api_version = api_version
+                .ok_or_else(|| ConversionError::UnknownVersion(H::kind(), "".to_string()))?;
+            registry.encode_with_type_template(hub.clone(), &api_version, media_type, type_template)
+        };
+        (hateoas, accept, encode)
+    }
+}
+
+/// Stripping the media type parameters, as they are not understood by the encoders.
+pub(crate) fn essence(media_type: &str) -> &str {
+    media_type.split(';').next().unwrap_or(media_type).trim()
+}
+
+/// The media type a payload is decoded and encoded with, the codec of vendor media types and the
+/// essence of other media types.
+pub(crate) fn codec(media_type: &str) -> String {
+    MediaType::parse(media_type)
+        .ok()
+        .and_then(|t| t.vendor_codec())
+        .unwrap_or_else(|| essence(media_type))
+        .to_string()
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use simple_serde::SimpleEncoder;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BulletHub {
+        pub caliber: u32,
+        pub material: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BulletV1 {
+        pub caliber: u32,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct BulletV2 {
+        pub caliber: u32,
+        pub material: String,
+    }
+
+    impl HateoasResource for BulletHub {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "hub";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    impl HateoasResource for BulletV1 {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    impl HateoasResource for BulletV2 {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v2";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    impl From<BulletV1> for BulletHub {
+        fn from(t: BulletV1) -> Self {
+            BulletHub {
+                caliber: t.caliber,
+                material: None,
+            }
+        }
+    }
+
+    impl From<BulletHub> for BulletV1 {
+        fn from(t: BulletHub) -> Self {
+            BulletV1 { caliber: t.caliber }
+        }
+    }
+
+    impl From<BulletV2> for BulletHub {
+        fn from(t: BulletV2) -> Self {
+            BulletHub {
+                caliber: t.caliber,
+                material: Some(t.material),
+            }
+        }
+    }
+
+    impl TryFrom<BulletHub> for BulletV2 {
+        type Error = String;
+        fn try_from(t: BulletHub) -> Result<Self, Self::Error> {
+            Ok(BulletV2 {
+                caliber: t.caliber,
+                material: t.material.ok_or("material is required in v2")?,
+            })
+        }
+    }
+
+    impl Convertible for BulletV1 {
+        type Hub = BulletHub;
+    }
+
+    impl Convertible for BulletV2 {
+        type Hub = BulletHub;
+    }
+
+    #[test]
+    fn convert_into_unregistered_version() {
+        let mut registry = ConversionRegistry::<BulletHub>::new();
+        registry.register::<BulletV1>();
+        let hub = Hateoas::OK(
+            Some(BulletHub {
+                caliber: 9,
+                material: Some("lead".to_string()),
+            }),
+            None,
+        );
+
+        let v2: Result<Hateoas<BulletV2>, ConversionError> = registry.convert(hub);
+        assert!(matches!(v2, Err(ConversionError::UnknownVersion(_, _))));
+    }
+
+    pub(crate) fn registry() -> ConversionRegistry<BulletHub> {
+        let mut registry = ConversionRegistry::new();
+        registry.register::<BulletV1>().register::<BulletV2>();
+        registry
+    }
+
+    #[test]
+    fn decode_v1_convert_to_v2() {
+        let payload = Hateoas::OK(Some(BulletV1 { caliber: 9 }), None)
+            .encode("application/json")
+            .unwrap()
+            .to_vec();
+
+        let registry = registry();
+        let hub = registry
+            .decode(&payload, "application/json", "ammo.hateoas.io/v2")
+            .unwrap();
+        assert_eq!(hub.api_version(), "ammo.hateoas.io/hub");
+
+        let v2: Result<Hateoas<BulletV2>, ConversionError> = registry.convert(hub);
+        assert!(matches!(v2, Err(ConversionError::Conversion(_, _))));
+    }
+
+    #[test]
+    fn decode_bare_resource_as_default_version() {
+        let payload = br#"{"caliber": 9, "material": "lead"}"#;
+
+        let registry = registry();
+        let hub = registry
+            .decode(payload, "application/json", "ammo.hateoas.io/v2")
+            .unwrap();
+        let v1: Hateoas<BulletV1> = registry.convert(hub).unwrap();
+
+        assert_eq!(v1.api_version(), "ammo.hateoas.io/v1");
+        assert_eq!(
+            v1.spec().and_then(|t| t.content().clone()),
+            Some(BulletV1 { caliber: 9 })
+        );
+    }
+
+    #[test]
+    fn unknown_version_is_rejected() {
+        let payload = br#"{"apiVersion": "ammo.hateoas.io/v9", "kind": "Bullet"}"#;
+
+        let result = registry().decode(payload, "application/json", "ammo.hateoas.io/v1");
+        assert!(matches!(result, Err(ConversionError::UnknownVersion(_, _))));
+    }
+
+    #[test]
+    fn encode_down_converts() {
+        let hub = Hateoas::OK(
+            Some(BulletHub {
+                caliber: 9,
+                material: Some("lead".to_string()),
+            }),
+            None,
+        );
+
+        let bytes = registry()
+            .encode(hub, "ammo.hateoas.io/v1", "application/json")
+            .unwrap();
+        let v1 = serde_json::from_slice::<Hateoas<BulletV1>>(&bytes).unwrap();
+        assert_eq!(v1.api_version(), "ammo.hateoas.io/v1");
+    }
+
+    #[test]
+    fn respond_with_requested_version() {
+        let hub = Hateoas::OK(
+            Some(BulletHub {
+                caliber: 9,
+                material: Some("lead".to_string()),
+            }),
+            None,
+        );
+        let requested = RequestedVersion::new(
+            Some("application/x-yaml; version=v2, application/json".to_string()),
+            None,
+        );
+
+        let response = requested.respond(Arc::new(registry()), hub);
+        assert_eq!(response.api_version(), Some("ammo.hateoas.io/v2"));
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let bytes = response.encode("application/yaml").unwrap();
+        let yaml = String::from_utf8(bytes).unwrap();
+        assert!(yaml.contains("apiVersion: ammo.hateoas.io/v2"));
+    }
+
+    #[test]
+    fn requested_version_from_accept_and_query() {
+        assert_eq!(
+            requested_api_version::<BulletHub>(Some("application/json; version=v2"), None),
+            Some("ammo.hateoas.io/v2".to_string())
+        );
+        assert_eq!(
+            requested_api_version::<BulletHub>(
+                Some("application/json; version=v2"),
+                Some("limit=10&apiVersion=ammo.hateoas.io%2Fv1")
+            ),
+            Some("ammo.hateoas.io/v1".to_string())
+        );
+        assert_eq!(
+            requested_api_version::<BulletHub>(Some("application/json"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn requested_version_ignores_refused_ranges() {
+        assert_eq!(
+            requested_api_version::<BulletHub>(
+                Some("application/json; version=v9; q=0, application/json; version=v2"),
+                None
+            ),
+            Some("ammo.hateoas.io/v2".to_string())
+        );
+        assert_eq!(
+            requested_api_version::<BulletHub>(
+                Some("application/vnd.ammo.hateoas.io.bullet.v9+json; q=0, application/json"),
+                None
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn requested_version_ignores_other_resources() {
+        assert_eq!(
+            requested_api_version::<BulletHub>(
+                Some("application/vnd.other.v9+json, application/json"),
+                None
+            ),
+            None
+        );
+        assert_eq!(
+            requested_api_version::<BulletHub>(
+                Some(
+                    "application/vnd.other.v9+json, application/vnd.ammo.hateoas.io.bullet.v2+json"
+                ),
+                None
+            ),
+            Some("ammo.hateoas.io/v2".to_string())
+        );
+    }
+
+    #[test]
+    fn respond_with_vendor_media_type() {
+        let hub = Hateoas::OK(
+            Some(BulletHub {
+                caliber: 9,
+                material: Some("lead".to_string()),
+            }),
+            None,
+        );
+        let requested = RequestedVersion::new(
+            Some("application/vnd.ammo.hateoas.io.bullet.v2+yaml; profile=short".to_string()),
+            None,
+        );
+        assert_eq!(
+            requested.api_version::<BulletHub>(),
+            Some("ammo.hateoas.io/v2".to_string())
+        );
+
+        let response = requested.respond(Arc::new(registry()), hub);
+        assert_eq!(response.api_version(), Some("ammo.hateoas.io/v2"));
+
+        let bytes = response.encode("application/yaml").unwrap();
+        let yaml = String::from_utf8(bytes).unwrap();
+        assert!(yaml.contains("apiVersion: ammo.hateoas.io/v2"));
+    }
+}
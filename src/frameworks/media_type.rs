@@ -0,0 +1,317 @@
+//! Media types
+//!
+//! Parsing media types with a structured syntax suffix and parameters, eg.
+//! `application/vnd.ammo.hateoas.io.bullet.v2+json` or `application/json; version=v2`. The
+//! suffix selects the codec the payload is encoded with, while the vendor tree, version and
+//! profile selects the resource and the apiVersion.
+use crate::frameworks::negotiation::SERDE_MEDIA_TYPES;
+use crate::{HateoasResource, Representation};
+use simple_serde::ContentType;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Display, PartialEq)]
+#[non_exhaustive]
+pub enum MediaTypeError {
+    #[display(fmt = "Invalid media type: {}", _0)]
+    Invalid(String),
+}
+
+/// # MediaType
+/// A media type split into its type, subtype, structured syntax suffix and parameters. The type,
+/// subtype, suffix and parameter names are case-insensitive and kept in lowercase.
+///
+/// ```
+/// use hateoas::frameworks::media_type::MediaType;
+///
+/// let media_type: MediaType = "application/vnd.ammo.hateoas.io.bullet.v2+yaml; profile=\"https://hateoas.io/bullet\""
+///     .parse()
+///     .unwrap();
+///
+/// assert_eq!(media_type.essence(), "application/vnd.ammo.hateoas.io.bullet.v2+yaml");
+/// assert_eq!(media_type.suffix(), Some("yaml"));
+/// assert_eq!(media_type.vendor(), Some("ammo.hateoas.io.bullet"));
+/// assert_eq!(media_type.version(), Some("v2"));
+/// assert_eq!(media_type.profile(), Some("https://hateoas.io/bullet"));
+/// assert_eq!(media_type.codec(), Some("application/yaml"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaType {
+    main_type: String,
+    subtype: String,
+    suffix: Option<String>,
+    parameters: Vec<(String, String)>,
+}
+
+impl MediaType {
+    pub fn parse(media_type: &str) -> Result<Self, MediaTypeError> {
+        let invalid = || MediaTypeError::Invalid(media_type.to_string());
+        let mut parts = media_type.split(';');
+        let (main_type, subtype) = parts
+            .next()
+            .and_then(|t| t.trim().split_once('/'))
+            .map(|(t, s)| (t.trim().to_ascii_lowercase(), s.trim().to_ascii_lowercase()))
+            .filter(|(t, s)| !t.is_empty() && !s.is_empty() && !s.contains('/'))
+            .ok_or_else(invalid)?;
+        let (subtype, suffix) = match subtype.rsplit_once('+') {
+            Some((subtype, suffix)) if !subtype.is_empty() && !suffix.is_empty() => {
+                (subtype.to_string(), Some(suffix.to_string()))
+            }
+            Some(_) => return Err(invalid()),
+            None => (subtype, None),
+        };
+        let parameters = parts
+            .filter(|t| !t.trim().is_empty())
+            .map(|t| {
+                t.split_once('=')
+                    .map(|(name, value)| {
+                        (
+                            name.trim().to_ascii_lowercase(),
+                            value.trim().trim_matches('"').to_string(),
+                        )
+                    })
+                    .ok_or_else(invalid)
+            })
+            .collect::<Result<Vec<(String, String)>, MediaTypeError>>()?;
+        Ok(Self {
+            main_type,
+            subtype,
+            suffix,
+            parameters,
+        })
+    }
+
+    /// ## The vendor media type of a resource
+    /// Creating `application/vnd.<group>.<kind>.<version>+<suffix>`, the kind is lowercase.
+    ///
+    /// ```
+    /// use hateoas::frameworks::media_type::MediaType;
+    /// use hateoas::HateoasResource;
+    ///
+    /// #[derive(Clone)]
+    /// struct Bullet;
+    ///
+    /// impl HateoasResource for Bullet {
+    ///     const KIND: &'static str = "Bullet";
+    ///     const VERSION: &'static str = "v2";
+    ///     const GROUP: &'static str = "ammo.hateoas.io";
+    ///     const URL_PATH_SEGMENT: &'static str = "/bullets";
+    /// }
+    ///
+    /// let media_type = MediaType::vendor_type::<Bullet>("json");
+    ///
+    /// assert_eq!(media_type.to_string(), "application/vnd.ammo.hateoas.io.bullet.v2+json");
+    /// assert!(media_type.is_resource::<Bullet>());
+    /// assert_eq!(media_type.api_version::<Bullet>(), Some("ammo.hateoas.io/v2".to_string()));
+    /// ```
+    pub fn vendor_type<T: HateoasResource>(suffix: &str) -> Self {
+        Self {
+            main_type: "application".to_string(),
+            subtype: format!("vnd.{}.{}.{}", T::GROUP, T::kind(), T::VERSION).to_ascii_lowercase(),
+            suffix: Some(suffix.to_ascii_lowercase()),
+            parameters: vec![],
+        }
+    }
+
+    /// The media type without parameters.
+    pub fn essence(&self) -> String {
+        match &self.suffix {
+            Some(suffix) => format!("{}/{}+{}", self.main_type, self.subtype, suffix),
+            None => format!("{}/{}", self.main_type, self.subtype),
+        }
+    }
+
+    pub fn main_type(&self) -> &str {
+        &self.main_type
+    }
+
+    /// The subtype without the suffix.
+    pub fn subtype(&self) -> &str {
+        &self.subtype
+    }
+
+    pub fn suffix(&self) -> Option<&str> {
+        self.suffix.as_deref()
+    }
+
+    pub fn parameters(&self) -> &Vec<(String, String)> {
+        &self.parameters
+    }
+
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(t, _)| t.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// ## The vendor tree
+    /// The subtype of a `vnd.` media type without the prefix and the version.
+    pub fn vendor(&self) -> Option<&str> {
+        let vendor = self.subtype.strip_prefix("vnd.")?;
+        Some(
+            vendor_version(vendor)
+                .and_then(|version| vendor.strip_suffix(version))
+                .map(|t| t.trim_end_matches('.'))
+                .unwrap_or(vendor),
+        )
+    }
+
+    /// ## The requested version
+    /// The `version` parameter, or the last segment of the vendor tree when it is a version,
+    /// eg. `v2` of `vnd.ammo.hateoas.io.bullet.v2`.
+    pub fn version(&self) -> Option<&str> {
+        self.parameter("version")
+            .or_else(|| self.subtype.strip_prefix("vnd.").and_then(vendor_version))
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.parameter("profile")
+    }
+
+    /// ## The codec of the media type
+    /// The canonical media type of the `simple_serde` format selected by the suffix, or by the
+    /// media type itself when it has no suffix.
+    ///
+    /// ```
+    /// use hateoas::frameworks::media_type::MediaType;
+    ///
+    /// assert_eq!(MediaType::parse("application/json; version=v2").unwrap().codec(), Some("application/json"));
+    /// assert_eq!(MediaType::parse("application/vnd.bullet+cbor").unwrap().codec(), Some("application/x-cbor"));
+    /// assert_eq!(MediaType::parse("application/vnd.bullet+xml").unwrap().codec(), None);
+    /// ```
+    pub fn codec(&self) -> Option<&'static str> {
+        let content_type = match &self.suffix {
+            Some(suffix) => ContentType::try_from(suffix.as_str()),
+            None => ContentType::try_from(self.essence().as_str()),
+        }
+        .ok()?;
+        SERDE_MEDIA_TYPES
+            .into_iter()
+            .find(|t| ContentType::try_from(*t).ok().as_ref() == Some(&content_type))
+    }
+
+    /// ## The codec of a vendor media type
+    /// The codec of the suffix for media types with a structured syntax suffix, except for the
+    /// [`Representation`](crate::Representation)s which are encoded by their own format.
+    ///
+    /// ```
+    /// use hateoas::frameworks::media_type::MediaType;
+    ///
+    /// assert_eq!(MediaType::parse("application/vnd.ammo.bullet.v2+json").unwrap().vendor_codec(), Some("application/json"));
+    /// assert_eq!(MediaType::parse("application/hal+json").unwrap().vendor_codec(), None);
+    /// assert_eq!(MediaType::parse("application/yaml").unwrap().vendor_codec(), None);
+    /// ```
+    pub fn vendor_codec(&self) -> Option<&'static str> {
+        self.suffix
+            .as_ref()
+            .filter(|_| Representation::from_media_type(&self.essence()).is_none())
+            .and_then(|_| self.codec())
+    }
+
+    /// ## Checking if the vendor tree is the resource
+    /// The vendor tree has to be `<group>.<kind>`, compared case-insensitively.
+    pub fn is_resource<T: HateoasResource>(&self) -> bool {
+        self.vendor()
+            .map(|t| t.eq_ignore_ascii_case(&format!("{}.{}", T::GROUP, T::kind())))
+            .unwrap_or(false)
+    }
+
+    /// ## The requested apiVersion of the resource
+    /// The version prefixed with the group of the resource, unless it already has a group.
+    pub fn api_version<T: HateoasResource>(&self) -> Option<String> {
+        self.version().map(|version| {
+            if version.contains('/') || T::GROUP.is_empty() {
+                version.to_string()
+            } else {
+                format!("{}/{}", T::GROUP, version)
+            }
+        })
+    }
+}
+
+/// The last segment of a vendor tree if it is a version, eg. `v2` or `v1beta1`.
+fn vendor_version(vendor: &str) -> Option<&str> {
+    vendor.rsplit('.').next().filter(|t| {
+        t.len() > 1 && t.starts_with('v') && t[1..].starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+/// The essence of a media type for comparing, media types that do not parse are only trimmed and
+/// lowercased.
+pub(crate) fn essence(media_type: &str) -> String {
+    MediaType::parse(media_type)
+        .map(|t| t.essence())
+        .unwrap_or_else(|_| media_type.trim().to_ascii_lowercase())
+}
+
+impl FromStr for MediaType {
+    type Err = MediaTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for MediaType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.essence())?;
+        for (name, value) in &self.parameters {
+            if !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+            {
+                write!(f, "; {}={}", name, value)?;
+            } else {
+                write!(f, "; {}=\"{}\"", name, value.replace('"', "\\\""))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MediaType, MediaTypeError};
+
+    #[test]
+    fn parameters() {
+        let media_type =
+            MediaType::parse("Application/JSON; Version=v2; profile=\"https://hateoas.io/a b\"")
+                .unwrap();
+
+        assert_eq!(media_type.essence(), "application/json");
+        assert_eq!(media_type.suffix(), None);
+        assert_eq!(media_type.vendor(), None);
+        assert_eq!(media_type.version(), Some("v2"));
+        assert_eq!(media_type.profile(), Some("https://hateoas.io/a b"));
+        assert_eq!(
+            media_type.to_string(),
+            "application/json; version=v2; profile=\"https://hateoas.io/a b\""
+        );
+    }
+
+    #[test]
+    fn vendor_tree() {
+        let media_type = MediaType::parse("application/vnd.ammo.bullet+json").unwrap();
+        assert_eq!(media_type.vendor(), Some("ammo.bullet"));
+        assert_eq!(media_type.version(), None);
+
+        let media_type =
+            MediaType::parse("application/vnd.ammo.bullet.v1beta1+json; version=v3").unwrap();
+        assert_eq!(media_type.vendor(), Some("ammo.bullet"));
+        assert_eq!(media_type.version(), Some("v3"));
+        assert_eq!(media_type.codec(), Some("application/json"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            MediaType::parse("json"),
+            Err(MediaTypeError::Invalid("json".to_string()))
+        );
+        assert!(MediaType::parse("application/+json").is_err());
+        assert!(MediaType::parse("application/json; version").is_err());
+    }
+}
@@ -3,4 +3,18 @@ pub mod actix;
 #[cfg(feature = "axum")]
 pub mod axum;
 #[cfg(feature = "web-framework-support")]
+pub mod compression;
+#[cfg(feature = "web-framework-support")]
+pub mod conversion;
+#[cfg(feature = "web-framework-support")]
+pub mod media_type;
+#[cfg(feature = "web-framework-support")]
+pub mod negotiation;
+#[cfg(feature = "web-framework-support")]
 pub mod payload_control;
+#[cfg(feature = "web-framework-support")]
+pub mod prefer;
+#[cfg(feature = "web-framework-support")]
+pub mod rejection;
+#[cfg(feature = "rocket")]
+pub mod rocket;
@@ -0,0 +1,419 @@
+//! Content negotiation
+//!
+//! Selecting the media type of a response from the `Accept` header of the request, shared by the
+//! web framework integrations. The media ranges are ranked by their `q` value and specificity,
+//! and matched against the media types the response can be encoded as, the
+//! [`Representation`]s and the formats of `simple_serde`. Vendor media types with a structured
+//! syntax suffix, eg. `application/vnd.ammo.bullet.v2+json`, are matched by the codec of the
+//! suffix and echoed back as the `Content-Type` of the response when they name the resource.
+use crate::frameworks::media_type::MediaType;
+use crate::{Hateoas, HateoasResource, Representation};
+use serde::Serialize;
+use simple_serde::{ContentType, SimpleEncoder};
+
+/// The media types `simple_serde` can encode, the first is used when there is no `Accept` header.
+pub(crate) const SERDE_MEDIA_TYPES: [&str; 13] = [
+    "application/json",
+    "application/yaml",
+    "application/x-bson",
+    "application/x-cbor",
+    "application/x-flexbuffers",
+    "application/json5",
+    "application/x-lexpr",
+    "application/x-messagepack",
+    "application/x-pickle",
+    "application/x-postcard",
+    "application/ron",
+    "application/toml",
+    "application/x-url",
+];
+
+const PROBLEM_MEDIA_TYPES: [&str; 2] = ["application/problem+json", "application/problem+xml"];
+
+/// # MediaRange
+/// A media range of an `Accept` header, eg. `application/*;q=0.8`.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::MediaRange;
+///
+/// let ranges = MediaRange::parse_accept("application/*;q=0.8, application/hal+json");
+///
+/// assert_eq!(ranges[0].essence(), "application/*");
+/// assert_eq!(ranges[0].quality(), 0.8);
+/// assert!(ranges[0].matches("application/yaml"));
+/// assert_eq!(ranges[1].quality(), 1.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaRange {
+    essence: String,
+    quality: f32,
+    media_type: Option<MediaType>,
+}
+
+impl MediaRange {
+    /// ## Parsing an Accept header
+    /// Parsing the media ranges of an `Accept` header in the order they are listed, the `q`
+    /// parameter defaults to 1 and parameters other than `q` are ignored.
+    pub fn parse_accept(accept: &str) -> Vec<MediaRange> {
+        accept
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';');
+                let essence = parts.next()?.trim().to_ascii_lowercase();
+                if !essence.contains('/') {
+                    return None;
+                }
+                let mut parameters = parts.by_ref().take_while(|t| {
+                    !t.split_once('=')
+                        .map(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                        .unwrap_or(false)
+                });
+                let media_type = MediaType::parse(
+                    &std::iter::once(essence.as_str())
+                        .chain(parameters.by_ref())
+                        .collect::<Vec<&str>>()
+                        .join(";"),
+                )
+                .ok();
+                let quality = range
+                    .split(';')
+                    .skip(1)
+                    .filter_map(|t| t.split_once('='))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                    .map(|(_, value)| value.trim().parse::<f32>().unwrap_or(0.0))
+                    .unwrap_or(1.0)
+                    .clamp(0.0, 1.0);
+                Some(MediaRange {
+                    essence,
+                    quality,
+                    media_type,
+                })
+            })
+            .collect()
+    }
+
+    pub fn essence(&self) -> &str {
+        &self.essence
+    }
+
+    pub fn quality(&self) -> f32 {
+        self.quality
+    }
+
+    /// The media range with its parameters, the `q` parameter and the parameters after it are
+    /// not included.
+    pub fn media_type(&self) -> Option<&MediaType> {
+        self.media_type.as_ref()
+    }
+
+    /// ## Checking if the media type is in the range
+    /// The aliases of the `simple_serde` formats are matching each other, eg. `application/x-ron`
+    /// matches `application/ron`, and vendor media types are matching the codec of their suffix.
+    pub fn matches(&self, media_type: &str) -> bool {
+        let media_type = media_type.to_ascii_lowercase();
+        match self.essence.split_once('/') {
+            Some(("*", "*")) => true,
+            Some((range_type, "*")) => media_type
+                .split_once('/')
+                .map(|(t, _)| t == range_type)
+                .unwrap_or(false),
+            _ => {
+                self.essence == media_type
+                    || matches!(
+                        (
+                            ContentType::try_from(self.essence.as_str()),
+                            ContentType::try_from(media_type.as_str())
+                        ),
+                        (Ok(range), Ok(media_type)) if range == media_type
+                    )
+                    || self.vendor_codec() == Some(media_type.as_str())
+            }
+        }
+    }
+
+    fn vendor_codec(&self) -> Option<&'static str> {
+        self.media_type.as_ref().and_then(|t| t.vendor_codec())
+    }
+
+    /// `*/*` is the least specific and a full media type the most specific.
+    fn specificity(&self) -> u8 {
+        match self.essence.split_once('/') {
+            Some(("*", _)) => 0,
+            Some((_, "*")) => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// ## The media types responses can be encoded as
+/// The `simple_serde` formats followed by the [`Representation`]s, the problem representations
+/// are only used for error responses.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::media_types;
+///
+/// assert_eq!(media_types()[0], "application/json");
+/// assert!(media_types().contains(&"application/hal+json"));
+/// ```
+pub fn media_types() -> Vec<&'static str> {
+    SERDE_MEDIA_TYPES
+        .into_iter()
+        .chain(
+            Representation::ALL
+                .iter()
+                .map(|t| t.media_type())
+                .filter(|t| !PROBLEM_MEDIA_TYPES.contains(t)),
+        )
+        .collect()
+}
+
+/// ## Negotiating the media type
+/// Selecting the available media type with the highest quality, each media type gets the quality
+/// of the most specific media range matching it. Ties are broken by the specificity of the media
+/// range, then by the order of the `Accept` header and last by the order of the available media
+/// types. Media ranges with a quality of 0 excludes the media type.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::negotiate;
+///
+/// let available = ["application/json", "application/yaml", "application/hal+json"];
+///
+/// assert_eq!(negotiate("application/yaml;q=0.5, application/*;q=0.8", &available), Some("application/json"));
+/// assert_eq!(negotiate("*/*, application/json;q=0", &available), Some("application/yaml"));
+/// assert_eq!(negotiate("text/html", &available), None);
+/// ```
+pub fn negotiate(accept: &str, available: &[&'static str]) -> Option<&'static str> {
+    rank(&MediaRange::parse_accept(accept), available)
+}
+
+fn rank(ranges: &[MediaRange], available: &[&'static str]) -> Option<&'static str> {
+    available
+        .iter()
+        .enumerate()
+        .filter_map(|(index, media_type)| {
+            ranges
+                .iter()
+                .enumerate()
+                .filter(|(_, range)| range.matches(media_type))
+                .max_by_key(|(position, range)| (range.specificity(), usize::MAX - position))
+                .filter(|(_, range)| range.quality > 0.0)
+                .map(|(position, range)| (range.quality, range.specificity(), position, index))
+        })
+        .min_by(|a, b| {
+            b.0.total_cmp(&a.0)
+                .then(b.1.cmp(&a.1))
+                .then(a.2.cmp(&b.2))
+                .then(a.3.cmp(&b.3))
+        })
+        .map(|(_, _, _, index)| available[index])
+}
+
+/// ## Selecting the media type of a response
+/// Without an `Accept` header the response is encoded as JSON. Error responses are encoded as
+/// problem details if the client explicitly accepts them, and falls back to JSON instead of
+/// `406 Not Acceptable` when nothing matches.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::select;
+///
+/// assert_eq!(select(None, false), Some("application/json"));
+/// assert_eq!(select(Some("text/html"), false), None);
+/// assert_eq!(select(Some("application/json, application/problem+json"), true), Some("application/problem+json"));
+/// assert_eq!(select(Some("text/html"), true), Some("application/json"));
+/// ```
+pub fn select(accept: Option<&str>, is_error: bool) -> Option<&'static str> {
+    let accept = match accept.filter(|t| !t.trim().is_empty()) {
+        Some(accept) => accept,
+        None => return Some(SERDE_MEDIA_TYPES[0]),
+    };
+    let ranges = MediaRange::parse_accept(accept);
+    if is_error {
+        let explicit = ranges
+            .iter()
+            .filter(|t| t.specificity() == 2)
+            .cloned()
+            .collect::<Vec<MediaRange>>();
+        return rank(&explicit, &PROBLEM_MEDIA_TYPES)
+            .or_else(|| rank(&ranges, &media_types()))
+            .or(Some(SERDE_MEDIA_TYPES[0]));
+    }
+    rank(&ranges, &media_types())
+}
+
+/// ## The Content-Type of the response
+/// The media range the selected media type was matched by is echoed back, with its parameters,
+/// when it is a vendor media type of the resource and its version, otherwise the selected media
+/// type is used.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::{content_type, select};
+/// use hateoas::HateoasResource;
+///
+/// struct Bullet;
+///
+/// impl HateoasResource for Bullet {
+///     const KIND: &'static str = "Bullet";
+///     const VERSION: &'static str = "v2";
+///     const GROUP: &'static str = "ammo";
+///     const URL_PATH_SEGMENT: &'static str = "bullets";
+/// }
+///
+/// let accept = "application/vnd.ammo.bullet.v2+yaml; profile=bullet, application/json;q=0.5";
+/// let media_type = select(Some(accept), false).unwrap();
+///
+/// assert_eq!(media_type, "application/yaml");
+/// assert_eq!(content_type::<Bullet>(Some(accept), media_type), "application/vnd.ammo.bullet.v2+yaml; profile=bullet");
+/// assert_eq!(content_type::<Bullet>(Some("application/vnd.ammo.bullet.v1+yaml"), media_type), "application/yaml");
+/// assert_eq!(content_type::<Bullet>(Some("application/x-ron"), "application/ron"), "application/ron");
+/// assert_eq!(content_type::<Bullet>(Some("application/json; version=v2"), "application/json"), "application/json");
+/// ```
+pub fn content_type<T: HateoasResource>(accept: Option<&str>, media_type: &'static str) -> String {
+    version_content_type::<T>(accept, media_type, T::VERSION)
+}
+
+/// The Content-Type of the response for a `version` of the resource other than `T::VERSION`, used
+/// for down-converted responses.
+pub(crate) fn version_content_type<T: HateoasResource>(
+    accept: Option<&str>,
+    media_type: &'static str,
+    version: &str,
+) -> String {
+    accept
+        .map(MediaRange::parse_accept)
+        .and_then(|ranges| {
+            ranges
+                .into_iter()
+                .find(|t| t.specificity() == 2 && t.matches(media_type))
+        })
+        .filter(|t| t.vendor_codec().is_some())
+        .and_then(|t| t.media_type)
+        .filter(|t| t.is_resource::<T>() && t.version() == Some(version))
+        .map(|t| t.to_string())
+        .unwrap_or_else(|| media_type.to_string())
+}
+
+/// ## Encoding the response as the media type
+/// The `type_template` is used for the `type` URI of problem details.
+pub fn encode<T>(
+    hateoas: &Hateoas<T>,
+    media_type: &str,
+    type_template: Option<&str>,
+) -> Option<Vec<u8>>
+where
+    T: Serialize + HateoasResource + Clone,
+{
+    match Representation::from_media_type(media_type) {
+        Some(representation) => representation
+            .encode_with_type_template(hateoas, type_template)
+            .ok(),
+        None => hateoas.encode(media_type).ok().map(|t| t.to_vec()),
+    }
+}
+
+/// ## The response when nothing is acceptable
+/// A `406 Not Acceptable` listing the media types that are available.
+///
+/// ```
+/// use hateoas::frameworks::negotiation::not_acceptable;
+///
+/// let response = not_acceptable();
+///
+/// assert!(response.status().and_then(|t| t.message().as_ref()).unwrap().contains("application/hal+json"));
+/// ```
+pub fn not_acceptable() -> Hateoas<()> {
+    Hateoas::NOT_ACCEPTABLE(
+        None,
+        Some(format!(
+            "None of the accepted media types are available, available media types: {}",
+            media_types().join(", ")
+        )),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{content_type, negotiate, select, MediaRange};
+    use crate::HateoasResource;
+
+    struct Bullet;
+
+    impl HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    #[test]
+    fn quality_and_specificity() {
+        let available = [
+            "application/json",
+            "application/yaml",
+            "application/hal+json",
+        ];
+
+        assert_eq!(
+            negotiate(
+                "application/*;q=0.2, application/hal+json;q=0.9, */*;q=0.1",
+                &available
+            ),
+            Some("application/hal+json")
+        );
+        assert_eq!(
+            negotiate("application/yaml, application/json", &available),
+            Some("application/yaml")
+        );
+        assert_eq!(
+            negotiate("application/*, application/json;q=0", &available),
+            Some("application/yaml")
+        );
+        assert_eq!(negotiate("*/*;q=0", &available), None);
+    }
+
+    #[test]
+    fn serde_aliases() {
+        assert!(MediaRange::parse_accept("application/x-ron")[0].matches("application/ron"));
+        assert_eq!(
+            select(Some("application/x-ron"), false),
+            Some("application/ron")
+        );
+    }
+
+    #[test]
+    fn errors_prefer_problem_details() {
+        assert_eq!(
+            select(Some("*/*, application/problem+xml;q=0.5"), true),
+            Some("application/problem+xml")
+        );
+        assert_eq!(
+            select(Some("application/hal+json, */*"), true),
+            Some("application/hal+json")
+        );
+        assert_eq!(
+            select(Some("application/problem+json;q=0, application/yaml"), true),
+            Some("application/yaml")
+        );
+    }
+
+    #[test]
+    fn vendor_content_type_of_other_resource() {
+        for accept in [
+            "application/vnd.ammo.shell.v1+yaml",
+            "application/vnd.ammo.bullet.v2+yaml",
+            "application/vnd.bullet.v1+yaml",
+        ] {
+            let media_type = select(Some(accept), false).unwrap();
+            assert_eq!(
+                content_type::<Bullet>(Some(accept), media_type),
+                "application/yaml"
+            );
+        }
+        assert_eq!(
+            content_type::<Bullet>(
+                Some("application/vnd.ammo.bullet.v1+yaml"),
+                "application/yaml"
+            ),
+            "application/vnd.ammo.bullet.v1+yaml"
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! Preferences
+//!
+//! The preferences of the `Prefer` header as described in
+//! [RFC 7240](https://www.rfc-editor.org/rfc/rfc7240). The `return` preference is applied by the
+//! web framework integrations when the response is sent, the other preferences are up to the
+//! handler which can extract the [`Preferences`] of the request.
+use crate::{Hateoas, HateoasResource};
+use std::time::Duration;
+
+/// The methods which do not change the resource, their responses are always the representation.
+const SAFE_METHODS: [&str; 4] = ["GET", "HEAD", "OPTIONS", "TRACE"];
+
+/// # ReturnPreference
+/// The `return` preference, whether the client wants the resource in the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReturnPreference {
+    /// `return=minimal`, only the status and the headers, eg. `Location`.
+    Minimal,
+    /// `return=representation`, the full resource.
+    Representation,
+}
+
+/// # Handling
+/// The `handling` preference, whether the server should reject or ignore invalid parts of the
+/// request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handling {
+    Strict,
+    Lenient,
+}
+
+/// # Preferences
+/// The preferences of a request, unknown preferences are ignored and only the first instance of a
+/// preference is used.
+///
+/// ```
+/// use hateoas::frameworks::prefer::{Handling, Preferences, ReturnPreference};
+/// use std::time::Duration;
+///
+/// let preferences = Preferences::parse("return=minimal; foo=\"bar\", handling=lenient, respond-async, wait=10");
+///
+/// assert_eq!(preferences.return_preference(), Some(ReturnPreference::Minimal));
+/// assert_eq!(preferences.handling(), Some(Handling::Lenient));
+/// assert!(preferences.respond_async());
+/// assert_eq!(preferences.wait(), Some(Duration::from_secs(10)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Preferences {
+    return_preference: Option<ReturnPreference>,
+    handling: Option<Handling>,
+    respond_async: bool,
+    wait: Option<Duration>,
+}
+
+impl Preferences {
+    /// ## Parsing the Prefer header
+    /// Multiple `Prefer` headers are parsed by joining them with a comma.
+    pub fn parse(prefer: &str) -> Self {
+        let mut preferences = Self::default();
+        let mut seen = Vec::new();
+        for preference in split_unquoted(prefer, ',') {
+            let token = split_unquoted(preference, ';')
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+            let (name, value) = match token.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (token.trim(), None),
+            };
+            let name = name.to_ascii_lowercase();
+            if name.is_empty() || seen.contains(&name) {
+                continue;
+            }
+            match (name.as_str(), value) {
+                ("return", Some(value)) if value.eq_ignore_ascii_case("minimal") => {
+                    preferences.return_preference = Some(ReturnPreference::Minimal)
+                }
+                ("return", Some(value)) if value.eq_ignore_ascii_case("representation") => {
+                    preferences.return_preference = Some(ReturnPreference::Representation)
+                }
+                ("handling", Some(value)) if value.eq_ignore_ascii_case("strict") => {
+                    preferences.handling = Some(Handling::Strict)
+                }
+                ("handling", Some(value)) if value.eq_ignore_ascii_case("lenient") => {
+                    preferences.handling = Some(Handling::Lenient)
+                }
+                ("respond-async", _) => preferences.respond_async = true,
+                ("wait", Some(value)) => {
+                    preferences.wait = value.parse::<u64>().ok().map(Duration::from_secs)
+                }
+                _ => {}
+            }
+            seen.push(name);
+        }
+        preferences
+    }
+
+    pub fn return_preference(&self) -> Option<ReturnPreference> {
+        self.return_preference
+    }
+
+    pub fn handling(&self) -> Option<Handling> {
+        self.handling
+    }
+
+    pub fn respond_async(&self) -> bool {
+        self.respond_async
+    }
+
+    pub fn wait(&self) -> Option<Duration> {
+        self.wait
+    }
+
+    /// ## Applying the return preference to a response
+    /// With `return=minimal` the content of the spec is dropped, the links are kept. Returns the
+    /// value of the `Preference-Applied` header. The preference is not applied to the responses of
+    /// safe methods, eg. `GET`, and to error responses.
+    ///
+    /// ```
+    /// use hateoas::frameworks::prefer::Preferences;
+    /// use hateoas::Hateoas;
+    ///
+    /// let mut response = Hateoas::CREATED(Some("bullet".to_string()), None);
+    ///
+    /// assert_eq!(Preferences::parse("wait=5").apply("POST", &mut response), None);
+    /// assert_eq!(Preferences::parse("return=minimal").apply("GET", &mut response), None);
+    /// assert_eq!(Preferences::parse("return=minimal").apply("POST", &mut response), Some("return=minimal"));
+    /// assert_eq!(response.spec().and_then(|t| t.content().as_ref()), None);
+    /// ```
+    pub fn apply<T>(&self, method: &str, hateoas: &mut Hateoas<T>) -> Option<&'static str>
+    where
+        T: HateoasResource + Clone,
+    {
+        let is_error = hateoas
+            .status()
+            .and_then(|t| t.http_status_code().clone())
+            .map(|t| u16::from(t) >= 400)
+            .unwrap_or(false);
+        if is_error || SAFE_METHODS.contains(&method) {
+            return None;
+        }
+        match self.return_preference? {
+            ReturnPreference::Minimal => {
+                if hateoas.spec().is_some() {
+                    *hateoas.spec_mut().content_mut() = None;
+                }
+                Some("return=minimal")
+            }
+            ReturnPreference::Representation => Some("return=representation"),
+        }
+    }
+}
+
+/// Splitting on the separator when it is not inside a quoted string.
+fn split_unquoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&value[start..index]);
+                start = index + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Handling, Preferences, ReturnPreference};
+    use crate::Hateoas;
+
+    #[test]
+    fn first_instance_is_used() {
+        let preferences = Preferences::parse(
+            "Return=Representation, return=minimal, handling=\"strict\", handling=lenient",
+        );
+
+        assert_eq!(
+            preferences.return_preference(),
+            Some(ReturnPreference::Representation)
+        );
+        assert_eq!(preferences.handling(), Some(Handling::Strict));
+        assert!(!preferences.respond_async());
+    }
+
+    #[test]
+    fn quoted_separators() {
+        let preferences = Preferences::parse("foo=\"a, b; return=minimal\", wait=x, respond-async");
+
+        assert_eq!(preferences.return_preference(), None);
+        assert_eq!(preferences.wait(), None);
+        assert!(preferences.respond_async());
+        assert_eq!(Preferences::parse(""), Preferences::default());
+    }
+
+    #[test]
+    fn safe_methods_and_errors_are_not_minimized() {
+        let preferences = Preferences::parse("return=minimal");
+
+        for method in ["GET", "HEAD", "OPTIONS", "TRACE"] {
+            let mut response = Hateoas::OK(Some("bullet".to_string()), None);
+            assert_eq!(preferences.apply(method, &mut response), None);
+            assert_eq!(
+                response.spec().and_then(|t| t.content().as_ref()),
+                Some(&"bullet".to_string())
+            );
+        }
+
+        let mut response: Hateoas<String> = Hateoas::CONFLICT(
+            Some("bullet".to_string()),
+            Some("Name is taken".to_string()),
+        );
+        assert_eq!(preferences.apply("PUT", &mut response), None);
+        assert_eq!(
+            response.spec().and_then(|t| t.content().as_ref()),
+            Some(&"bullet".to_string())
+        );
+    }
+}
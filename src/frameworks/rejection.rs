@@ -0,0 +1,344 @@
+//! Rejections
+//!
+//! The reasons an extractor rejects a request and the [`RejectionHandler`] mapping them into the
+//! [`Hateoas`] error envelope of the response. A handler is registered on the `HateoasConfig` of
+//! actix or the `HateoasExtractorConfig` of axum, without a handler the [`DefaultRejectionHandler`]
+//! is used.
+use crate::frameworks::compression::CompressionError;
+use crate::frameworks::conversion::ConversionError;
+use crate::{Hateoas, HateoasResource, PaginationError, RepresentationError};
+
+/// # DecodeError
+/// A payload that could not be decoded, with the format and the position of the error for the
+/// formats reporting it.
+#[derive(Debug, Display)]
+#[display(fmt = "{}", error)]
+pub struct DecodeError {
+    format: Option<&'static str>,
+    message: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    error: simple_serde::Error,
+}
+
+impl DecodeError {
+    /// The name of the format, eg. `JSON`, `None` when the error is not from a format.
+    pub fn format(&self) -> Option<&'static str> {
+        self.format
+    }
+
+    /// The error message of the format.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> Option<usize> {
+        self.column
+    }
+
+    pub fn error(&self) -> &simple_serde::Error {
+        &self.error
+    }
+}
+
+impl From<simple_serde::Error> for DecodeError {
+    fn from(error: simple_serde::Error) -> Self {
+        use simple_serde::Error;
+        let (format, message, position) = match &error {
+            Error::BsonSerializationFailure(e) => (Some("BSON"), e.to_string(), None),
+            Error::BsonDeserializationFailure(e) => (Some("BSON"), e.to_string(), None),
+            Error::CborFailure(e) => (Some("CBOR"), e.to_string(), None),
+            Error::FlexBuffersSerializationFailure(e) => (Some("Flexbuffer"), e.to_string(), None),
+            Error::FlexBuffersDeserializationFailure(e) => {
+                (Some("Flexbuffer"), e.to_string(), None)
+            }
+            Error::JsonError(e) => (Some("JSON"), e.to_string(), Some((e.line(), e.column()))),
+            Error::Json5Error(e) => (Some("JSON5"), e.to_string(), None),
+            Error::LexprError(e) => (Some("Lexpr"), e.to_string(), None),
+            Error::MessagePackEncodeError(e) => (Some("Message Pack"), e.to_string(), None),
+            Error::MessagePackDecodeError(e) => (Some("Message Pack"), e.to_string(), None),
+            Error::PickleError(e) => (Some("Pickle"), e.to_string(), None),
+            Error::PostcardError(e) => (Some("Postcard"), e.to_string(), None),
+            Error::RonError(e) => (Some("Ron"), e.to_string(), None),
+            Error::RonDecodeError(e) => (
+                Some("Ron"),
+                e.to_string(),
+                Some((e.position.line, e.position.col)),
+            ),
+            Error::TomlSerializationFailure(e) => (Some("TOML"), e.to_string(), None),
+            Error::TomlDeserializationFailure(e) => (Some("TOML"), e.to_string(), None),
+            Error::UrlEncodingFailure(e) => (Some("URL Encoded"), format!("{:?}", e), None),
+            Error::YamlError(e) => (
+                Some("YAML"),
+                e.to_string(),
+                e.location().map(|t| (t.line(), t.column())),
+            ),
+            e => (None, e.to_string(), None),
+        };
+        Self {
+            format,
+            message,
+            line: position.map(|(line, _)| line),
+            column: position.map(|(_, column)| column),
+            error,
+        }
+    }
+}
+
+/// # Rejection
+/// The reason an extractor rejected the request.
+#[derive(Debug, Display)]
+#[non_exhaustive]
+pub enum Rejection {
+    /// The request has no `Content-Type` header
+    #[display(fmt = "Content-Type not found in request")]
+    MissingContentType,
+
+    /// The `Content-Type` is not supported or not allowed by the config
+    #[display(fmt = "Content-Type {} is not accepted", _0)]
+    UnsupportedContentType(String),
+
+    /// The payload is larger than the limit, the length is known when it was announced by the
+    /// `Content-Length` header
+    #[display(fmt = "Payload is larger than the limit of {} bytes", limit)]
+    Overflow { length: Option<usize>, limit: usize },
+
+    /// The payload could not be read
+    #[display(fmt = "Failed to read the payload: {}", _0)]
+    Payload(String),
+
+    #[display(fmt = "Failed to decode the payload: {}", _0)]
+    Decode(DecodeError),
+
+    /// The kind or apiVersion of the payload is unknown, or the payload could not be converted
+    #[display(fmt = "{}", _0)]
+    Conversion(ConversionError),
+
+    #[display(fmt = "{}", _0)]
+    Representation(RepresentationError),
+
+    #[display(fmt = "{}", _0)]
+    Compression(CompressionError),
+
+    #[display(fmt = "Invalid pagination query: {}", _0)]
+    Pagination(PaginationError),
+
+    /// Failures that are not caused by the request
+    #[display(fmt = "{}", _0)]
+    Internal(String),
+}
+
+impl From<simple_serde::Error> for Rejection {
+    fn from(e: simple_serde::Error) -> Self {
+        Self::Decode(e.into())
+    }
+}
+
+impl From<ConversionError> for Rejection {
+    fn from(e: ConversionError) -> Self {
+        Self::Conversion(e)
+    }
+}
+
+impl From<RepresentationError> for Rejection {
+    fn from(e: RepresentationError) -> Self {
+        Self::Representation(e)
+    }
+}
+
+impl From<CompressionError> for Rejection {
+    fn from(e: CompressionError) -> Self {
+        Self::Compression(e)
+    }
+}
+
+impl From<PaginationError> for Rejection {
+    fn from(e: PaginationError) -> Self {
+        Self::Pagination(e)
+    }
+}
+
+impl<T> From<Rejection> for Hateoas<T>
+where
+    T: HateoasResource + Clone,
+{
+    fn from(rejection: Rejection) -> Self {
+        match rejection {
+            Rejection::MissingContentType | Rejection::UnsupportedContentType(_) => {
+                Hateoas::UNSUPPORTED_MEDIA_TYPE(None, Some(rejection.to_string()))
+            }
+            Rejection::Overflow { .. } => {
+                Hateoas::PAYLOAD_TOO_LARGE(None, Some(rejection.to_string()))
+            }
+            Rejection::Payload(_) => {
+                Hateoas::BAD_REQUEST(None, Some("Corrupted payload".to_string()))
+            }
+            Rejection::Decode(e) => decode_error(e),
+            Rejection::Conversion(e) => e.into(),
+            Rejection::Representation(e) => e.into(),
+            Rejection::Compression(e) => e.into(),
+            Rejection::Pagination(e) => Hateoas::BAD_REQUEST(None, Some(e.to_string())),
+            Rejection::Internal(_) => Hateoas::INTERNAL_SERVER_ERROR(None, None),
+        }
+    }
+}
+
+fn decode_error<T: HateoasResource + Clone>(e: DecodeError) -> Hateoas<T> {
+    use simple_serde::Error;
+    if let Some(format) = e.format {
+        return Hateoas::BAD_REQUEST(
+            None,
+            Some(format!("{} processing error: {}", format, e.message)),
+        );
+    }
+    match e.error {
+        Error::Infallible => {
+            Hateoas::INTERNAL_SERVER_ERROR(None, Some("How did you end up here?".to_string()))
+        }
+        Error::ByteToUTF8ConversionFailure(_) => Hateoas::BAD_REQUEST(
+            None,
+            Some("Payload data not readable into UTF8".to_string()),
+        ),
+        Error::UnknownContentTypeMatchFromStr(_) => {
+            Hateoas::BAD_REQUEST(None, Some("Unknown content type given".to_string()))
+        }
+        Error::TypeDoesNotSupportSerialization(_) => Hateoas::BAD_REQUEST(
+            None,
+            Some("Payload datatype does not support De-/Serialization".to_string()),
+        ),
+        Error::FailedConvertingHeaderValueToContentType(_) | Error::InvalidHeaderValue(_) => {
+            Hateoas::BAD_REQUEST(
+                None,
+                Some("Header for typecontent is not supported".to_string()),
+            )
+        }
+        _ => Hateoas::BAD_REQUEST(None, Some("Unknown payload content".to_string())),
+    }
+}
+
+/// # RejectionHandler
+/// Mapping the rejections of the extractors into the error envelope of the response, the default
+/// implementation is the mapping used when no handler is registered.
+///
+/// ```
+/// use hateoas::frameworks::rejection::{DefaultRejectionHandler, Rejection, RejectionHandler};
+/// use hateoas::Hateoas;
+///
+/// struct PositionedRejections;
+///
+/// impl RejectionHandler for PositionedRejections {
+///     fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+///         match rejection {
+///             Rejection::Decode(e) => Hateoas::UNPROCESSABLE_ENTITY(
+///                 None,
+///                 Some(format!("invalid {} at {:?}:{:?}", e.format().unwrap_or("payload"), e.line(), e.column())),
+///             ),
+///             rejection => DefaultRejectionHandler.reject(rejection),
+///         }
+///     }
+/// }
+///
+/// let rejection = Rejection::from(serde_json::from_str::<u8>("1x").map_err(simple_serde::Error::JsonError).unwrap_err());
+/// let response = PositionedRejections.reject(rejection);
+///
+/// assert_eq!(response.status().and_then(|t| t.message().as_deref()), Some("invalid JSON at Some(1):Some(2)"));
+/// ```
+pub trait RejectionHandler: Send + Sync {
+    fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+        rejection.into()
+    }
+}
+
+/// # DefaultRejectionHandler
+/// The [`RejectionHandler`] used when no handler is registered.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultRejectionHandler;
+
+impl RejectionHandler for DefaultRejectionHandler {}
+
+impl<F> RejectionHandler for F
+where
+    F: Fn(Rejection) -> Hateoas<()> + Send + Sync,
+{
+    fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+        self(rejection)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DecodeError, DefaultRejectionHandler, Rejection, RejectionHandler};
+    use crate::frameworks::conversion::ConversionError;
+    use crate::{status_code, Hateoas, StatusCode};
+
+    fn status(hateoas: &Hateoas<()>) -> Option<StatusCode> {
+        hateoas.status().and_then(|t| t.http_status_code().clone())
+    }
+
+    #[test]
+    fn decode_position() {
+        let error = serde_json::from_str::<Vec<u8>>("[1,\n 2,]")
+            .map_err(simple_serde::Error::JsonError)
+            .unwrap_err();
+        let error = DecodeError::from(error);
+
+        assert_eq!(error.format(), Some("JSON"));
+        assert_eq!(error.line(), Some(2));
+        assert_eq!(error.column(), Some(4));
+
+        let response = DefaultRejectionHandler.reject(Rejection::Decode(error));
+        assert_eq!(status(&response), Some(status_code::BAD_REQUEST));
+        assert!(response
+            .status()
+            .and_then(|t| t.message().as_deref())
+            .unwrap()
+            .starts_with("JSON processing error: "));
+    }
+
+    #[test]
+    fn default_rejections() {
+        let handler = DefaultRejectionHandler;
+
+        let response = handler.reject(Rejection::MissingContentType);
+        assert_eq!(status(&response), Some(status_code::UNSUPPORTED_MEDIA_TYPE));
+
+        let response = handler.reject(Rejection::Overflow {
+            length: Some(2048),
+            limit: 1024,
+        });
+        assert_eq!(status(&response), Some(status_code::PAYLOAD_TOO_LARGE));
+
+        let response = handler.reject(Rejection::Conversion(ConversionError::KindMismatch(
+            "Shell".to_string(),
+            "Bullet".to_string(),
+        )));
+        assert_eq!(status(&response), Some(status_code::BAD_REQUEST));
+
+        let response = handler.reject(Rejection::Internal("No registry".to_string()));
+        assert_eq!(status(&response), Some(status_code::INTERNAL_SERVER_ERROR));
+    }
+
+    #[test]
+    fn closure_handler() {
+        let handler = |rejection: Rejection| match rejection {
+            Rejection::Overflow { limit, .. } => {
+                Hateoas::BAD_REQUEST(None, Some(format!("limit is {}", limit)))
+            }
+            rejection => DefaultRejectionHandler.reject(rejection),
+        };
+
+        let response = handler.reject(Rejection::Overflow {
+            length: None,
+            limit: 8,
+        });
+        assert_eq!(status(&response), Some(status_code::BAD_REQUEST));
+        assert_eq!(
+            response.status().and_then(|t| t.message().as_deref()),
+            Some("limit is 8")
+        );
+    }
+}
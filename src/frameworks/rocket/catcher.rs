@@ -0,0 +1,49 @@
+use crate::{Hateoas, Status as HateoasStatus};
+use rocket::catcher::{BoxFuture, Catcher};
+use rocket::http::Status;
+use rocket::request::Request;
+use rocket::response::Responder;
+
+/// The envelope of the payload rejected by the data guard of the request.
+struct Rejected(Option<Hateoas<()>>);
+
+/// Keeping the envelope of the rejected payload for the catcher, the first rejection of the
+/// request is kept.
+pub(crate) fn cache(req: &Request<'_>, hateoas: &Hateoas<()>) {
+    req.local_cache(|| Rejected(Some(hateoas.clone())));
+}
+
+/// # Catcher
+/// Default catcher responding with the envelope of the rejected payload, or with an envelope
+/// holding the status of the error when the request was not rejected by the
+/// [`Hateoas`](crate::Hateoas) data guard.
+///
+/// ```
+/// use hateoas::frameworks::rocket::catcher::catcher;
+///
+/// let rocket = rocket::build().register("/", vec![catcher()]);
+/// ```
+pub fn catcher() -> Catcher {
+    Catcher::new(None, handle)
+}
+
+fn handle<'r>(status: Status, req: &'r Request<'_>) -> BoxFuture<'r> {
+    let hateoas = req
+        .local_cache(|| Rejected(None))
+        .0
+        .clone()
+        .unwrap_or_else(|| {
+            Hateoas::new(
+                None,
+                None,
+                Some(HateoasStatus::new(
+                    status.reason(),
+                    None,
+                    Some(status.code),
+                    None,
+                    None,
+                )),
+            )
+        });
+    Box::pin(async move { hateoas.respond_to(req) })
+}
@@ -0,0 +1,106 @@
+use crate::frameworks::media_type::essence;
+use crate::frameworks::rejection::{Rejection, RejectionHandler};
+use crate::Hateoas;
+use rocket::request::Request;
+use std::sync::Arc;
+
+static DEFAULT_CONFIG: HateoasConfig = HateoasConfig {
+    content_types: None,
+    strict: false,
+    rejection_handler: None,
+    problem_type_template: None,
+};
+
+/// # HateoasConfig
+/// Configuration of the [`Hateoas`](crate::Hateoas) data guard and responder, read from the
+/// managed state of the application. Without a config all content types supported by
+/// `simple_serde` are accepted and decoding is lenient, the body limit is the `hateoas` limit of
+/// the Rocket [`Limits`](rocket::data::Limits).
+///
+/// ```
+/// use hateoas::frameworks::rocket::config::HateoasConfig;
+///
+/// let rocket = rocket::build().manage(
+///     HateoasConfig::default()
+///         .content_types(["application/json", "application/yaml"])
+///         .strict(true),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct HateoasConfig {
+    pub(crate) content_types: Option<Vec<String>>,
+    pub(crate) strict: bool,
+    pub(crate) rejection_handler: Option<Arc<dyn RejectionHandler>>,
+    pub(crate) problem_type_template: Option<String>,
+}
+
+impl HateoasConfig {
+    /// ## Allowed content types
+    /// Payloads with another `Content-Type` are rejected with `UNSUPPORTED_MEDIA_TYPE`, the
+    /// parameters of the media types are ignored when comparing.
+    pub fn content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.content_types = Some(
+            content_types
+                .into_iter()
+                .map(|t| essence(t.as_ref()))
+                .collect(),
+        );
+        self
+    }
+
+    /// ## Strict envelope decoding
+    /// Strict decoding only accepts payloads wrapped in the [`Hateoas`](crate::Hateoas) envelope,
+    /// lenient decoding also accepts the bare resource.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// ## Mapping the rejections
+    /// The [`RejectionHandler`] creating the error envelope of rejected payloads, which is
+    /// responded by the [`catcher`](crate::frameworks::rocket::catcher::catcher).
+    pub fn rejection_handler<H: RejectionHandler + 'static>(mut self, handler: H) -> Self {
+        self.rejection_handler = Some(Arc::new(handler));
+        self
+    }
+
+    /// ## Problem type template
+    /// The template the `type` URI of `application/problem+json` responses is generated from and
+    /// the code of problem payloads is read from, see
+    /// [`ProblemDetails::type_uri`](crate::ProblemDetails::type_uri).
+    pub fn problem_type_template(mut self, template: &str) -> Self {
+        self.problem_type_template = Some(template.to_string());
+        self
+    }
+
+    pub(crate) fn reject(&self, rejection: Rejection) -> Hateoas<()> {
+        match &self.rejection_handler {
+            Some(handler) => handler.reject(rejection),
+            None => rejection.into(),
+        }
+    }
+
+    /// Checking if the content type is allowed, every content type is allowed when no content
+    /// types are configured.
+    pub(crate) fn allows(&self, content_type: &str) -> bool {
+        self.content_types
+            .as_ref()
+            .map(|t| t.contains(&essence(content_type)))
+            .unwrap_or(true)
+    }
+
+    /// The config managed by the application, the default config when there is none.
+    pub(crate) fn from_request<'r>(req: &'r Request<'_>) -> &'r Self {
+        req.rocket().state::<Self>().unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+impl Default for HateoasConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG.clone()
+    }
+}
@@ -0,0 +1,222 @@
+//! Rocket integration
+//!
+//! [`Hateoas`](crate::Hateoas) is a data guard and a responder. The body limit is the `hateoas`
+//! limit of the Rocket [`Limits`], the other options are set by the managed
+//! [`HateoasConfig`](config::HateoasConfig), and the envelope of rejected payloads is responded by
+//! the [`catcher`](catcher::catcher) registered on the application.
+pub mod catcher;
+pub mod config;
+
+use crate::frameworks::conversion::codec;
+use crate::frameworks::prefer::Preferences;
+use crate::frameworks::rejection::Rejection;
+use crate::frameworks::rocket::config::HateoasConfig;
+use crate::frameworks::{compression, negotiation};
+use crate::header::HeaderKey;
+use crate::{status_code, Hateoas, HateoasResource, HeaderMap, Representation};
+use rocket::data::{Data, FromData, Limits, Outcome};
+use rocket::http::{Method, Status};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use simple_serde::{Decoded, SimpleDecoder};
+use std::io::Cursor;
+use tracing::{event, span, Level};
+
+/// The name of the limit of the payload in the Rocket [`Limits`], payloads are limited to
+/// [`Limits::JSON`] when the limit is not configured.
+pub const LIMIT: &str = "hateoas";
+
+/// The headers describing the body are set by the responder and not copied from the response.
+const BODY_HEADERS: [&str; 4] = [
+    "Content-Type",
+    "Content-Length",
+    "Content-Encoding",
+    "Transfer-Encoding",
+];
+
+#[rocket::async_trait]
+impl<'r, T> FromData<'r> for Hateoas<T>
+where
+    T: DeserializeOwned + HateoasResource + Clone + Send,
+{
+    type Error = Hateoas<()>;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> Outcome<'r, Self> {
+        span!(Level::TRACE, "Extracting Hateoas object from request data");
+        match decode(req, data).await {
+            Ok(t) => Outcome::Success(t),
+            Err(rejection) => {
+                let hateoas = HateoasConfig::from_request(req).reject(rejection);
+                let status = status(&hateoas);
+                catcher::cache(req, &hateoas);
+                Outcome::Error((status, hateoas))
+            }
+        }
+    }
+}
+
+async fn decode<'r, T>(req: &'r Request<'_>, data: Data<'r>) -> Result<Hateoas<T>, Rejection>
+where
+    T: DeserializeOwned + HateoasResource + Clone,
+{
+    let config = HateoasConfig::from_request(req);
+    let headers = HeaderMap::from(req.headers());
+    let content_type = headers
+        .get_first(&HeaderKey::ContentType)
+        .map(|t| t.to_owned())
+        .ok_or(Rejection::MissingContentType)?;
+    if !config.allows(&content_type) {
+        return Err(Rejection::UnsupportedContentType(content_type));
+    }
+    let limit = req.limits().get(LIMIT).unwrap_or(Limits::JSON).as_u64() as usize;
+    let length = req
+        .headers()
+        .get_one("Content-Length")
+        .and_then(|t| t.parse::<usize>().ok());
+    if length.map(|t| t > limit).unwrap_or(false) {
+        return Err(Rejection::Overflow { length, limit });
+    }
+    let bytes = data
+        .open(limit.into())
+        .into_bytes()
+        .await
+        .map_err(|e| Rejection::Payload(e.to_string()))?;
+    if !bytes.is_complete() {
+        return Err(Rejection::Overflow { length, limit });
+    }
+    let bytes = compression::decompress(
+        headers
+            .get_first(&HeaderKey::ContentEncoding)
+            .map(|t| t.as_str()),
+        bytes.into_inner(),
+        limit,
+    )?;
+    let mut hateoas = match Representation::from_media_type(&content_type) {
+        Some(representation) => representation
+            .decode_with_type_template(&bytes, config.problem_type_template.as_deref())?,
+        None => {
+            let content_type = codec(&content_type);
+            bytes
+                .decode(&content_type)
+                .map(|t: Decoded<Hateoas<T>>| t.into())
+                .or_else(|e| {
+                    if config.strict {
+                        return Err(e);
+                    }
+                    event!(Level::WARN, "Failed to decode the payload, will try again.");
+                    bytes
+                        .decode(&content_type)
+                        .map(|t: Decoded<T>| Hateoas::from(t.into()))
+                })?
+        }
+    };
+    *hateoas.metadata_mut().headers_mut() = Some(headers.uncommon_extract());
+    Ok(hateoas)
+}
+
+impl<'r, T> Responder<'r, 'static> for Hateoas<T>
+where
+    T: HateoasResource + Serialize + Clone,
+{
+    fn respond_to(mut self, req: &'r Request<'_>) -> response::Result<'static> {
+        let type_template = HateoasConfig::from_request(req)
+            .problem_type_template
+            .as_deref();
+        let status = status(&self);
+        let body_less = status == Status::NoContent || status == Status::NotModified;
+        if body_less || req.method() == Method::Head {
+            self.add_link_header();
+        }
+
+        let accept = request_header(req, "Accept");
+        let is_error = status.class().is_client_error() || status.class().is_server_error();
+        let media_type = match negotiation::select(Some(&accept), is_error) {
+            Some(media_type) => media_type,
+            None => {
+                let media_type =
+                    negotiation::select(Some(&accept), true).unwrap_or("application/json");
+                let body =
+                    negotiation::encode(&negotiation::not_acceptable(), media_type, type_template)
+                        .ok_or(Status::InternalServerError)?;
+                return Response::build()
+                    .status(Status::NotAcceptable)
+                    .raw_header("Vary", "Accept")
+                    .raw_header("Content-Type", media_type)
+                    .sized_body(body.len(), Cursor::new(body))
+                    .ok();
+            }
+        };
+
+        let preference_applied =
+            Preferences::parse(&request_header(req, &HeaderKey::Prefer.to_string()))
+                .apply(req.method().as_str(), &mut self);
+        let headers = response_headers(&self);
+        let body = negotiation::encode(&self, media_type, type_template)
+            .ok_or(Status::InternalServerError)?;
+
+        let mut response = Response::build();
+        response.status(status);
+        for header in headers.into_iter() {
+            response.header_adjoin(header);
+        }
+        response.raw_header_adjoin("Vary", "Accept");
+        if compression::varies() {
+            response.raw_header_adjoin("Vary", "Accept-Encoding");
+        }
+        if let Some(preference_applied) = preference_applied {
+            response.raw_header(HeaderKey::PreferenceApplied.to_string(), preference_applied);
+            response.raw_header_adjoin("Vary", HeaderKey::Prefer.to_string());
+        }
+        if body_less {
+            return response.ok();
+        }
+        let (encoding, body) =
+            compression::compress(Some(&request_header(req, "Accept-Encoding")), body);
+        if encoding != compression::Encoding::Identity {
+            response.raw_header("Content-Encoding", encoding.token());
+        }
+        response
+            .raw_header(
+                "Content-Type",
+                negotiation::content_type::<T>(Some(&accept), media_type),
+            )
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+/// The status of the response, `OK` when the status code is not set.
+fn status<T: HateoasResource + Clone>(hateoas: &Hateoas<T>) -> Status {
+    Status::new(u16::from(
+        hateoas
+            .status()
+            .and_then(|t| t.http_status_code().clone())
+            .unwrap_or(status_code::OK),
+    ))
+}
+
+/// The values of the request header joined as a list.
+fn request_header(req: &Request<'_>, name: &str) -> String {
+    req.headers().get(name).collect::<Vec<&str>>().join(",")
+}
+
+/// The headers of the metadata and the status of the response, in that order.
+fn response_headers<T: HateoasResource + Clone>(
+    hateoas: &Hateoas<T>,
+) -> rocket::http::HeaderMap<'static> {
+    let mut headers = rocket::http::HeaderMap::new();
+    [
+        hateoas.metadata().and_then(|t| t.headers().clone()),
+        hateoas.status().and_then(|t| t.headers().clone()),
+    ]
+    .into_iter()
+    .flatten()
+    .flat_map(|t| rocket::http::HeaderMap::from(t).into_iter())
+    .for_each(|t| headers.add(t));
+    for name in BODY_HEADERS {
+        headers.remove(name);
+    }
+    headers
+}
@@ -1,7 +1,7 @@
 use crate::header::HeaderKey;
 use crate::resource_trait::HateoasResource;
 use crate::serde::Serialize;
-use crate::{Content, Metadata, Status};
+use crate::{Content, HeaderMap, Metadata, RelLink, Status};
 use serde::Deserialize;
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
@@ -37,7 +37,7 @@ impl<T: HateoasResource + Clone> Hateoas<T> {
     ) -> Self {
         Self {
             api_version: format!("{}/{}", T::GROUP, T::VERSION),
-            kind: format!("{}", T::KIND),
+            kind: T::kind(),
             metadata,
             spec,
             status,
@@ -161,6 +161,132 @@ impl<T: HateoasResource + Clone> Hateoas<T> {
     pub fn spec_mut(&mut self) -> &mut Content<T> {
         self.spec.get_or_insert(Content::default())
     }
+
+    /// ## Converting the resource
+    /// Converting the spec content into another resource type, eg. another version of the same
+    /// kind. The `apiVersion` and `kind` are updated to the new resource while metadata, status and
+    /// rel links are kept.
+    ///
+    /// ```
+    /// use hateoas::Hateoas;
+    ///
+    /// let response: Hateoas<u16> = Hateoas::OK(Some(42), None);
+    /// let converted: Hateoas<u32> = response.try_map(|t| Ok::<u32, ()>(t as u32)).unwrap();
+    ///
+    /// assert_eq!(converted, Hateoas::OK(Some(42), None));
+    /// assert_eq!(converted.kind(), "U32");
+    /// ```
+    pub fn try_map<U, E, F>(self, f: F) -> Result<Hateoas<U>, E>
+    where
+        U: HateoasResource + Clone,
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        Ok(Hateoas {
+            api_version: format!("{}/{}", U::GROUP, U::VERSION),
+            kind: U::kind(),
+            metadata: self.metadata,
+            spec: self.spec.map(|t| t.try_map(f)).transpose()?,
+            status: self.status,
+        })
+    }
+
+    /// ## Generating the self link
+    /// Generating the canonical `self` link for the resource, the link is build from the base url,
+    /// the resource group/version and `URL_PATH_SEGMENT` and the id from the metadata. If there is
+    /// no id in the metadata no link can be generated.
+    ///
+    /// ```
+    /// use hateoas::{Hateoas, Metadata, RelLink};
+    ///
+    /// let mut response: Hateoas<String> = Hateoas::OK(Some("hello".to_string()), None);
+    ///
+    /// assert_eq!(response.self_link("https://example.com"), None);
+    ///
+    /// *response.metadata_mut() = Metadata::new(Some("42".to_string()), None, None, None, None);
+    ///
+    /// assert_eq!(
+    ///     response.self_link("https://example.com"),
+    ///     Some(RelLink::GET("self", "https://example.com/hateoas.io/0.0.1/string/42"))
+    /// );
+    /// ```
+    pub fn self_link(&self, base_url: &str) -> Option<RelLink> {
+        self.metadata
+            .as_ref()
+            .and_then(|t| t.id.as_ref())
+            .map(|id| RelLink::GET("self", &T::resource_url(base_url, id)))
+    }
+
+    /// ## Generating the collection link
+    /// Generating the `collection` link pointing to the collection the resource belongs to.
+    ///
+    /// ```
+    /// use hateoas::{Hateoas, RelLink};
+    ///
+    /// assert_eq!(
+    ///     Hateoas::<String>::collection_link("/api"),
+    ///     RelLink::GET("collection", "/api/hateoas.io/0.0.1/string")
+    /// );
+    /// ```
+    pub fn collection_link(base_url: &str) -> RelLink {
+        RelLink::GET("collection", &T::collection_url(base_url))
+    }
+
+    /// ## Adding the self and collection links
+    /// Adding the generated `self` and `collection` links to the rel links of the spec, existing
+    /// links with the same rel will be replaced.
+    ///
+    /// ```
+    /// use hateoas::{Hateoas, Metadata};
+    ///
+    /// let mut response: Hateoas<String> = Hateoas::OK(Some("hello".to_string()), None);
+    /// *response.metadata_mut() = Metadata::new(Some("42".to_string()), None, None, None, None);
+    /// response.add_self_links("/api");
+    ///
+    /// let rel = response.spec_mut().rel();
+    /// assert_eq!(rel.get("self").map(|t| t.href()), Some("/api/hateoas.io/0.0.1/string/42"));
+    /// assert_eq!(rel.get("collection").map(|t| t.href()), Some("/api/hateoas.io/0.0.1/string"));
+    /// ```
+    pub fn add_self_links(&mut self, base_url: &str) -> &mut Self {
+        let self_link = self.self_link(base_url);
+        let rel = self.spec_mut().rel();
+        if let Some(link) = self_link {
+            rel.add(link);
+        }
+        rel.add(Self::collection_link(base_url));
+        self
+    }
+
+    /// ## Adding the Link header
+    /// Adding the rel links of the spec as an RFC 8288 `Link` header to the status headers, the
+    /// header is written to the response by the web framework integrations. This allows responses
+    /// without a body, eg. `204 No Content` or `HEAD`, to still carry their links.
+    ///
+    /// ```
+    /// use hateoas::{Hateoas, HeaderMap, RelLink};
+    ///
+    /// let mut response: Hateoas<String> = Hateoas::NO_CONTENT(None, None);
+    /// response.spec_mut().rel().add(RelLink::GET("next", "/bullets?offset=10"));
+    /// response.add_link_header();
+    ///
+    /// assert_eq!(
+    ///     response.status().and_then(|t| t.headers().as_ref()),
+    ///     Some(&HeaderMap::from(("Link", r#"</bullets?offset=10>; rel="next""#)))
+    /// );
+    /// ```
+    pub fn add_link_header(&mut self) -> &mut Self {
+        let link = self
+            .spec
+            .as_ref()
+            .and_then(|t| t.rel_links())
+            .and_then(|t| t.to_link_header());
+        if let Some(link) = link {
+            self.status_mut()
+                .headers_mut()
+                .get_or_insert_with(HeaderMap::default)
+                .set("Link", link);
+        }
+        self
+    }
 }
 
 impl<T: HateoasResource + Clone> From<T> for Hateoas<T> {
@@ -173,7 +299,7 @@ impl<T: HateoasResource + Clone> Default for Hateoas<T> {
     fn default() -> Self {
         Self {
             api_version: format!("{}/{}", T::GROUP, T::VERSION),
-            kind: format!("{}", T::KIND),
+            kind: T::kind(),
             metadata: None,
             spec: None,
             status: None,
@@ -481,6 +607,23 @@ mod test {
         assert_eq!(&mut RelLinkCollection::default(), rel);
     }
 
+    #[test]
+    fn test_self_links_on_empty_group() {
+        let mut response = Hateoas::OK(Some(RubberBullet::default()), None);
+        response.metadata_mut().id = Some("bullet/1".to_string());
+        response.add_self_links("http://localhost:8080/");
+
+        let rel = response.spec_mut().rel();
+        assert_eq!(
+            rel.get("self").map(|t| t.href()),
+            Some("http://localhost:8080/bullet%2F1")
+        );
+        assert_eq!(
+            rel.get("collection").map(|t| t.href()),
+            Some("http://localhost:8080")
+        );
+    }
+
     #[test]
     fn test_get_spec_on_none() {
         let mut response: Hateoas<String> = Hateoas::default();
@@ -212,6 +212,60 @@ impl TryFrom<HeaderMap> for http::HeaderMap {
     }
 }
 
+#[cfg(feature = "actix")]
+impl From<&actix_web::http::header::HeaderMap> for HeaderMap {
+    fn from(t: &actix_web::http::header::HeaderMap) -> Self {
+        let mut headers = Self::default();
+        for (key, value) in t.iter() {
+            headers.append(key.as_str(), HeaderValue::from(value));
+        }
+        headers
+    }
+}
+
+/// Each value of a header is added as a header of its own.
+#[cfg(feature = "actix")]
+impl TryFrom<HeaderMap> for actix_web::http::header::HeaderMap {
+    type Error = String;
+    fn try_from(h: HeaderMap) -> Result<Self, Self::Error> {
+        let mut headers = Self::new();
+        for (key, value) in h.0 {
+            let name = HeaderName::from_str(key.to_string().as_str()).map_err(|e| e.to_string())?;
+            for value in value.iter() {
+                let value = http::HeaderValue::from_str(value).map_err(|e| e.to_string())?;
+                headers.append(name.clone(), value);
+            }
+        }
+        Ok(headers)
+    }
+}
+
+#[cfg(feature = "rocket")]
+impl From<&rocket::http::HeaderMap<'_>> for HeaderMap {
+    fn from(t: &rocket::http::HeaderMap<'_>) -> Self {
+        let mut headers = Self::default();
+        for header in t.iter() {
+            headers.append(header.name().as_str(), header.value());
+        }
+        headers
+    }
+}
+
+/// Each value of a header is added as a header of its own.
+#[cfg(feature = "rocket")]
+impl From<HeaderMap> for rocket::http::HeaderMap<'static> {
+    fn from(h: HeaderMap) -> Self {
+        let mut headers = Self::new();
+        for (key, value) in h.0 {
+            let name = key.to_string();
+            for value in value.iter() {
+                headers.add_raw(name.clone(), value.clone());
+            }
+        }
+        headers
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::HeaderMap;
@@ -223,4 +277,32 @@ mod test {
 
         assert_eq!(HeaderMap::from(("Content-Type", "application/json")), map);
     }
+
+    #[cfg(feature = "actix")]
+    #[test]
+    pub fn actix_header_map() {
+        let mut map = HeaderMap::new();
+        map.append("Link", "</bullets/1>; rel=\"self\"");
+        map.append("Link", "</bullets>; rel=\"collection\"");
+        map.append("x-request-id", "1");
+
+        let actix_map = actix_web::http::header::HeaderMap::try_from(map.clone()).unwrap();
+        assert_eq!(actix_map.get_all("link").count(), 2);
+        assert_eq!(actix_map.get("x-request-id").unwrap(), "1");
+        assert_eq!(HeaderMap::from(&actix_map), map);
+    }
+
+    #[cfg(feature = "rocket")]
+    #[test]
+    pub fn rocket_header_map() {
+        let mut map = HeaderMap::new();
+        map.append("Link", "</bullets/1>; rel=\"self\"");
+        map.append("Link", "</bullets>; rel=\"collection\"");
+        map.append("x-request-id", "1");
+
+        let rocket_map = rocket::http::HeaderMap::from(map.clone());
+        assert_eq!(rocket_map.get("link").count(), 2);
+        assert_eq!(rocket_map.get_one("x-request-id"), Some("1"));
+        assert_eq!(HeaderMap::from(&rocket_map), map);
+    }
 }
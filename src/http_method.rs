@@ -1,4 +1,6 @@
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub enum HttpMethod {
     Get,
     Head,
@@ -20,3 +22,43 @@ pub const CONNECT: HttpMethod = HttpMethod::Connect;
 pub const OPTIONS: HttpMethod = HttpMethod::Options;
 pub const TRACE: HttpMethod = HttpMethod::Trace;
 pub const PATCH: HttpMethod = HttpMethod::Patch;
+
+impl HttpMethod {
+    pub const ALL: [HttpMethod; 9] = [GET, HEAD, POST, PUT, DELETE, CONNECT, OPTIONS, TRACE, PATCH];
+
+    /// ## The method name as used in HTTP
+    ///
+    /// ```
+    /// use hateoas::HttpMethod;
+    ///
+    /// assert_eq!(HttpMethod::Patch.as_str(), "PATCH");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Patch => "PATCH",
+        }
+    }
+
+    /// ## Finding the method from its HTTP name
+    /// The name is matched case-insensitive.
+    ///
+    /// ```
+    /// use hateoas::HttpMethod;
+    ///
+    /// assert_eq!(HttpMethod::from_name("delete"), Some(HttpMethod::Delete));
+    /// assert_eq!(HttpMethod::from_name("FETCH"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(name))
+    }
+}
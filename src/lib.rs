@@ -17,25 +17,51 @@ extern crate futures_core;
 extern crate lazy_static;
 #[cfg(any(feature = "simple_serde", future = "axum"))]
 extern crate simple_serde;
+#[cfg(feature = "derive")]
+extern crate hateoas_derive;
 
 mod content;
-mod frameworks;
+mod embedded;
+pub mod frameworks;
 mod hateoas;
 mod header;
 mod http_method;
+mod list;
 mod metadata;
+mod pagination;
+mod registry;
 mod rel;
+mod representation;
 mod resource_trait;
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+mod schema;
 mod status;
 pub mod status_code;
 
 pub use crate::hateoas::Hateoas;
+#[cfg(feature = "derive")]
+pub use hateoas_derive::HateoasResource;
 pub use content::Content;
+pub use embedded::{Embedded, EmbeddedEnvelope, EmbeddedError};
 pub use header::{HeaderMap, HeaderValue};
 pub use http_method::HttpMethod;
+pub use list::{HateoasList, ResourceList};
 pub use metadata::Metadata;
+pub use pagination::{
+    CursorParams, OffsetParams, PageInfo, PageParams, Pagination, PaginationError,
+    PaginationParams, PaginationQuery, DEFAULT_PAGE_SIZE,
+};
+pub use registry::{
+    APIGroup, APIGroupList, APIResource, APIResourceList, GroupVersionForDiscovery,
+    ResourceDescriptor, ResourceRegistry,
+};
+pub use rel::link_field::LinkField;
+pub use rel::link_header::LinkHeaderError;
 pub use rel::rel_link::RelLink;
 pub use rel::rel_link_collection::RelLinkCollection;
+pub use representation::{ProblemDetails, Representation, RepresentationError};
 pub use resource_trait::{AsHateoasResponse, HateoasResource, ToHateoasResponse};
+#[cfg(any(feature = "schemars", feature = "utoipa"))]
+pub use schema::ErrorEnvelope;
 pub use status::Status;
 pub use status_code::StatusCode;
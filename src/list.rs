@@ -0,0 +1,235 @@
+use crate::{Content, Hateoas, HateoasResource};
+
+/// # HateoasList
+/// The envelope for returning many resources of the same kind, the kind of the envelope is the
+/// kind of the items followed by `List`, eg. `BulletList`.
+///
+/// ```
+/// use hateoas::{HateoasList, ResourceList};
+///
+/// let list: HateoasList<String> = HateoasList::OK(Some(ResourceList::from(vec!["foo".to_string()])), None);
+///
+/// assert_eq!(list.kind(), "StringList");
+/// assert_eq!(list.api_version(), "hateoas.io/0.0.1");
+/// ```
+pub type HateoasList<T> = Hateoas<ResourceList<T>>;
+
+/// # ResourceList
+/// The spec of a [`HateoasList`], holding the items, each with their own rel links, and the list
+/// metadata used for paginating through the collection.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(rename = "ResourceList_{T}")
+)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ResourceList<T> {
+    items: Vec<Content<T>>,
+    total: Option<u64>,
+    page_size: Option<u64>,
+    #[serde(rename = "continue")]
+    continue_token: Option<String>,
+    remaining_item_count: Option<u64>,
+}
+
+impl<T> ResourceList<T> {
+    /// ## New ResourceList
+    /// Creating a list from items that already carry their rel links.
+    ///
+    /// ```
+    /// use hateoas::{Content, RelLink, ResourceList};
+    ///
+    /// let mut item = Content::new("foo".to_string());
+    /// item.rel().add(RelLink::GET("self", "/strings/foo"));
+    /// let list = ResourceList::new(vec![item]);
+    ///
+    /// assert_eq!(list.items().len(), 1);
+    /// assert_eq!(list.total(), None);
+    /// ```
+    pub fn new(items: Vec<Content<T>>) -> Self {
+        ResourceList {
+            items,
+            total: None,
+            page_size: None,
+            continue_token: None,
+            remaining_item_count: None,
+        }
+    }
+
+    pub fn items(&self) -> &Vec<Content<T>> {
+        &self.items
+    }
+
+    pub fn items_mut(&mut self) -> &mut Vec<Content<T>> {
+        &mut self.items
+    }
+
+    /// ## Adding an item to the list
+    /// ```
+    /// use hateoas::{Content, ResourceList};
+    ///
+    /// let mut list: ResourceList<String> = ResourceList::default();
+    /// list.push("foo".to_string());
+    /// list.push(Content::new("bar".to_string()));
+    ///
+    /// assert_eq!(list.items().len(), 2);
+    /// ```
+    pub fn push<C: Into<Content<T>>>(&mut self, item: C) {
+        self.items.push(item.into());
+    }
+
+    /// ## Total amount of items in the collection
+    /// The total amount of items matching the request, not the amount of items in this page.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    pub fn total_mut(&mut self) -> &mut Option<u64> {
+        &mut self.total
+    }
+
+    /// ## Requested page size
+    pub fn page_size(&self) -> Option<u64> {
+        self.page_size
+    }
+
+    pub fn page_size_mut(&mut self) -> &mut Option<u64> {
+        &mut self.page_size
+    }
+
+    /// ## Continue token
+    /// The opaque token for fetching the next page, serialized as `continue`. If there is no token
+    /// there are no more items.
+    pub fn continue_token(&self) -> Option<&String> {
+        self.continue_token.as_ref()
+    }
+
+    pub fn continue_token_mut(&mut self) -> &mut Option<String> {
+        &mut self.continue_token
+    }
+
+    /// ## Amount of items remaining after this page
+    pub fn remaining_item_count(&self) -> Option<u64> {
+        self.remaining_item_count
+    }
+
+    pub fn remaining_item_count_mut(&mut self) -> &mut Option<u64> {
+        &mut self.remaining_item_count
+    }
+
+    /// ## Setting the pagination metadata
+    /// Setting the total and the page size of the list, the `remainingItemCount` is calculated from
+    /// the offset of the first item in the page.
+    ///
+    /// ```
+    /// use hateoas::ResourceList;
+    ///
+    /// let mut list = ResourceList::from(vec![3u16, 4u16]);
+    /// list.paginate(10, 2, 2);
+    ///
+    /// assert_eq!(list.total(), Some(10));
+    /// assert_eq!(list.page_size(), Some(2));
+    /// assert_eq!(list.remaining_item_count(), Some(6));
+    /// ```
+    pub fn paginate(&mut self, total: u64, page_size: u64, offset: u64) -> &mut Self {
+        self.total = Some(total);
+        self.page_size = Some(page_size);
+        self.remaining_item_count =
+            Some(total.saturating_sub(offset.saturating_add(self.items.len() as u64)));
+        self
+    }
+}
+
+impl<T> Default for ResourceList<T> {
+    fn default() -> Self {
+        ResourceList::new(vec![])
+    }
+}
+
+impl<T> From<Vec<T>> for ResourceList<T> {
+    fn from(items: Vec<T>) -> Self {
+        ResourceList::new(items.into_iter().map(Content::new).collect())
+    }
+}
+
+impl<T> FromIterator<Content<T>> for ResourceList<T> {
+    fn from_iter<I: IntoIterator<Item = Content<T>>>(iter: I) -> Self {
+        ResourceList::new(iter.into_iter().collect())
+    }
+}
+
+impl<T: HateoasResource> HateoasResource for ResourceList<T> {
+    const KIND: &'static str = "List";
+    const VERSION: &'static str = T::VERSION;
+    const GROUP: &'static str = T::GROUP;
+    const URL_PATH_SEGMENT: &'static str = T::URL_PATH_SEGMENT;
+    const IS_LIST: bool = true;
+
+    fn kind() -> String {
+        format!("{}{}", T::kind(), Self::KIND)
+    }
+}
+
+#[cfg(feature = "web-framework-support")]
+impl<T: crate::frameworks::payload_control::PayloadControl>
+    crate::frameworks::payload_control::PayloadControl for ResourceList<T>
+{
+    const MAX_PAYLOAD_SIZE: Option<usize> = T::MAX_PAYLOAD_SIZE;
+    const BUFFER_CAPACITY: Option<usize> = T::BUFFER_CAPACITY;
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Content, HateoasList, HateoasResource, RelLink, ResourceList};
+    #[cfg(feature = "web-framework-support")]
+    use simple_serde::{Decoded, SimpleDecoder, SimpleEncoder};
+
+    fn list() -> HateoasList<String> {
+        let mut item = Content::new("foo".to_string());
+        item.rel().add(RelLink::GET("self", "/strings/foo"));
+        let mut list = ResourceList::new(vec![item]);
+        list.paginate(3, 1, 0);
+        *list.continue_token_mut() = Some("Zm9v".to_string());
+        HateoasList::OK(Some(list), None)
+    }
+
+    #[test]
+    fn list_serialization() {
+        let value = serde_json::to_value(list()).unwrap();
+
+        assert_eq!(value["kind"], "StringList");
+        assert_eq!(value["spec"]["content"]["total"], 3);
+        assert_eq!(value["spec"]["content"]["pageSize"], 1);
+        assert_eq!(value["spec"]["content"]["continue"], "Zm9v");
+        assert_eq!(value["spec"]["content"]["remainingItemCount"], 2);
+        assert_eq!(
+            value["spec"]["content"]["items"][0]["rel"][0]["href"],
+            "/strings/foo"
+        );
+    }
+
+    #[test]
+    fn list_kind() {
+        assert_eq!(ResourceList::<String>::kind(), "StringList");
+        assert_eq!(
+            ResourceList::<ResourceList<String>>::kind(),
+            "StringListList"
+        );
+    }
+
+    #[cfg(feature = "web-framework-support")]
+    #[test]
+    fn list_round_trip() {
+        let encoded = list().encode("application/x-yaml").unwrap();
+        let decoded: HateoasList<String> = encoded
+            .to_vec()
+            .decode("application/x-yaml")
+            .map(|t: Decoded<HateoasList<String>>| t.into())
+            .unwrap();
+
+        assert_eq!(decoded, list());
+    }
+}
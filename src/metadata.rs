@@ -3,6 +3,8 @@ use std::collections::HashMap;
 
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Metadata {
     pub(crate) id: Option<String>,
     pub(crate) name: Option<String>,
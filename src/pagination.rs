@@ -0,0 +1,496 @@
+use crate::{RelLink, RelLinkCollection};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+/// The page size used when the request does not give one.
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+
+/// # Pagination
+/// The pagination strategy and the position of the current page.
+///
+/// - `Offset` uses the `offset` and `limit` query parameters.
+/// - `Page` uses the `page` and `per_page` query parameters, pages start at 1.
+/// - `Cursor` uses the opaque `cursor` and the `limit` query parameters.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pagination {
+    Offset { offset: u64, limit: u64 },
+    Page { page: u64, per_page: u64 },
+    Cursor { cursor: Option<String>, limit: u64 },
+}
+
+/// # PageInfo
+/// What is known about the rest of the collection, this decides which links can be generated.
+///
+/// - `Total` allows for all of `first`, `prev`, `next` and `last`.
+/// - `HasMore` has no `last` link, as the end of the collection is unknown.
+/// - `Cursors` holds the cursors of the next and previous page for cursor pagination.
+#[derive(Debug, PartialEq, Clone)]
+pub enum PageInfo {
+    Total(u64),
+    HasMore(bool),
+    Cursors {
+        next: Option<String>,
+        prev: Option<String>,
+    },
+}
+
+/// # PaginationError
+#[derive(Debug, Display, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum PaginationError {
+    #[display(fmt = "Query parameter {} is not a valid number: {}", _0, _1)]
+    InvalidNumber(String, String),
+
+    #[display(fmt = "Query parameter {} must be greater than 0", _0)]
+    MustBePositive(String),
+}
+
+impl Pagination {
+    /// ## Generating the pagination links
+    /// Generating the `first`, `prev`, `next` and `last` links from the request uri, the
+    /// pagination parameters of the uri are replaced while all other query parameters are kept.
+    /// Links that does not apply for the current page, eg. `prev` on the first page, are left out.
+    ///
+    /// ```
+    /// use hateoas::{PageInfo, Pagination};
+    ///
+    /// let pagination = Pagination::Offset { offset: 10, limit: 10 };
+    /// let links = pagination.links("/bullets?offset=10&limit=10&sort=caliber", &PageInfo::Total(25));
+    ///
+    /// assert_eq!(links.get("first").unwrap().href(), "/bullets?sort=caliber&offset=0&limit=10");
+    /// assert_eq!(links.get("prev").unwrap().href(), "/bullets?sort=caliber&offset=0&limit=10");
+    /// assert_eq!(links.get("next").unwrap().href(), "/bullets?sort=caliber&offset=20&limit=10");
+    /// assert_eq!(links.get("last").unwrap().href(), "/bullets?sort=caliber&offset=20&limit=10");
+    /// ```
+    pub fn links(&self, uri: &str, info: &PageInfo) -> RelLinkCollection {
+        let mut collection = RelLinkCollection::default();
+        self.add_links(&mut collection, uri, info);
+        collection
+    }
+
+    /// ## Adding the pagination links to a collection
+    /// Same as [`Pagination::links`] but adding the links to an existing collection, existing
+    /// pagination links in the collection are replaced.
+    pub fn add_links(&self, collection: &mut RelLinkCollection, uri: &str, info: &PageInfo) {
+        let (path, query) = split_uri(uri);
+        let retained = query
+            .split('&')
+            .filter(|t| !t.is_empty())
+            .filter(|t| !self.parameters().contains(&query_key(t).as_str()))
+            .collect::<Vec<&str>>();
+        let href = |params: Vec<(&str, String)>| {
+            let mut query = retained
+                .iter()
+                .map(|t| t.to_string())
+                .collect::<Vec<String>>();
+            query.extend(
+                params
+                    .into_iter()
+                    .map(|(k, v)| format!("{}={}", k, utf8_percent_encode(&v, QUERY_ENCODE_SET))),
+            );
+            format!("{}?{}", path, query.join("&"))
+        };
+
+        for (rel, params) in self.pages(info) {
+            collection.add(RelLink::GET(rel, &href(params)));
+        }
+    }
+
+    /// The query parameters used by the strategy.
+    pub fn parameters(&self) -> [&'static str; 2] {
+        match self {
+            Pagination::Offset { .. } => ["offset", "limit"],
+            Pagination::Page { .. } => ["page", "per_page"],
+            Pagination::Cursor { .. } => ["cursor", "limit"],
+        }
+    }
+
+    fn pages(&self, info: &PageInfo) -> Vec<(&'static str, Vec<(&'static str, String)>)> {
+        let mut pages = vec![];
+        match self {
+            Pagination::Offset { offset, limit } => {
+                let limit = (*limit).max(1);
+                let params = |offset: u64| {
+                    vec![("offset", offset.to_string()), ("limit", limit.to_string())]
+                };
+                pages.push(("first", params(0)));
+                if *offset > 0 {
+                    pages.push(("prev", params(offset.saturating_sub(limit))));
+                }
+                // there is no next page past the largest offset
+                let next = offset.checked_add(limit);
+                match (info, next) {
+                    (PageInfo::Total(total), next) => {
+                        if let Some(next) = next.filter(|t| t < total) {
+                            pages.push(("next", params(next)));
+                        }
+                        pages.push(("last", params(total.saturating_sub(1) / limit * limit)));
+                    }
+                    (PageInfo::HasMore(true), Some(next)) => pages.push(("next", params(next))),
+                    _ => {}
+                }
+            }
+            Pagination::Page { page, per_page } => {
+                let per_page = (*per_page).max(1);
+                let params = |page: u64| {
+                    vec![
+                        ("page", page.to_string()),
+                        ("per_page", per_page.to_string()),
+                    ]
+                };
+                pages.push(("first", params(1)));
+                if *page > 1 {
+                    pages.push(("prev", params(page - 1)));
+                }
+                let next = page.checked_add(1);
+                match (info, next) {
+                    (PageInfo::Total(total), next) => {
+                        let last = total.div_ceil(per_page).max(1);
+                        if let Some(next) = next.filter(|t| *t <= last) {
+                            pages.push(("next", params(next)));
+                        }
+                        pages.push(("last", params(last)));
+                    }
+                    (PageInfo::HasMore(true), Some(next)) => pages.push(("next", params(next))),
+                    _ => {}
+                }
+            }
+            Pagination::Cursor { limit, .. } => {
+                let params = |cursor: Option<&String>| {
+                    let mut params = vec![];
+                    if let Some(cursor) = cursor {
+                        params.push(("cursor", cursor.to_string()));
+                    }
+                    params.push(("limit", limit.to_string()));
+                    params
+                };
+                pages.push(("first", params(None)));
+                if let PageInfo::Cursors { next, prev } = info {
+                    if let Some(prev) = prev {
+                        pages.push(("prev", params(Some(prev))));
+                    }
+                    if let Some(next) = next {
+                        pages.push(("next", params(Some(next))));
+                    }
+                }
+            }
+        }
+        pages
+    }
+}
+
+/// # PaginationParams
+/// The query parameters of a pagination strategy, used by the framework extractors for parsing
+/// the query string of the request.
+pub trait PaginationParams: Sized {
+    /// Parsing the parameters from the query string, missing parameters use the defaults.
+    fn from_query(query: &str) -> Result<Self, PaginationError>;
+
+    fn pagination(&self) -> Pagination;
+}
+
+/// Query parameters for `offset`/`limit` pagination.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OffsetParams {
+    pub offset: u64,
+    pub limit: u64,
+}
+
+/// Query parameters for `page`/`per_page` pagination.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PageParams {
+    pub page: u64,
+    pub per_page: u64,
+}
+
+/// Query parameters for `cursor`/`limit` pagination.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CursorParams {
+    pub cursor: Option<String>,
+    pub limit: u64,
+}
+
+impl PaginationParams for OffsetParams {
+    /// ```
+    /// use hateoas::{OffsetParams, PaginationParams};
+    ///
+    /// let params = OffsetParams::from_query("offset=40&sort=name").unwrap();
+    ///
+    /// assert_eq!(params, OffsetParams { offset: 40, limit: 20 });
+    /// ```
+    fn from_query(query: &str) -> Result<Self, PaginationError> {
+        Ok(OffsetParams {
+            offset: number(query, "offset")?.unwrap_or(0),
+            limit: positive(query, "limit")?.unwrap_or(DEFAULT_PAGE_SIZE),
+        })
+    }
+
+    fn pagination(&self) -> Pagination {
+        Pagination::Offset {
+            offset: self.offset,
+            limit: self.limit,
+        }
+    }
+}
+
+impl PaginationParams for PageParams {
+    fn from_query(query: &str) -> Result<Self, PaginationError> {
+        Ok(PageParams {
+            page: positive(query, "page")?.unwrap_or(1),
+            per_page: positive(query, "per_page")?.unwrap_or(DEFAULT_PAGE_SIZE),
+        })
+    }
+
+    fn pagination(&self) -> Pagination {
+        Pagination::Page {
+            page: self.page,
+            per_page: self.per_page,
+        }
+    }
+}
+
+impl PaginationParams for CursorParams {
+    fn from_query(query: &str) -> Result<Self, PaginationError> {
+        Ok(CursorParams {
+            cursor: query_value(query, "cursor").filter(|t| !t.is_empty()),
+            limit: positive(query, "limit")?.unwrap_or(DEFAULT_PAGE_SIZE),
+        })
+    }
+
+    fn pagination(&self) -> Pagination {
+        Pagination::Cursor {
+            cursor: self.cursor.clone(),
+            limit: self.limit,
+        }
+    }
+}
+
+/// # PaginationQuery
+/// The parsed pagination parameters together with the uri of the request, extracted by the
+/// framework extractors so the handler can generate the pagination links.
+///
+/// ```
+/// use hateoas::{OffsetParams, PageInfo, PaginationQuery};
+///
+/// let query: PaginationQuery<OffsetParams> = PaginationQuery::from_uri("/bullets?limit=5").unwrap();
+/// let links = query.links(&PageInfo::HasMore(true));
+///
+/// assert_eq!(query.params, OffsetParams { offset: 0, limit: 5 });
+/// assert_eq!(links.get("next").unwrap().href(), "/bullets?offset=5&limit=5");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct PaginationQuery<P: PaginationParams> {
+    pub params: P,
+    pub uri: String,
+}
+
+impl<P: PaginationParams> PaginationQuery<P> {
+    pub fn from_uri(uri: &str) -> Result<Self, PaginationError> {
+        Ok(PaginationQuery {
+            params: P::from_query(split_uri(uri).1)?,
+            uri: uri.to_string(),
+        })
+    }
+
+    pub fn links(&self, info: &PageInfo) -> RelLinkCollection {
+        self.params.pagination().links(&self.uri, info)
+    }
+}
+
+/// Characters that has to be encoded in a query parameter value.
+const QUERY_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'&')
+    .add(b'+')
+    .add(b'<')
+    .add(b'=')
+    .add(b'>');
+
+fn split_uri(uri: &str) -> (&str, &str) {
+    let uri = uri.split('#').next().unwrap_or(uri);
+    uri.split_once('?').unwrap_or((uri, ""))
+}
+
+fn query_key(pair: &str) -> String {
+    let key = pair.split('=').next().unwrap_or(pair);
+    percent_decode_str(&key.replace('+', " "))
+        .decode_utf8_lossy()
+        .to_string()
+}
+
+fn query_value(query: &str, key: &str) -> Option<String> {
+    query
+        .split('&')
+        .find(|t| query_key(t) == key)
+        .map(|t| t.split_once('=').map(|t| t.1).unwrap_or(""))
+        .map(|t| {
+            percent_decode_str(&t.replace('+', " "))
+                .decode_utf8_lossy()
+                .to_string()
+        })
+}
+
+fn number(query: &str, key: &str) -> Result<Option<u64>, PaginationError> {
+    query_value(query, key)
+        .map(|t| {
+            t.parse::<u64>()
+                .map_err(|_| PaginationError::InvalidNumber(key.to_string(), t))
+        })
+        .transpose()
+}
+
+fn positive(query: &str, key: &str) -> Result<Option<u64>, PaginationError> {
+    match number(query, key)? {
+        Some(0) => Err(PaginationError::MustBePositive(key.to_string())),
+        t => Ok(t),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        CursorParams, OffsetParams, PageInfo, PageParams, Pagination, PaginationError,
+        PaginationParams,
+    };
+
+    #[test]
+    fn page_links_with_total() {
+        let links = Pagination::Page {
+            page: 1,
+            per_page: 10,
+        }
+        .links("https://example.com/bullets?page=1", &PageInfo::Total(21));
+
+        assert_eq!(
+            links.get("first").unwrap().href(),
+            "https://example.com/bullets?page=1&per_page=10"
+        );
+        assert!(links.get("prev").is_none());
+        assert_eq!(
+            links.get("next").unwrap().href(),
+            "https://example.com/bullets?page=2&per_page=10"
+        );
+        assert_eq!(
+            links.get("last").unwrap().href(),
+            "https://example.com/bullets?page=3&per_page=10"
+        );
+    }
+
+    #[test]
+    fn offset_links_at_the_largest_offset() {
+        let query = format!("offset={}", u64::MAX);
+        let pagination = OffsetParams::from_query(&query).unwrap().pagination();
+
+        let links = pagination.links(&format!("/bullets?{}", query), &PageInfo::HasMore(true));
+        assert!(links.get("next").is_none());
+        assert_eq!(
+            links.get("prev").unwrap().href(),
+            format!("/bullets?offset={}&limit=20", u64::MAX - 20)
+        );
+
+        let links = pagination.links("/bullets", &PageInfo::Total(u64::MAX));
+        assert!(links.get("next").is_none());
+        assert!(links.get("last").is_some());
+    }
+
+    #[test]
+    fn page_links_at_the_largest_page() {
+        let query = format!("page={}", u64::MAX);
+        let pagination = PageParams::from_query(&query).unwrap().pagination();
+
+        let links = pagination.links("/bullets", &PageInfo::HasMore(true));
+        assert!(links.get("next").is_none());
+        assert_eq!(
+            links.get("prev").unwrap().href(),
+            format!("/bullets?page={}&per_page=20", u64::MAX - 1)
+        );
+
+        let links = pagination.links("/bullets", &PageInfo::Total(u64::MAX));
+        assert!(links.get("next").is_none());
+    }
+
+    #[test]
+    fn page_links_on_empty_collection() {
+        let links = Pagination::Page {
+            page: 1,
+            per_page: 10,
+        }
+        .links("/bullets", &PageInfo::Total(0));
+
+        assert!(links.get("next").is_none());
+        assert_eq!(
+            links.get("last").unwrap().href(),
+            "/bullets?page=1&per_page=10"
+        );
+    }
+
+    #[test]
+    fn cursor_links_are_encoded() {
+        let pagination = Pagination::Cursor {
+            cursor: Some("abc".to_string()),
+            limit: 5,
+        };
+        let links = pagination.links(
+            "/bullets?cursor=abc&filter=a%20b",
+            &PageInfo::Cursors {
+                next: Some("a+b=/c&d".to_string()),
+                prev: None,
+            },
+        );
+
+        assert_eq!(
+            links.get("first").unwrap().href(),
+            "/bullets?filter=a%20b&limit=5"
+        );
+        assert!(links.get("prev").is_none());
+        assert!(links.get("last").is_none());
+        assert_eq!(
+            links.get("next").unwrap().href(),
+            "/bullets?filter=a%20b&cursor=a%2Bb%3D/c%26d&limit=5"
+        );
+    }
+
+    #[test]
+    fn offset_links_without_total() {
+        let links = Pagination::Offset {
+            offset: 5,
+            limit: 10,
+        }
+        .links("/bullets", &PageInfo::HasMore(false));
+
+        assert_eq!(
+            links.get("prev").unwrap().href(),
+            "/bullets?offset=0&limit=10"
+        );
+        assert!(links.get("next").is_none());
+        assert!(links.get("last").is_none());
+    }
+
+    #[test]
+    fn params_from_query() {
+        assert_eq!(
+            PageParams::from_query("per_page=5"),
+            Ok(PageParams {
+                page: 1,
+                per_page: 5
+            })
+        );
+        assert_eq!(
+            CursorParams::from_query("cursor=a%2Bb&limit=3"),
+            Ok(CursorParams {
+                cursor: Some("a+b".to_string()),
+                limit: 3
+            })
+        );
+        assert_eq!(
+            PageParams::from_query("page=0"),
+            Err(PaginationError::MustBePositive("page".to_string()))
+        );
+        assert!(matches!(
+            PageParams::from_query("page=two"),
+            Err(PaginationError::InvalidNumber(_, _))
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+use crate::{Hateoas, HateoasResource, HttpMethod};
+
+/// # ResourceDescriptor
+/// Description of a single resource registered in the [`ResourceRegistry`], this holds the
+/// constants from the [`HateoasResource`] together with the methods that the service supports
+/// for the resource.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct ResourceDescriptor {
+    pub(crate) kind: String,
+    pub(crate) group: String,
+    pub(crate) version: String,
+    pub(crate) path: String,
+    pub(crate) methods: Vec<HttpMethod>,
+}
+
+impl ResourceDescriptor {
+    /// ## Create a descriptor from a HateoasResource
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, ResourceDescriptor};
+    ///
+    /// let descriptor = ResourceDescriptor::from_resource::<String>(vec![HttpMethod::Get]);
+    ///
+    /// assert_eq!(descriptor.kind(), "String");
+    /// assert_eq!(descriptor.group_version(), "hateoas.io/0.0.1");
+    /// assert_eq!(descriptor.path(), "string");
+    /// ```
+    pub fn from_resource<T: HateoasResource>(methods: Vec<HttpMethod>) -> Self {
+        ResourceDescriptor {
+            kind: T::kind(),
+            group: T::GROUP.to_string(),
+            version: T::VERSION.to_string(),
+            path: T::URL_PATH_SEGMENT.trim_matches('/').to_string(),
+            methods,
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn group(&self) -> &str {
+        &self.group
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn methods(&self) -> &Vec<HttpMethod> {
+        &self.methods
+    }
+
+    /// ## Getting the group version
+    /// This is the same value as the `apiVersion` of a [`Hateoas`] response for the resource.
+    pub fn group_version(&self) -> String {
+        format!("{}/{}", self.group, self.version)
+    }
+}
+
+/// # ResourceRegistry
+/// Registry of all the resources that a service exposes, this allows for rendering discovery
+/// documents, so clients can find the resources without out-of-band documentation.
+///
+/// ```
+/// use hateoas::{HttpMethod, ResourceRegistry};
+///
+/// let mut registry = ResourceRegistry::new();
+/// registry.register::<String>(vec![HttpMethod::Get, HttpMethod::Post]);
+/// registry.register::<u16>(vec![HttpMethod::Get]);
+///
+/// let groups = registry.api_group_list();
+/// let group_list = groups.spec().and_then(|t| t.content().as_ref()).unwrap();
+///
+/// assert_eq!(group_list.groups.len(), 1);
+/// assert_eq!(group_list.groups[0].name, "hateoas.io");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ResourceRegistry {
+    resources: Vec<ResourceDescriptor>,
+}
+
+impl ResourceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// ## Register a resource
+    /// Registering a resource with the methods supported, if the kind is already registered for
+    /// the same group and version, the new descriptor will replace the old and the old will be
+    /// returned.
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, ResourceDescriptor, ResourceRegistry};
+    ///
+    /// let mut registry = ResourceRegistry::new();
+    /// registry.register::<String>(vec![HttpMethod::Get]);
+    /// let old = registry.register::<String>(vec![HttpMethod::Get, HttpMethod::Delete]);
+    ///
+    /// assert_eq!(old, Some(ResourceDescriptor::from_resource::<String>(vec![HttpMethod::Get])));
+    /// assert_eq!(registry.resources().len(), 1);
+    /// ```
+    pub fn register<T: HateoasResource>(
+        &mut self,
+        methods: Vec<HttpMethod>,
+    ) -> Option<ResourceDescriptor> {
+        self.add(ResourceDescriptor::from_resource::<T>(methods))
+    }
+
+    /// ## Add a descriptor to the registry
+    /// Same as [`ResourceRegistry::register`] but for an already constructed descriptor.
+    pub fn add(&mut self, descriptor: ResourceDescriptor) -> Option<ResourceDescriptor> {
+        if let Some(found) = self.resources.iter_mut().find(|r| {
+            r.kind == descriptor.kind
+                && r.group == descriptor.group
+                && r.version == descriptor.version
+        }) {
+            Some(std::mem::replace(found, descriptor))
+        } else {
+            self.resources.push(descriptor);
+            None
+        }
+    }
+
+    pub fn resources(&self) -> &Vec<ResourceDescriptor> {
+        &self.resources
+    }
+
+    /// ## Find a resource
+    /// Finding the descriptor of a registered resource by its `HateoasResource` constants.
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, ResourceRegistry};
+    ///
+    /// let mut registry = ResourceRegistry::new();
+    /// registry.register::<String>(vec![HttpMethod::Get]);
+    ///
+    /// assert!(registry.get::<String>().is_some());
+    /// assert!(registry.get::<u16>().is_none());
+    /// ```
+    pub fn get<T: HateoasResource>(&self) -> Option<&ResourceDescriptor> {
+        self.resources
+            .iter()
+            .find(|r| r.kind == T::kind() && r.group == T::GROUP && r.version == T::VERSION)
+    }
+
+    /// ## Rendering the APIGroupList
+    /// Rendering the discovery document listing all groups and the versions for each group.
+    /// The preferred version of a group is the version that was registered first.
+    pub fn api_group_list(&self) -> Hateoas<APIGroupList> {
+        let mut groups: Vec<APIGroup> = vec![];
+        for resource in &self.resources {
+            let version = GroupVersionForDiscovery {
+                group_version: resource.group_version(),
+                version: resource.version.clone(),
+            };
+            match groups.iter_mut().find(|g| g.name == resource.group) {
+                Some(group) => {
+                    if !group.versions.contains(&version) {
+                        group.versions.push(version);
+                    }
+                }
+                None => groups.push(APIGroup {
+                    name: resource.group.clone(),
+                    versions: vec![version.clone()],
+                    preferred_version: version,
+                }),
+            }
+        }
+
+        Hateoas::OK(Some(APIGroupList { groups }), None)
+    }
+
+    /// ## Rendering the APIResourceList
+    /// Rendering the discovery document for all resources in a group version, if nothing is
+    /// registered for the group version a `NOT_FOUND` response is returned.
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, ResourceRegistry, StatusCode};
+    ///
+    /// let mut registry = ResourceRegistry::new();
+    /// registry.register::<String>(vec![HttpMethod::Get]);
+    ///
+    /// let found = registry.api_resource_list("hateoas.io", "0.0.1");
+    /// let missing = registry.api_resource_list("hateoas.io", "v2");
+    ///
+    /// assert_eq!(found.status().and_then(|t| t.http_status_code().clone()), Some(StatusCode::OK));
+    /// assert_eq!(missing.status().and_then(|t| t.http_status_code().clone()), Some(StatusCode::NotFound));
+    /// ```
+    pub fn api_resource_list(&self, group: &str, version: &str) -> Hateoas<APIResourceList> {
+        let resources = self
+            .resources
+            .iter()
+            .filter(|r| r.group == group && r.version == version)
+            .map(|r| APIResource {
+                name: r.path.clone(),
+                kind: r.kind.clone(),
+                methods: r.methods.clone(),
+            })
+            .collect::<Vec<APIResource>>();
+
+        if resources.is_empty() {
+            return Hateoas::NOT_FOUND(
+                None,
+                Some(format!("No resources registered for {}/{}", group, version)),
+            );
+        }
+
+        Hateoas::OK(
+            Some(APIResourceList {
+                group_version: format!("{}/{}", group, version),
+                resources,
+            }),
+            None,
+        )
+    }
+
+    /// ## Rendering the JSON-LD context
+    /// Rendering the context document referenced by the `@context` of the JSON-LD representation,
+    /// defining the Hydra prefix and a term for each kind registered for the group version. If
+    /// nothing is registered for the group version `None` is returned.
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, ResourceRegistry};
+    ///
+    /// let mut registry = ResourceRegistry::new();
+    /// registry.register::<String>(vec![HttpMethod::Get]);
+    ///
+    /// let context = registry.json_ld_context("hateoas.io", "0.0.1").unwrap();
+    ///
+    /// assert_eq!(context["@context"]["hydra"], "http://www.w3.org/ns/hydra/core#");
+    /// assert_eq!(context["@context"]["String"]["@id"], "/apis/hateoas.io/0.0.1/context#String");
+    /// assert!(registry.json_ld_context("hateoas.io", "v2").is_none());
+    /// ```
+    pub fn json_ld_context(&self, group: &str, version: &str) -> Option<serde_json::Value> {
+        let resources = self
+            .resources
+            .iter()
+            .filter(|r| r.group == group && r.version == version)
+            .collect::<Vec<&ResourceDescriptor>>();
+        if resources.is_empty() {
+            return None;
+        }
+
+        Some(crate::representation::json_ld::context(
+            group,
+            version,
+            resources.iter().map(|r| r.kind.as_str()),
+        ))
+    }
+}
+
+/// # APIGroupList
+/// Discovery document listing all the groups exposed by the service.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct APIGroupList {
+    pub groups: Vec<APIGroup>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct APIGroup {
+    pub name: String,
+    pub versions: Vec<GroupVersionForDiscovery>,
+    pub preferred_version: GroupVersionForDiscovery,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupVersionForDiscovery {
+    pub group_version: String,
+    pub version: String,
+}
+
+/// # APIResourceList
+/// Discovery document listing all the resources of a single group version.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct APIResourceList {
+    pub group_version: String,
+    pub resources: Vec<APIResource>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct APIResource {
+    pub name: String,
+    pub kind: String,
+    pub methods: Vec<HttpMethod>,
+}
+
+impl HateoasResource for APIGroupList {
+    const KIND: &'static str = "APIGroupList";
+    const VERSION: &'static str = "0.0.1";
+    const GROUP: &'static str = "hateoas.io";
+    const URL_PATH_SEGMENT: &'static str = "apis";
+}
+
+impl HateoasResource for APIResourceList {
+    const KIND: &'static str = "APIResourceList";
+    const VERSION: &'static str = "0.0.1";
+    const GROUP: &'static str = "hateoas.io";
+    const URL_PATH_SEGMENT: &'static str = "apis";
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{HateoasResource, HttpMethod, ResourceRegistry};
+
+    #[derive(Clone)]
+    struct BulletV1;
+    #[derive(Clone)]
+    struct BulletV2;
+    #[derive(Clone)]
+    struct Gun;
+
+    impl HateoasResource for BulletV1 {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "/bullets";
+    }
+
+    impl HateoasResource for BulletV2 {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v2";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    impl HateoasResource for Gun {
+        const KIND: &'static str = "Gun";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "weapons.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "guns";
+    }
+
+    fn registry() -> ResourceRegistry {
+        let mut registry = ResourceRegistry::new();
+        registry.register::<BulletV1>(vec![HttpMethod::Get]);
+        registry.register::<BulletV2>(vec![HttpMethod::Get, HttpMethod::Post]);
+        registry.register::<Gun>(vec![HttpMethod::Get]);
+        registry
+    }
+
+    #[test]
+    fn group_list_collects_versions() {
+        let response = registry().api_group_list();
+        let list = response.spec().and_then(|t| t.content().clone()).unwrap();
+
+        assert_eq!(list.groups.len(), 2);
+        assert_eq!(list.groups[0].name, "ammo.hateoas.io");
+        assert_eq!(list.groups[0].versions.len(), 2);
+        assert_eq!(list.groups[0].preferred_version.version, "v1");
+        assert_eq!(
+            list.groups[1].preferred_version.group_version,
+            "weapons.hateoas.io/v1"
+        );
+    }
+
+    #[test]
+    fn resource_list_for_group_version() {
+        let response = registry().api_resource_list("ammo.hateoas.io", "v2");
+        let list = response.spec().and_then(|t| t.content().clone()).unwrap();
+
+        assert_eq!(response.kind(), "APIResourceList");
+        assert_eq!(list.group_version, "ammo.hateoas.io/v2");
+        assert_eq!(list.resources.len(), 1);
+        assert_eq!(list.resources[0].name, "bullets");
+        assert_eq!(
+            list.resources[0].methods,
+            vec![HttpMethod::Get, HttpMethod::Post]
+        );
+    }
+
+    #[test]
+    fn discovery_serialization() {
+        let response = registry().api_group_list();
+        let value = serde_json::to_value(&response).unwrap();
+
+        assert_eq!(value["kind"], "APIGroupList");
+        assert_eq!(
+            value["spec"]["content"]["groups"][0]["preferredVersion"]["groupVersion"],
+            "ammo.hateoas.io/v1"
+        );
+    }
+}
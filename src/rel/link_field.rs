@@ -0,0 +1,75 @@
+use serde_json::Value;
+
+/// # LinkField
+/// Describing a field of the payload a [`crate::RelLink`] accepts, eg. the fields of a form for
+/// creating a resource. The fields are written as the fields of the Siren actions.
+///
+/// ```
+/// use hateoas::{LinkField, RelLink};
+///
+/// let mut caliber = LinkField::new("caliber");
+/// *caliber.field_type_mut() = Some("number".to_string());
+///
+/// let mut create = RelLink::POST("create", "/bullets");
+/// create.add_field(caliber);
+///
+/// assert_eq!(create.fields().unwrap()[0].name(), "caliber");
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct LinkField {
+    pub(crate) name: String,
+    #[serde(rename = "type")]
+    pub(crate) field_type: Option<String>,
+    pub(crate) value: Option<Value>,
+    pub(crate) title: Option<String>,
+}
+
+impl LinkField {
+    pub fn new(name: &str) -> Self {
+        LinkField {
+            name: name.to_string(),
+            field_type: None,
+            value: None,
+            title: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn name_mut(&mut self) -> &mut String {
+        &mut self.name
+    }
+
+    /// ## Getter for the field type
+    /// The type of the input, following the HTML5 input types, eg. `text`, `number` or `hidden`.
+    pub fn field_type(&self) -> Option<&String> {
+        self.field_type.as_ref()
+    }
+
+    pub fn field_type_mut(&mut self) -> &mut Option<String> {
+        &mut self.field_type
+    }
+
+    /// ## Getter for the value
+    /// The default value of the field.
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    pub fn value_mut(&mut self) -> &mut Option<Value> {
+        &mut self.value
+    }
+
+    pub fn title(&self) -> Option<&String> {
+        self.title.as_ref()
+    }
+
+    pub fn title_mut(&mut self) -> &mut Option<String> {
+        &mut self.title
+    }
+}
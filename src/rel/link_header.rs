@@ -0,0 +1,290 @@
+use crate::header::HeaderKey;
+use crate::{HttpMethod, RelLink, RelLinkCollection};
+use percent_encoding::percent_decode_str;
+
+#[derive(Debug, Display, PartialEq)]
+#[non_exhaustive]
+pub enum LinkHeaderError {
+    /// The link is not a valid RFC 8288 link-value
+    #[display(fmt = "Invalid link at position {}: {}", _0, _1)]
+    InvalidLink(usize, String),
+}
+
+impl RelLinkCollection {
+    /// ## Rendering the Link header
+    /// Rendering the rel links as an RFC 8288 `Link` header value, the `Content-Type` header of a
+    /// link is written as `type` and the name as `title`. Links that are not `GET` carry the method
+    /// as the `method` extension. If there are no links `None` is returned.
+    ///
+    /// ```
+    /// use hateoas::{RelLink, RelLinkCollection};
+    ///
+    /// let mut next = RelLink::GET("next", "/bullets?offset=10");
+    /// next.headers_mut().set("Content-Type", "application/json");
+    /// let links = RelLinkCollection::new(vec![next, RelLink::DELETE("delete", "/bullets/9")]);
+    ///
+    /// assert_eq!(
+    ///     links.to_link_header(),
+    ///     Some(r#"</bullets?offset=10>; rel="next"; type="application/json", </bullets/9>; rel="delete"; method="DELETE""#.to_string())
+    /// );
+    /// assert_eq!(RelLinkCollection::default().to_link_header(), None);
+    /// ```
+    pub fn to_link_header(&self) -> Option<String> {
+        let links = self
+            .0
+            .iter()
+            .map(|link| {
+                let mut value = format!("<{}>; rel={}", link.href(), quote(link.rel()));
+                if let Some(content_type) = link.headers().get_first(&HeaderKey::ContentType) {
+                    value.push_str(&format!("; type={}", quote(content_type)));
+                }
+                if let Some(name) = link.name() {
+                    value.push_str(&format!("; title={}", quote(name)));
+                }
+                if link.method() != &HttpMethod::Get {
+                    value.push_str(&format!("; method={}", quote(link.method().as_str())));
+                }
+                value
+            })
+            .collect::<Vec<String>>();
+        (!links.is_empty()).then(|| links.join(", "))
+    }
+
+    /// ## Parsing a Link header
+    /// Parsing an RFC 8288 `Link` header value into rel links, a link with multiple relation types
+    /// is read as one rel link per relation type. The `title*` parameter takes precedence over
+    /// `title`.
+    ///
+    /// ```
+    /// use hateoas::{HttpMethod, RelLinkCollection};
+    ///
+    /// let links = RelLinkCollection::from_link_header(
+    ///     r#"</bullets?offset=10>; rel="next", </bullets/9>; rel="delete"; method="DELETE""#,
+    /// ).unwrap();
+    ///
+    /// assert_eq!(links.get("next").map(|t| t.href()), Some("/bullets?offset=10"));
+    /// assert_eq!(links.get("delete").map(|t| t.method()), Some(&HttpMethod::Delete));
+    /// assert!(RelLinkCollection::from_link_header("/bullets; rel=next").is_err());
+    /// ```
+    pub fn from_link_header(value: &str) -> Result<Self, LinkHeaderError> {
+        let mut parser = Parser {
+            input: value.as_bytes(),
+            value,
+            position: 0,
+        };
+        let mut links = vec![];
+        loop {
+            parser.skip(|t| t.is_ascii_whitespace() || t == b',');
+            if parser.is_done() {
+                break;
+            }
+            links.extend(parser.link()?);
+        }
+        Ok(RelLinkCollection::new(links))
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    value: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn is_done(&self) -> bool {
+        self.position >= self.input.len()
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.position).copied()
+    }
+
+    fn skip<F: Fn(u8) -> bool>(&mut self, f: F) {
+        while self.peek().map(&f).unwrap_or(false) {
+            self.position += 1;
+        }
+    }
+
+    fn take<F: Fn(u8) -> bool>(&mut self, f: F) -> &'a str {
+        let start = self.position;
+        self.skip(f);
+        &self.value[start..self.position]
+    }
+
+    fn error(&self, message: &str) -> LinkHeaderError {
+        LinkHeaderError::InvalidLink(self.position, message.to_string())
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), LinkHeaderError> {
+        match self.peek() {
+            Some(t) if t == c => {
+                self.position += 1;
+                Ok(())
+            }
+            _ => Err(self.error(&format!("expected '{}'", c as char))),
+        }
+    }
+
+    fn link(&mut self) -> Result<Vec<RelLink>, LinkHeaderError> {
+        self.expect(b'<')?;
+        let href = self.take(|t| t != b'>');
+        self.expect(b'>')?;
+
+        let mut params: Vec<(String, String)> = vec![];
+        loop {
+            self.skip(|t| t.is_ascii_whitespace());
+            match self.peek() {
+                None | Some(b',') => break,
+                Some(b';') => self.position += 1,
+                _ => return Err(self.error("expected ';' or ','")),
+            }
+            self.skip(|t| t.is_ascii_whitespace());
+            let name = self
+                .take(|t| !matches!(t, b'=' | b';' | b',') && !t.is_ascii_whitespace())
+                .to_ascii_lowercase();
+            self.skip(|t| t.is_ascii_whitespace());
+            let value = if self.peek() == Some(b'=') {
+                self.position += 1;
+                self.skip(|t| t.is_ascii_whitespace());
+                self.param_value()?
+            } else {
+                String::new()
+            };
+            // Only the first occurrence of a parameter is used
+            if !name.is_empty() && !params.iter().any(|(t, _)| t == &name) {
+                params.push((name, value));
+            }
+        }
+
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(t, _)| t == name)
+                .map(|(_, t)| t.as_str())
+        };
+        let rel = param("rel").ok_or_else(|| self.error("link is missing rel"))?;
+        let method = match param("method") {
+            Some(method) => HttpMethod::from_name(method)
+                .ok_or_else(|| self.error(&format!("unknown method {}", method)))?,
+            None => HttpMethod::Get,
+        };
+        let title = param("title*")
+            .and_then(decode_ext_value)
+            .or_else(|| param("title").map(|t| t.to_string()));
+
+        Ok(rel
+            .split_ascii_whitespace()
+            .map(|rel| {
+                let mut link = RelLink::new(rel, href, method.clone(), None);
+                if let Some(content_type) = param("type") {
+                    link.headers_mut().set("Content-Type", content_type);
+                }
+                *link.name_mut() = title.clone();
+                link
+            })
+            .collect())
+    }
+
+    fn param_value(&mut self) -> Result<String, LinkHeaderError> {
+        if self.peek() != Some(b'"') {
+            return Ok(self
+                .take(|t| !matches!(t, b';' | b',') && !t.is_ascii_whitespace())
+                .to_string());
+        }
+
+        self.position += 1;
+        let mut value = String::new();
+        loop {
+            let part = self.take(|t| t != b'"' && t != b'\\');
+            value.push_str(part);
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(value);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = self.value[self.position..]
+                        .chars()
+                        .next()
+                        .ok_or_else(|| self.error("unterminated quoted string"))?;
+                    value.push(escaped);
+                    self.position += escaped.len_utf8();
+                }
+                _ => return Err(self.error("unterminated quoted string")),
+            }
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Decoding an RFC 8187 ext-value, eg. `UTF-8'en'%E2%82%AC%20rates`.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+    if !charset.eq_ignore_ascii_case("utf-8") {
+        return None;
+    }
+    percent_decode_str(encoded)
+        .decode_utf8()
+        .ok()
+        .map(|t| t.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{HttpMethod, LinkHeaderError, RelLink, RelLinkCollection};
+
+    #[test]
+    fn link_header_round_trip() {
+        let mut search = RelLink::GET("search", "/bullets{?caliber}");
+        search
+            .headers_mut()
+            .set("Content-Type", "application/hal+json");
+        *search.name_mut() = Some(r#"by "caliber""#.to_string());
+        let links = RelLinkCollection::new(vec![
+            RelLink::GET("self", "/bullets/9"),
+            search,
+            RelLink::PATCH("update", "/bullets/9"),
+        ]);
+
+        let header = links.to_link_header().unwrap();
+
+        assert_eq!(RelLinkCollection::from_link_header(&header), Ok(links));
+    }
+
+    #[test]
+    fn link_header_parsing() {
+        let links = RelLinkCollection::from_link_header(
+            "<https://example.com/a,b>; rel=\"next prev\"; title*=UTF-8'de'n%c3%a4chstes, \
+             <https://example.com/c>;REL=last;title=\"ignored\"",
+        )
+        .unwrap();
+        let links: Vec<RelLink> = links.into();
+
+        assert_eq!(links.len(), 3);
+        assert_eq!(links[0].rel(), "next");
+        assert_eq!(links[0].href(), "https://example.com/a,b");
+        assert_eq!(links[0].name(), Some(&"nächstes".to_string()));
+        assert_eq!(links[1].rel(), "prev");
+        assert_eq!(links[2].rel(), "last");
+        assert_eq!(links[2].method(), &HttpMethod::Get);
+        assert_eq!(links[2].name(), Some(&"ignored".to_string()));
+    }
+
+    #[test]
+    fn link_header_without_rel() {
+        assert_eq!(
+            RelLinkCollection::from_link_header("</bullets>; title=\"bullets\""),
+            Err(LinkHeaderError::InvalidLink(
+                27,
+                "link is missing rel".to_string()
+            ))
+        );
+    }
+}
@@ -1,2 +1,4 @@
+pub mod link_field;
+pub mod link_header;
 pub mod rel_link;
 pub mod rel_link_collection;
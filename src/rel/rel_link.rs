@@ -1,12 +1,19 @@
 use crate::header::HeaderMap;
 use crate::http_method::HttpMethod;
+use crate::LinkField;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct RelLink {
     pub(crate) href: String,
     pub(crate) rel: String,
     pub(crate) method: HttpMethod,
     pub(crate) headers: HeaderMap,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) fields: Option<Vec<LinkField>>,
 }
 
 impl RelLink {
@@ -26,6 +33,8 @@ impl RelLink {
             rel: rel.to_string(),
             method,
             headers: headers.into(),
+            name: None,
+            fields: None,
         }
     }
 
@@ -140,6 +149,78 @@ impl RelLink {
     pub fn headers_mut(&mut self) -> &mut HeaderMap {
         &mut self.headers
     }
+
+    /// ## Getter for name
+    /// The name is a secondary key for selecting between links with the same rel.
+    ///
+    /// ```
+    /// use hateoas::RelLink;
+    ///
+    /// let rel = RelLink::GET("author", "/authors/rick");
+    ///
+    /// assert_eq!(rel.name(), None);
+    /// ```
+    pub fn name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    /// ## Getter/Setter for name
+    ///
+    /// ```
+    /// use hateoas::RelLink;
+    ///
+    /// let mut rel = RelLink::GET("author", "/authors/rick");
+    /// *(rel.name_mut()) = Some("rick".to_string());
+    ///
+    /// assert_eq!(rel.name(), Some(&"rick".to_string()));
+    /// ```
+    pub fn name_mut(&mut self) -> &mut Option<String> {
+        &mut self.name
+    }
+
+    /// ## Getter for the field descriptors
+    /// The fields of the payload accepted by the link, see [`LinkField`].
+    ///
+    /// ```
+    /// use hateoas::RelLink;
+    ///
+    /// let rel = RelLink::POST("create", "/bullets");
+    ///
+    /// assert_eq!(rel.fields(), None);
+    /// ```
+    pub fn fields(&self) -> Option<&Vec<LinkField>> {
+        self.fields.as_ref()
+    }
+
+    /// ## Getter/Setter for the field descriptors
+    pub fn fields_mut(&mut self) -> &mut Option<Vec<LinkField>> {
+        &mut self.fields
+    }
+
+    /// ## Adding a field descriptor
+    /// Adding the field, if there is already a field with the same name it will be replaced and the
+    /// old field is returned.
+    ///
+    /// ```
+    /// use hateoas::{LinkField, RelLink};
+    ///
+    /// let mut rel = RelLink::POST("create", "/bullets");
+    /// rel.add_field(LinkField::new("caliber"));
+    /// let old = rel.add_field(LinkField::new("caliber"));
+    ///
+    /// assert_eq!(old, Some(LinkField::new("caliber")));
+    /// assert_eq!(rel.fields().unwrap().len(), 1);
+    /// ```
+    pub fn add_field(&mut self, field: LinkField) -> Option<LinkField> {
+        let fields = self.fields.get_or_insert_with(Vec::new);
+        match fields.iter_mut().find(|t| t.name == field.name) {
+            Some(existing) => Some(std::mem::replace(existing, field)),
+            None => {
+                fields.push(field);
+                None
+            }
+        }
+    }
 }
 
 impl From<(String, String, HttpMethod)> for RelLink {
@@ -201,7 +282,9 @@ macro_rules! relational_links {
                     href: format!("{}",href),
                     rel: format!("{}",rel),
                     method: HttpMethod::$konst,
-                    headers: HeaderMap::default()
+                    headers: HeaderMap::default(),
+                    name: None,
+                    fields: None,
                 }
             }
         )+
@@ -70,7 +70,9 @@ use crate::RelLink;
 /// ```
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Default, Clone)]
-pub struct RelLinkCollection(Vec<RelLink>);
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct RelLinkCollection(pub(crate) Vec<RelLink>);
 
 impl RelLinkCollection {
     /// ## Create Collection
@@ -0,0 +1,576 @@
+//! Collection+JSON
+//!
+//! The content is written as the `data` of an item, a single resource is written as a collection
+//! with one item and the items of a [`crate::HateoasList`] as the items of the collection. The
+//! `self` link is written as the `href`, `GET` links with [`crate::LinkField`]s are written as
+//! `queries` and the fields of the first link with another method as the `template`. Responses with
+//! a 4xx/5xx status carry the status in the `error` object.
+//!
+//! Collection+JSON has no place for the list metadata or links with other methods than `GET`,
+//! these are therefore left out.
+use crate::representation::RepresentationError;
+use crate::{
+    Content, Hateoas, HateoasResource, HttpMethod, LinkField, RelLink, RelLinkCollection, Status,
+    StatusCode,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const COLLECTION_JSON: &str = "Collection+JSON";
+const VERSION: &str = "1.0";
+const SELF: &str = "self";
+
+pub(crate) fn to_collection_json<T>(hateoas: &Hateoas<T>) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    let envelope = serde_json::to_value(hateoas)?;
+    let spec = &envelope["spec"];
+    let mut collection = Map::from_iter([("version".to_string(), Value::from(VERSION))]);
+
+    let links = Links::from_rel(&spec["rel"])?;
+    if let Some(href) = &links.href {
+        collection.insert("href".to_string(), Value::from(href.as_str()));
+    }
+
+    let list = T::IS_LIST && spec["content"].get("items").map(Value::is_array) == Some(true);
+    let items = if list {
+        if !links.links.is_empty() {
+            let links = links
+                .links
+                .iter()
+                .map(|t| Value::Object(link_to_collection_json(t)))
+                .collect();
+            collection.insert("links".to_string(), Value::Array(links));
+        }
+        spec["content"]["items"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|t| item_to_collection_json(t, true))
+            .collect::<Result<Vec<Value>, RepresentationError>>()?
+    } else if spec.get("content").map(Value::is_null) == Some(false) {
+        vec![item_to_collection_json(spec, false)?]
+    } else {
+        vec![]
+    };
+    if !items.is_empty() {
+        collection.insert("items".to_string(), Value::Array(items));
+    }
+
+    if !links.queries.is_empty() {
+        let queries = links.queries.iter().map(query_to_collection_json).collect();
+        collection.insert("queries".to_string(), Value::Array(queries));
+    }
+    if let Some(template) = links.template.as_ref().and_then(RelLink::fields) {
+        let data = template.iter().map(field_to_collection_json).collect();
+        let template = Map::from_iter([("data".to_string(), Value::Array(data))]);
+        collection.insert("template".to_string(), Value::Object(template));
+    }
+    if let Some(error) = hateoas.status().and_then(error_to_collection_json) {
+        collection.insert("error".to_string(), error);
+    }
+
+    Ok(Value::Object(Map::from_iter([(
+        "collection".to_string(),
+        Value::Object(collection),
+    )])))
+}
+
+/// Reading a collection document, or a template submitted by the client.
+pub(crate) fn from_collection_json<T>(value: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    let mut document = match value {
+        Value::Object(document) => document,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                COLLECTION_JSON,
+                "expected an object".to_string(),
+            ))
+        }
+    };
+
+    if let Some(collection) = document.remove("collection") {
+        return collection_from_collection_json(collection);
+    }
+    match document.remove("template") {
+        Some(template) => {
+            let content = data_from_collection_json(template.get("data").cloned())?;
+            Ok(Hateoas::new(
+                Some(Content::new(deserialize_content(content)?)),
+                None,
+                None,
+            ))
+        }
+        None => Err(RepresentationError::InvalidDocument(
+            COLLECTION_JSON,
+            "the document must contain a collection or a template".to_string(),
+        )),
+    }
+}
+
+/// The rel links sorted by where they are written in the collection.
+#[derive(Default)]
+struct Links {
+    href: Option<String>,
+    links: Vec<RelLink>,
+    queries: Vec<RelLink>,
+    template: Option<RelLink>,
+}
+
+impl Links {
+    fn from_rel(rel: &Value) -> Result<Self, RepresentationError> {
+        let mut links = Links::default();
+        if rel.is_null() {
+            return Ok(links);
+        }
+        let collection: RelLinkCollection = serde_json::from_value(rel.clone())?;
+        for link in Vec::<RelLink>::from(collection) {
+            if link.method() != &HttpMethod::Get {
+                if links.template.is_none() && link.fields().is_some() {
+                    links.template = Some(link);
+                }
+            } else if link.rel() == SELF && links.href.is_none() {
+                links.href = Some(link.href().to_string());
+            } else if link.fields().is_some() {
+                links.queries.push(link);
+            } else {
+                links.links.push(link);
+            }
+        }
+        Ok(links)
+    }
+}
+
+/// Items of a list have no queries, when `list_item` is set the links with fields are written as
+/// plain links.
+fn item_to_collection_json(spec: &Value, list_item: bool) -> Result<Value, RepresentationError> {
+    let mut item = Map::new();
+    let links = Links::from_rel(&spec["rel"])?;
+    if let Some(href) = links.href {
+        item.insert("href".to_string(), Value::from(href));
+    }
+
+    let data = match spec.get("content") {
+        Some(Value::Object(content)) => content
+            .iter()
+            .map(|(name, value)| data_to_collection_json(name, value.clone()))
+            .collect(),
+        None | Some(Value::Null) => vec![],
+        Some(content) => vec![data_to_collection_json("content", content.clone())],
+    };
+    item.insert("data".to_string(), Value::Array(data));
+
+    let queries = if list_item { links.queries } else { vec![] };
+    let links = links
+        .links
+        .iter()
+        .chain(queries.iter())
+        .map(|t| Value::Object(link_to_collection_json(t)))
+        .collect::<Vec<Value>>();
+    if !links.is_empty() {
+        item.insert("links".to_string(), Value::Array(links));
+    }
+    Ok(Value::Object(item))
+}
+
+fn data_to_collection_json(name: &str, value: Value) -> Value {
+    Value::Object(Map::from_iter([
+        ("name".to_string(), Value::from(name)),
+        ("value".to_string(), value),
+    ]))
+}
+
+fn link_to_collection_json(link: &RelLink) -> Map<String, Value> {
+    let mut collection_link = Map::from_iter([
+        ("rel".to_string(), Value::from(link.rel())),
+        ("href".to_string(), Value::from(link.href())),
+    ]);
+    if let Some(name) = link.name() {
+        collection_link.insert("name".to_string(), Value::from(name.as_str()));
+    }
+    collection_link
+}
+
+fn query_to_collection_json(link: &RelLink) -> Value {
+    let mut query = link_to_collection_json(link);
+    let data = link
+        .fields()
+        .into_iter()
+        .flatten()
+        .map(field_to_collection_json)
+        .collect();
+    query.insert("data".to_string(), Value::Array(data));
+    Value::Object(query)
+}
+
+/// Fields without a value are written with an empty value, as the value is required.
+fn field_to_collection_json(field: &LinkField) -> Value {
+    let mut data = Map::from_iter([
+        ("name".to_string(), Value::from(field.name())),
+        (
+            "value".to_string(),
+            field.value().cloned().unwrap_or_else(|| Value::from("")),
+        ),
+    ]);
+    if let Some(title) = field.title() {
+        data.insert("prompt".to_string(), Value::from(title.as_str()));
+    }
+    Value::Object(data)
+}
+
+fn error_to_collection_json(status: &Status) -> Option<Value> {
+    let status_code = status.http_status_code().as_ref().map(u16::from)?;
+    if status_code < 400 {
+        return None;
+    }
+
+    let mut error = Map::from_iter([("code".to_string(), Value::from(status_code.to_string()))]);
+    if let Some(message) = status.message() {
+        error.insert("message".to_string(), Value::from(message.as_str()));
+    }
+    Some(Value::Object(error))
+}
+
+fn collection_from_collection_json<T>(collection: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    let mut collection = match collection {
+        Value::Object(collection) => collection,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                COLLECTION_JSON,
+                "collection must be an object".to_string(),
+            ))
+        }
+    };
+
+    let status = collection.remove("error").map(status_from_collection_json);
+    let mut rel = links_from_collection_json(
+        collection.remove("href"),
+        collection.remove("links"),
+        collection.remove("queries"),
+    )?;
+    let mut items = vec![];
+    for item in as_array(collection.remove("items"), "items")? {
+        let mut item = match item {
+            Value::Object(item) => item,
+            _ => {
+                return Err(RepresentationError::InvalidDocument(
+                    COLLECTION_JSON,
+                    "items must be objects".to_string(),
+                ))
+            }
+        };
+        let links = links_from_collection_json(item.remove("href"), item.remove("links"), None)?;
+        items.push((data_from_collection_json(item.remove("data"))?, links));
+    }
+
+    let content = if T::IS_LIST {
+        let items = items
+            .into_iter()
+            .map(|(content, links)| {
+                let mut item = Map::from_iter([("content".to_string(), unwrap_content(content))]);
+                if !links.is_empty() {
+                    item.insert(
+                        "rel".to_string(),
+                        serde_json::to_value(RelLinkCollection::new(links))?,
+                    );
+                }
+                Ok(Value::Object(item))
+            })
+            .collect::<Result<Vec<Value>, RepresentationError>>()?;
+        let list = Map::from_iter([("items".to_string(), Value::Array(items))]);
+        Some(serde_json::from_value(Value::Object(list))?)
+    } else {
+        match items.into_iter().next() {
+            Some((content, links)) => {
+                // The collection href of a single resource is the href of the item
+                rel.retain(|t| !(t.rel() == SELF && links.iter().any(|l| l.rel() == SELF)));
+                rel.splice(0..0, links);
+                Some(deserialize_content(content)?)
+            }
+            None => None,
+        }
+    };
+
+    let spec = match (content, rel.is_empty()) {
+        (None, true) => None,
+        (content, _) => {
+            let mut spec = Content::default();
+            *spec.content_mut() = content;
+            *spec.rel() = RelLinkCollection::new(rel);
+            Some(spec)
+        }
+    };
+    Ok(Hateoas::new(spec, None, status))
+}
+
+fn links_from_collection_json(
+    href: Option<Value>,
+    links: Option<Value>,
+    queries: Option<Value>,
+) -> Result<Vec<RelLink>, RepresentationError> {
+    let mut rel_links = vec![];
+    if let Some(href) = href.as_ref().and_then(Value::as_str) {
+        rel_links.push(RelLink::GET(SELF, href));
+    }
+    for link in as_array(links, "links")?
+        .into_iter()
+        .chain(as_array(queries.clone(), "queries")?)
+    {
+        let (rel, href) = match (link["rel"].as_str(), link["href"].as_str()) {
+            (Some(rel), Some(href)) => (rel, href),
+            _ => {
+                return Err(RepresentationError::InvalidDocument(
+                    COLLECTION_JSON,
+                    "link is missing rel or href".to_string(),
+                ))
+            }
+        };
+        let mut rel_link = RelLink::GET(rel, href);
+        *rel_link.name_mut() = link["name"].as_str().map(|t| t.to_string());
+        if let Some(Value::Array(data)) = link.get("data") {
+            let mut fields = vec![];
+            for data in data {
+                let name = data["name"].as_str().ok_or_else(|| {
+                    RepresentationError::InvalidDocument(
+                        COLLECTION_JSON,
+                        "data is missing name".to_string(),
+                    )
+                })?;
+                let mut field = LinkField::new(name);
+                *field.value_mut() = data
+                    .get("value")
+                    .filter(|t| !t.is_null() && t.as_str() != Some(""))
+                    .cloned();
+                *field.title_mut() = data["prompt"].as_str().map(|t| t.to_string());
+                fields.push(field);
+            }
+            *rel_link.fields_mut() = Some(fields);
+        }
+        rel_links.push(rel_link);
+    }
+    Ok(rel_links)
+}
+
+fn data_from_collection_json(
+    data: Option<Value>,
+) -> Result<Map<String, Value>, RepresentationError> {
+    let mut object = Map::new();
+    for data in as_array(data, "data")? {
+        let name = data["name"].as_str().ok_or_else(|| {
+            RepresentationError::InvalidDocument(
+                COLLECTION_JSON,
+                "data is missing name".to_string(),
+            )
+        })?;
+        if let Some(value) = data.get("value") {
+            object.insert(name.to_string(), value.clone());
+        }
+    }
+    Ok(object)
+}
+
+fn status_from_collection_json(error: Value) -> Status {
+    let status_code = error["code"]
+        .as_str()
+        .and_then(|t| t.parse::<u16>().ok())
+        .map(StatusCode::from);
+    let message = error["message"]
+        .as_str()
+        .or_else(|| error["title"].as_str());
+    Status::new(message, None, status_code, None, None)
+}
+
+/// Content that is not an object is written as the `content` data.
+fn unwrap_content(mut content: Map<String, Value>) -> Value {
+    match (content.len(), content.remove("content")) {
+        (1, Some(content)) => content,
+        (_, Some(value)) => {
+            content.insert("content".to_string(), value);
+            Value::Object(content)
+        }
+        (_, None) => Value::Object(content),
+    }
+}
+
+fn deserialize_content<U: DeserializeOwned>(
+    content: Map<String, Value>,
+) -> Result<U, RepresentationError> {
+    serde_json::from_value(Value::Object(content.clone())).or_else(|e| {
+        serde_json::from_value(unwrap_content(content)).map_err(|_| RepresentationError::from(e))
+    })
+}
+
+fn as_array(value: Option<Value>, name: &str) -> Result<Vec<Value>, RepresentationError> {
+    match value {
+        None => Ok(vec![]),
+        Some(Value::Array(values)) => Ok(values),
+        Some(_) => Err(RepresentationError::InvalidDocument(
+            COLLECTION_JSON,
+            format!("{} must be an array", name),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Content, Hateoas, HateoasList, LinkField, RelLink, Representation, ResourceList, StatusCode,
+    };
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Bullet {
+        caliber: u32,
+        material: String,
+    }
+
+    impl crate::HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    fn bullet() -> Hateoas<Bullet> {
+        let mut content = Content::new(Bullet {
+            caliber: 9,
+            material: "rubber".to_string(),
+        });
+        content.rel().add(RelLink::GET("self", "/bullets/9"));
+        content.rel().add(RelLink::GET("author", "/authors/rick"));
+        let mut search = RelLink::GET("search", "/bullets");
+        *search.name_mut() = Some("by-caliber".to_string());
+        let mut caliber = LinkField::new("caliber");
+        *caliber.title_mut() = Some("Caliber".to_string());
+        search.add_field(caliber);
+        content.rel().add(search);
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn collection_json_encoding() {
+        let mut bullet = bullet();
+        let mut create = RelLink::POST("create", "/bullets");
+        create.add_field(LinkField::new("caliber"));
+        create.add_field(LinkField::new("material"));
+        bullet.spec_mut().rel().add(create);
+
+        let encoded = Representation::CollectionJson.encode(&bullet).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "collection": {
+                    "version": "1.0",
+                    "href": "/bullets/9",
+                    "items": [{
+                        "href": "/bullets/9",
+                        "data": [
+                            {"name": "caliber", "value": 9},
+                            {"name": "material", "value": "rubber"}
+                        ],
+                        "links": [{"rel": "author", "href": "/authors/rick"}]
+                    }],
+                    "queries": [{
+                        "rel": "search",
+                        "href": "/bullets",
+                        "name": "by-caliber",
+                        "data": [{"name": "caliber", "value": "", "prompt": "Caliber"}]
+                    }],
+                    "template": {
+                        "data": [
+                            {"name": "caliber", "value": ""},
+                            {"name": "material", "value": ""}
+                        ]
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn collection_json_round_trip() {
+        let encoded = Representation::CollectionJson.encode(&bullet()).unwrap();
+        let decoded: Hateoas<Bullet> = Representation::CollectionJson.decode(&encoded).unwrap();
+        let spec = decoded.spec().unwrap();
+
+        assert_eq!(spec.content(), bullet().spec().unwrap().content());
+        let mut rel = spec.clone();
+        assert_eq!(
+            rel.rel().get("self"),
+            Some(&RelLink::GET("self", "/bullets/9"))
+        );
+        assert_eq!(
+            rel.rel().get("search"),
+            bullet().spec_mut().rel().get("search")
+        );
+    }
+
+    #[test]
+    fn collection_json_list_round_trip() {
+        let mut item = Content::new(9u16);
+        item.rel().add(RelLink::GET("self", "/numbers/9"));
+        let mut list: HateoasList<u16> = HateoasList::OK(Some(ResourceList::new(vec![item])), None);
+        list.spec_mut().rel().add(RelLink::GET("self", "/numbers"));
+        list.spec_mut()
+            .rel()
+            .add(RelLink::GET("next", "/numbers?offset=10"));
+
+        let encoded = Representation::CollectionJson.encode(&list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["collection"]["href"], "/numbers");
+        assert_eq!(
+            value["collection"]["links"],
+            json!([{"rel": "next", "href": "/numbers?offset=10"}])
+        );
+        assert_eq!(
+            value["collection"]["items"],
+            json!([{"href": "/numbers/9", "data": [{"name": "content", "value": 9}]}])
+        );
+
+        let decoded: HateoasList<u16> = Representation::CollectionJson.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), list.spec());
+    }
+
+    #[test]
+    fn collection_json_error() {
+        let error: Hateoas<Bullet> =
+            Hateoas::NOT_FOUND(None, Some("Bullet 9 not found".to_string()));
+
+        let encoded = Representation::CollectionJson.encode(&error).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({"collection": {"version": "1.0", "error": {"code": "404", "message": "Bullet 9 not found"}}})
+        );
+
+        let decoded: Hateoas<Bullet> = Representation::CollectionJson.decode(&encoded).unwrap();
+        assert!(decoded.spec().is_none());
+        let status = decoded.status().unwrap();
+        assert_eq!(status.http_status_code(), &Some(StatusCode::NotFound));
+        assert_eq!(status.message(), &Some("Bullet 9 not found".to_string()));
+    }
+
+    #[test]
+    fn collection_json_template_submission() {
+        let document = br#"{"template": {"data": [{"name": "caliber", "value": 9}, {"name": "material", "value": "lead"}]}}"#;
+
+        let decoded: Hateoas<Bullet> = Representation::CollectionJson.decode(document).unwrap();
+
+        assert_eq!(
+            decoded.spec().unwrap().content(),
+            &Some(Bullet {
+                caliber: 9,
+                material: "lead".to_string()
+            })
+        );
+    }
+}
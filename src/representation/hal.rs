@@ -0,0 +1,425 @@
+//! Hypertext Application Language (HAL)
+//!
+//! The content fields are written at the top level of the document, the rel links are written to
+//! `_links` keyed by rel and the embedded resources to `_embedded`. The items of a
+//! [`crate::HateoasList`] are embedded as `items`.
+use crate::header::HeaderKey;
+use crate::representation::RepresentationError;
+use crate::{Content, Embedded, Hateoas, HateoasResource, RelLink, RelLinkCollection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const HAL: &str = "HAL";
+
+pub(crate) fn to_hal<T>(hateoas: &Hateoas<T>) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    spec_to_hal(&serde_json::to_value(hateoas)?["spec"], T::IS_LIST)
+}
+
+pub(crate) fn from_hal<T>(value: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    match value {
+        Value::Object(document) => Ok(Hateoas::new(Some(content_from_hal(document)?), None, None)),
+        _ => Err(RepresentationError::InvalidDocument(
+            HAL,
+            "expected an object".to_string(),
+        )),
+    }
+}
+
+fn spec_to_hal(spec: &Value, list: bool) -> Result<Value, RepresentationError> {
+    let mut document = match spec.get("content") {
+        Some(Value::Object(content)) => content.clone(),
+        None | Some(Value::Null) => Map::new(),
+        Some(content) => Map::from_iter([("content".to_string(), content.clone())]),
+    };
+    let mut embedded = Map::new();
+
+    if list {
+        if let Some(Value::Array(items)) = document.remove("items") {
+            let items = items
+                .iter()
+                .map(|t| spec_to_hal(t, false))
+                .collect::<Result<Vec<Value>, RepresentationError>>()?;
+            embedded.insert("items".to_string(), Value::Array(items));
+        }
+    }
+    if let Some(rel) = spec.get("rel").filter(|t| !t.is_null()) {
+        let links: RelLinkCollection = serde_json::from_value(rel.clone())?;
+        let links = links_to_hal(links.into());
+        if !links.is_empty() {
+            document.insert("_links".to_string(), Value::Object(links));
+        }
+    }
+    if let Some(resources) = spec.get("embedded").filter(|t| !t.is_null()) {
+        let resources: Embedded = serde_json::from_value(resources.clone())?;
+        for (name, resource, list) in resources.resources() {
+            embedded.insert(name.to_string(), spec_to_hal(&resource["spec"], list)?);
+        }
+    }
+    if !embedded.is_empty() {
+        document.insert("_embedded".to_string(), Value::Object(embedded));
+    }
+
+    Ok(Value::Object(document))
+}
+
+fn links_to_hal(links: Vec<RelLink>) -> Map<String, Value> {
+    let mut hal_links = Map::new();
+    for link in links {
+        let mut hal_link = Map::new();
+        hal_link.insert("href".to_string(), Value::from(link.href()));
+        if link.href().contains('{') && link.href().contains('}') {
+            hal_link.insert("templated".to_string(), Value::Bool(true));
+        }
+        if let Some(content_type) = link.headers().get_first(&HeaderKey::ContentType) {
+            hal_link.insert("type".to_string(), Value::from(content_type.as_str()));
+        }
+        if let Some(name) = link.name() {
+            hal_link.insert("name".to_string(), Value::from(name.as_str()));
+        }
+
+        match hal_links.get_mut(link.rel()) {
+            Some(Value::Array(existing)) => existing.push(Value::Object(hal_link)),
+            Some(existing) => {
+                *existing = Value::Array(vec![existing.take(), Value::Object(hal_link)])
+            }
+            None => {
+                hal_links.insert(link.rel().to_string(), Value::Object(hal_link));
+            }
+        }
+    }
+    hal_links
+}
+
+fn links_from_hal(links: Value) -> Result<RelLinkCollection, RepresentationError> {
+    let links = match links {
+        Value::Object(links) => links,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                HAL,
+                "_links must be an object".to_string(),
+            ))
+        }
+    };
+
+    let mut collection = vec![];
+    for (rel, link) in links {
+        let link = match link {
+            Value::Array(link) => link,
+            link => vec![link],
+        };
+        for link in link {
+            let href = link["href"].as_str().ok_or_else(|| {
+                RepresentationError::InvalidDocument(HAL, format!("link {} is missing href", rel))
+            })?;
+            let mut rel_link = RelLink::GET(&rel, href);
+            if let Some(content_type) = link["type"].as_str() {
+                rel_link.headers_mut().set("Content-Type", content_type);
+            }
+            *rel_link.name_mut() = link["name"].as_str().map(|t| t.to_string());
+            collection.push(rel_link);
+        }
+    }
+    Ok(RelLinkCollection::new(collection))
+}
+
+fn content_from_hal<T: DeserializeOwned>(
+    mut document: Map<String, Value>,
+) -> Result<Content<T>, RepresentationError> {
+    let links = document.remove("_links").map(links_from_hal).transpose()?;
+    let mut embedded = match document.remove("_embedded") {
+        Some(Value::Object(embedded)) => embedded,
+        None => Map::new(),
+        Some(_) => {
+            return Err(RepresentationError::InvalidDocument(
+                HAL,
+                "_embedded must be an object".to_string(),
+            ))
+        }
+    };
+
+    let mut content = if document.is_empty() && !embedded.contains_key("items") {
+        Content::default()
+    } else {
+        match serde_json::from_value::<T>(Value::Object(document.clone())) {
+            Ok(content) => Content::new(content),
+            Err(e) => {
+                if let (1, Some(content)) = (document.len(), document.get("content")) {
+                    Content::new(serde_json::from_value(content.clone())?)
+                } else if let Some(Value::Array(items)) = embedded.get("items") {
+                    let items = items
+                        .iter()
+                        .cloned()
+                        .map(spec_from_hal)
+                        .collect::<Result<Vec<Value>, RepresentationError>>()?;
+                    document.insert("items".to_string(), Value::Array(items));
+                    let content = serde_json::from_value(Value::Object(document))?;
+                    embedded.remove("items");
+                    Content::new(content)
+                } else {
+                    return Err(e.into());
+                }
+            }
+        }
+    };
+
+    if let Some(links) = links {
+        *content.rel() = links;
+    }
+    for (name, resource) in embedded {
+        let list = resource.is_array();
+        let envelope = Value::Object(Map::from_iter([(
+            "spec".to_string(),
+            spec_from_hal(resource)?,
+        )]));
+        content.embedded_mut().insert_raw(&name, envelope, list);
+    }
+    Ok(content)
+}
+
+/// Converting an embedded HAL resource into the `Content` shape, arrays of resources are read as a
+/// list.
+fn spec_from_hal(resource: Value) -> Result<Value, RepresentationError> {
+    let mut document = match resource {
+        Value::Object(document) => document,
+        Value::Array(items) => {
+            let items = items
+                .into_iter()
+                .map(spec_from_hal)
+                .collect::<Result<Vec<Value>, RepresentationError>>()?;
+            let list = Map::from_iter([("items".to_string(), Value::Array(items))]);
+            return Ok(Value::Object(Map::from_iter([(
+                "content".to_string(),
+                Value::Object(list),
+            )])));
+        }
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                HAL,
+                "embedded resources must be objects".to_string(),
+            ))
+        }
+    };
+
+    let mut spec = Map::new();
+    if let Some(links) = document.remove("_links") {
+        spec.insert(
+            "rel".to_string(),
+            serde_json::to_value(links_from_hal(links)?)?,
+        );
+    }
+    if let Some(Value::Object(embedded)) = document.remove("_embedded") {
+        let mut resources = Embedded::default();
+        for (name, resource) in embedded {
+            let list = resource.is_array();
+            let envelope = Map::from_iter([("spec".to_string(), spec_from_hal(resource)?)]);
+            resources.insert_raw(&name, Value::Object(envelope), list);
+        }
+        spec.insert("embedded".to_string(), serde_json::to_value(resources)?);
+    }
+    let content = match (document.len(), document.remove("content")) {
+        (1, Some(content)) => content,
+        (_, Some(content)) => {
+            document.insert("content".to_string(), content);
+            Value::Object(document)
+        }
+        (_, None) => Value::Object(document),
+    };
+    spec.insert("content".to_string(), content);
+    Ok(Value::Object(spec))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Content, Hateoas, HateoasList, RelLink, Representation, ResourceList};
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Bullet {
+        caliber: u32,
+        material: String,
+    }
+
+    impl crate::HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    fn bullet() -> Hateoas<Bullet> {
+        let mut author = Hateoas::OK(Some("Rick".to_string()), None);
+        author
+            .spec_mut()
+            .rel()
+            .add(RelLink::GET("self", "/authors/rick"));
+
+        let mut content = Content::new(Bullet {
+            caliber: 9,
+            material: "rubber".to_string(),
+        });
+        content.rel().add(RelLink::GET("self", "/bullets/9"));
+        let mut search = RelLink::GET("search", "/bullets{?caliber}");
+        search
+            .headers_mut()
+            .set("Content-Type", "application/hal+json");
+        *search.name_mut() = Some("by-caliber".to_string());
+        content.rel().add(search);
+        content.embed("author", author).unwrap();
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn hal_encoding() {
+        let encoded = Representation::Hal.encode(&bullet()).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "caliber": 9,
+                "material": "rubber",
+                "_links": {
+                    "self": {"href": "/bullets/9"},
+                    "search": {
+                        "href": "/bullets{?caliber}",
+                        "templated": true,
+                        "type": "application/hal+json",
+                        "name": "by-caliber"
+                    }
+                },
+                "_embedded": {
+                    "author": {
+                        "content": "Rick",
+                        "_links": {"self": {"href": "/authors/rick"}}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn hal_round_trip() {
+        let encoded = Representation::Hal.encode(&bullet()).unwrap();
+        let decoded: Hateoas<Bullet> = Representation::Hal.decode(&encoded).unwrap();
+        let spec = decoded.spec().unwrap();
+
+        assert_eq!(spec.content(), bullet().spec().unwrap().content());
+        assert_eq!(spec.clone().rel(), bullet().spec_mut().rel());
+        let author: Hateoas<String> = spec.get_embedded("author").unwrap().unwrap();
+        assert_eq!(author.spec().unwrap().content(), &Some("Rick".to_string()));
+    }
+
+    #[test]
+    fn hal_list_round_trip() {
+        let mut item = Content::new(9u16);
+        item.rel().add(RelLink::GET("self", "/numbers/9"));
+        let mut list = ResourceList::new(vec![item]);
+        list.paginate(1, 10, 0);
+        let list: HateoasList<u16> = HateoasList::OK(Some(list), None);
+
+        let encoded = Representation::Hal.encode(&list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["total"], 1);
+        assert_eq!(value["_embedded"]["items"][0]["content"], 9);
+        assert_eq!(
+            value["_embedded"]["items"][0]["_links"]["self"]["href"],
+            "/numbers/9"
+        );
+
+        let decoded: HateoasList<u16> = Representation::Hal.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), list.spec());
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct WishList {
+        items: Vec<String>,
+    }
+
+    impl crate::HateoasResource for WishList {
+        const KIND: &'static str = "WishList";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "wish_lists";
+    }
+
+    #[test]
+    fn hal_kind_ending_in_list_is_not_a_list() {
+        let wish_list = Hateoas::OK(
+            Some(WishList {
+                items: vec!["rubber".to_string()],
+            }),
+            None,
+        );
+
+        let encoded = Representation::Hal.encode(&wish_list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value, json!({"items": ["rubber"]}));
+
+        let mut content = Content::new(9u16);
+        content.embed("wishes", wish_list).unwrap();
+        let encoded = Representation::Hal
+            .encode(&Hateoas::new(Some(content), None, None))
+            .unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["_embedded"]["wishes"], json!({"items": ["rubber"]}));
+    }
+
+    #[test]
+    fn hal_embedded_empty_list() {
+        let mut content = Content::new(9u16);
+        content
+            .embed(
+                "wishes",
+                Hateoas::OK(Some(WishList { items: vec![] }), None),
+            )
+            .unwrap();
+        content
+            .embed(
+                "shells",
+                HateoasList::<u16>::OK(Some(ResourceList::new(vec![])), None),
+            )
+            .unwrap();
+
+        let encoded = Representation::Hal
+            .encode(&Hateoas::new(Some(content), None, None))
+            .unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["_embedded"]["wishes"], json!({"items": []}));
+        assert_eq!(
+            value["_embedded"]["shells"],
+            json!({"_embedded": {"items": []}})
+        );
+    }
+
+    #[test]
+    fn hal_embedded_array_is_read_as_list() {
+        let document = br#"{"caliber": 9, "material": "lead", "_embedded": {"shells": [{"content": 1}, {"content": 2}]}}"#;
+
+        let decoded: Hateoas<Bullet> = Representation::Hal.decode(document).unwrap();
+        let shells: HateoasList<u16> = decoded
+            .spec()
+            .unwrap()
+            .get_embedded("shells")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            shells
+                .spec()
+                .unwrap()
+                .content()
+                .as_ref()
+                .unwrap()
+                .items()
+                .len(),
+            2
+        );
+    }
+}
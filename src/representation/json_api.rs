@@ -0,0 +1,597 @@
+//! JSON:API
+//!
+//! The resource is written as the primary `data` with the kind as `type`, the metadata id as `id`,
+//! the content as `attributes` and the rel links as `links`. The items of a
+//! [`crate::HateoasList`] are written as an array of resources, with the pagination fields in the
+//! top level `meta`. Responses with a 4xx/5xx status are written as an `errors` document.
+//!
+//! As JSON:API does not allow `id` in the attributes, an `id` field of the content is written as
+//! the resource id, and read back into the content.
+use crate::header::HeaderKey;
+use crate::representation::RepresentationError;
+use crate::{
+    Content, Hateoas, HateoasResource, HttpMethod, RelLink, RelLinkCollection, Status, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const JSON_API: &str = "JSON:API";
+
+pub(crate) fn to_json_api<T>(hateoas: &Hateoas<T>) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    if let Some(errors) = hateoas.status().and_then(errors_to_json_api) {
+        return Ok(Value::Object(Map::from_iter([(
+            "errors".to_string(),
+            errors,
+        )])));
+    }
+
+    let envelope = serde_json::to_value(hateoas)?;
+    let spec = &envelope["spec"];
+    let mut document = Map::new();
+
+    let list = hateoas
+        .kind()
+        .strip_suffix("List")
+        .zip(spec["content"].as_object())
+        .filter(|(_, content)| content.get("items").map(Value::is_array) == Some(true));
+    if let Some((item_kind, content)) = list {
+        let mut meta = content.clone();
+        let items = match meta.remove("items") {
+            Some(Value::Array(items)) => items,
+            _ => vec![],
+        };
+        let items = items
+            .iter()
+            .map(|t| resource_to_json_api(item_kind, None, t))
+            .collect::<Result<Vec<Value>, RepresentationError>>()?;
+        document.insert("data".to_string(), Value::Array(items));
+        let links = links_to_json_api(&spec["rel"])?;
+        if !links.is_empty() {
+            document.insert("links".to_string(), Value::Object(links));
+        }
+        if !meta.is_empty() {
+            document.insert("meta".to_string(), Value::Object(meta));
+        }
+    } else if spec.is_null() {
+        document.insert("data".to_string(), Value::Null);
+    } else {
+        let id = hateoas.metadata().and_then(|t| t.id.clone());
+        document.insert(
+            "data".to_string(),
+            resource_to_json_api(hateoas.kind(), id, spec)?,
+        );
+    }
+
+    Ok(Value::Object(document))
+}
+
+pub(crate) fn from_json_api<T>(value: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    let mut document = match value {
+        Value::Object(document) => document,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_API,
+                "expected an object".to_string(),
+            ))
+        }
+    };
+
+    if let Some(errors) = document.remove("errors") {
+        return Ok(Hateoas::new(None, None, Some(status_from_errors(errors)?)));
+    }
+
+    match document.remove("data") {
+        Some(Value::Null) => Ok(Hateoas::new(None, None, None)),
+        Some(Value::Object(mut resource)) => {
+            check_type(&resource, &T::kind())?;
+            let id = resource_id(&resource)?;
+            let links = resource
+                .remove("links")
+                .map(links_from_json_api)
+                .transpose()?;
+            let mut content = match resource.remove("attributes") {
+                None => Content::default(),
+                Some(attributes) => {
+                    let attributes = attributes_from_json_api(attributes)?;
+                    Content::new(deserialize_candidates(
+                        candidates(&attributes, id.as_deref()).to_vec(),
+                    )?)
+                }
+            };
+            if let Some(links) = links {
+                *content.rel() = links;
+            }
+
+            let mut hateoas = Hateoas::new(Some(content), None, None);
+            if id.is_some() {
+                hateoas.metadata_mut().id = id;
+            }
+            Ok(hateoas)
+        }
+        Some(Value::Array(resources)) => {
+            let kind = T::kind();
+            let item_kind = kind.strip_suffix("List").ok_or_else(|| {
+                RepresentationError::InvalidDocument(
+                    JSON_API,
+                    format!("data is an array but {} is not a list", kind),
+                )
+            })?;
+            let mut items = vec![];
+            for resource in resources {
+                let mut resource = match resource {
+                    Value::Object(resource) => resource,
+                    _ => {
+                        return Err(RepresentationError::InvalidDocument(
+                            JSON_API,
+                            "resources must be objects".to_string(),
+                        ))
+                    }
+                };
+                check_type(&resource, item_kind)?;
+                let id = resource_id(&resource)?;
+                let links = match resource.remove("links") {
+                    Some(links) => Some(serde_json::to_value(links_from_json_api(links)?)?),
+                    None => None,
+                };
+                let attributes = resource
+                    .remove("attributes")
+                    .map(attributes_from_json_api)
+                    .transpose()?
+                    .unwrap_or_default();
+                items.push((candidates(&attributes, id.as_deref()), links));
+            }
+
+            let meta = match document.remove("meta") {
+                Some(Value::Object(meta)) => meta,
+                _ => Map::new(),
+            };
+            let lists = (0..4)
+                .map(|i| {
+                    let items = items
+                        .iter()
+                        .map(|(candidates, links)| {
+                            let mut item =
+                                Map::from_iter([("content".to_string(), candidates[i].clone())]);
+                            if let Some(links) = links {
+                                item.insert("rel".to_string(), links.clone());
+                            }
+                            Value::Object(item)
+                        })
+                        .collect();
+                    let mut list = meta.clone();
+                    list.insert("items".to_string(), Value::Array(items));
+                    Value::Object(list)
+                })
+                .collect();
+            let mut content = Content::new(deserialize_candidates(lists)?);
+            if let Some(links) = document.remove("links") {
+                *content.rel() = links_from_json_api(links)?;
+            }
+            Ok(Hateoas::new(Some(content), None, None))
+        }
+        _ => Err(RepresentationError::InvalidDocument(
+            JSON_API,
+            "the document must contain data or errors".to_string(),
+        )),
+    }
+}
+
+fn resource_to_json_api(
+    kind: &str,
+    id: Option<String>,
+    spec: &Value,
+) -> Result<Value, RepresentationError> {
+    let mut resource = Map::from_iter([("type".to_string(), Value::from(kind))]);
+    let attributes = match spec.get("content") {
+        Some(Value::Object(content)) => Some(content.clone()),
+        None | Some(Value::Null) => None,
+        Some(content) => Some(Map::from_iter([("content".to_string(), content.clone())])),
+    };
+    // The id of the content is used as the resource id when the metadata has none, otherwise it
+    // is kept as an attribute.
+    let attributes = attributes.map(|mut attributes| {
+        let id = id.or_else(|| {
+            attributes.remove("id").map(|t| match t {
+                Value::String(id) => id,
+                id => id.to_string(),
+            })
+        });
+        if let Some(id) = id {
+            resource.insert("id".to_string(), Value::from(id));
+        }
+        attributes
+    });
+    if let Some(attributes) = attributes {
+        resource.insert("attributes".to_string(), Value::Object(attributes));
+    }
+
+    let links = links_to_json_api(&spec["rel"])?;
+    if !links.is_empty() {
+        resource.insert("links".to_string(), Value::Object(links));
+    }
+    Ok(Value::Object(resource))
+}
+
+/// Links are written as the plain href, links that are not `GET` or carries a content type or a
+/// name are written as a link object with the method in the `meta`. A JSON:API link can not hold
+/// more than one link, so duplicated rels are rejected.
+fn links_to_json_api(rel: &Value) -> Result<Map<String, Value>, RepresentationError> {
+    if rel.is_null() {
+        return Ok(Map::new());
+    }
+    let links: RelLinkCollection = serde_json::from_value(rel.clone())?;
+    let links: Vec<RelLink> = links.into();
+
+    let mut json_api_links = Map::new();
+    for link in links {
+        let content_type = link.headers().get_first(&HeaderKey::ContentType);
+        let json_api_link = if link.method() == &HttpMethod::Get
+            && content_type.is_none()
+            && link.name().is_none()
+        {
+            Value::from(link.href())
+        } else {
+            let mut json_api_link =
+                Map::from_iter([("href".to_string(), Value::from(link.href()))]);
+            if let Some(name) = link.name() {
+                json_api_link.insert("title".to_string(), Value::from(name.as_str()));
+            }
+            if let Some(content_type) = content_type {
+                json_api_link.insert("type".to_string(), Value::from(content_type.as_str()));
+            }
+            if link.method() != &HttpMethod::Get {
+                let meta =
+                    Map::from_iter([("method".to_string(), Value::from(link.method().as_str()))]);
+                json_api_link.insert("meta".to_string(), Value::Object(meta));
+            }
+            Value::Object(json_api_link)
+        };
+        if json_api_links
+            .insert(link.rel().to_string(), json_api_link)
+            .is_some()
+        {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_API,
+                format!("more than one link with the rel {}", link.rel()),
+            ));
+        }
+    }
+    Ok(json_api_links)
+}
+
+fn links_from_json_api(links: Value) -> Result<RelLinkCollection, RepresentationError> {
+    let links = match links {
+        Value::Object(links) => links,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_API,
+                "links must be an object".to_string(),
+            ))
+        }
+    };
+
+    let mut collection = vec![];
+    for (rel, link) in links {
+        let href = match &link {
+            Value::String(href) => href.as_str(),
+            Value::Object(_) => link["href"].as_str().ok_or_else(|| {
+                RepresentationError::InvalidDocument(
+                    JSON_API,
+                    format!("link {} is missing href", rel),
+                )
+            })?,
+            // JSON:API allows links to be null when they do not exist
+            Value::Null => continue,
+            _ => {
+                return Err(RepresentationError::InvalidDocument(
+                    JSON_API,
+                    format!("link {} must be a string or a link object", rel),
+                ))
+            }
+        };
+        let mut rel_link = RelLink::GET(&rel, href);
+        if let Some(method) = link["meta"]["method"].as_str() {
+            *rel_link.method_mut() = HttpMethod::from_name(method).ok_or_else(|| {
+                RepresentationError::InvalidDocument(
+                    JSON_API,
+                    format!("link {} has unknown method {}", rel, method),
+                )
+            })?;
+        }
+        if let Some(content_type) = link["type"].as_str() {
+            rel_link.headers_mut().set("Content-Type", content_type);
+        }
+        *rel_link.name_mut() = link["title"].as_str().map(|t| t.to_string());
+        collection.push(rel_link);
+    }
+    Ok(RelLinkCollection::new(collection))
+}
+
+fn errors_to_json_api(status: &Status) -> Option<Value> {
+    let status_code = status.http_status_code().as_ref().map(u16::from)?;
+    if status_code < 400 {
+        return None;
+    }
+
+    let mut error = Map::from_iter([("status".to_string(), Value::from(status_code.to_string()))]);
+    if let Some(code) = status.code() {
+        error.insert("code".to_string(), Value::from(code.to_string()));
+    }
+    if let Some(message) = status.message() {
+        error.insert("detail".to_string(), Value::from(message.as_str()));
+    }
+    if let Some(session) = status.session() {
+        let meta = Map::from_iter([("session".to_string(), Value::from(session.to_string()))]);
+        error.insert("meta".to_string(), Value::Object(meta));
+    }
+    Some(Value::Array(vec![Value::Object(error)]))
+}
+
+/// Reading the status from the first error of the `errors` array.
+fn status_from_errors(errors: Value) -> Result<Status, RepresentationError> {
+    let error = match errors {
+        Value::Array(errors) if !errors.is_empty() => errors[0].clone(),
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_API,
+                "errors must be a non-empty array".to_string(),
+            ))
+        }
+    };
+
+    let status_code = error["status"]
+        .as_str()
+        .and_then(|t| t.parse::<u16>().ok())
+        .map(StatusCode::from);
+    let code = error["code"].as_str().and_then(|t| t.parse::<u32>().ok());
+    let message = error["detail"].as_str().or_else(|| error["title"].as_str());
+    let session = error["meta"]["session"]
+        .as_str()
+        .and_then(|t| uuid::Uuid::parse_str(t).ok());
+    Ok(Status::new(message, code, status_code, session, None))
+}
+
+fn check_type(resource: &Map<String, Value>, kind: &str) -> Result<(), RepresentationError> {
+    match resource.get("type").and_then(Value::as_str) {
+        Some(t) if t != kind => Err(RepresentationError::InvalidDocument(
+            JSON_API,
+            format!("resource is of type {} but {} was expected", t, kind),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn resource_id(resource: &Map<String, Value>) -> Result<Option<String>, RepresentationError> {
+    match resource.get("id") {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(id)) => Ok(Some(id.to_string())),
+        Some(_) => Err(RepresentationError::InvalidDocument(
+            JSON_API,
+            "id must be a string".to_string(),
+        )),
+    }
+}
+
+fn attributes_from_json_api(attributes: Value) -> Result<Map<String, Value>, RepresentationError> {
+    match attributes {
+        Value::Object(attributes) => Ok(attributes),
+        _ => Err(RepresentationError::InvalidDocument(
+            JSON_API,
+            "attributes must be an object".to_string(),
+        )),
+    }
+}
+
+/// The possible content of a resource, as the type of the content is not known while reading the
+/// document. The id is tried as a string, as a number and left out, and lastly content that was
+/// written as `{"content": ..}` is unwrapped.
+fn candidates(attributes: &Map<String, Value>, id: Option<&str>) -> [Value; 4] {
+    let with_id = |id: Option<Value>| {
+        let mut attributes = attributes.clone();
+        if let Some(id) = id {
+            attributes.entry("id").or_insert(id);
+        }
+        Value::Object(attributes)
+    };
+    let unwrapped = match (attributes.len(), attributes.get("content")) {
+        (1, Some(content)) => content.clone(),
+        _ => Value::Object(attributes.clone()),
+    };
+
+    [
+        with_id(id.map(Value::from)),
+        with_id(id.and_then(|t| {
+            serde_json::from_str::<serde_json::Number>(t)
+                .ok()
+                .map(Value::from)
+        })),
+        with_id(None),
+        unwrapped,
+    ]
+}
+
+fn deserialize_candidates<U: DeserializeOwned>(
+    candidates: Vec<Value>,
+) -> Result<U, RepresentationError> {
+    let mut error = None;
+    for candidate in candidates {
+        match serde_json::from_value(candidate) {
+            Ok(content) => return Ok(content),
+            Err(e) => {
+                error.get_or_insert(e);
+            }
+        }
+    }
+    Err(error.map(RepresentationError::from).unwrap_or_else(|| {
+        RepresentationError::InvalidDocument(JSON_API, "no content".to_string())
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Content, Hateoas, HateoasList, Metadata, RelLink, RelLinkCollection, Representation,
+        ResourceList, StatusCode,
+    };
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Bullet {
+        id: u32,
+        caliber: u32,
+        material: String,
+    }
+
+    impl crate::HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    fn bullet() -> Hateoas<Bullet> {
+        let mut content = Content::new(Bullet {
+            id: 9,
+            caliber: 9,
+            material: "rubber".to_string(),
+        });
+        content.rel().add(RelLink::GET("self", "/bullets/9"));
+        content.rel().add(RelLink::DELETE("delete", "/bullets/9"));
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn json_api_encoding() {
+        let encoded = Representation::JsonApi.encode(&bullet()).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "data": {
+                    "type": "Bullet",
+                    "id": "9",
+                    "attributes": {"caliber": 9, "material": "rubber"},
+                    "links": {
+                        "self": "/bullets/9",
+                        "delete": {"href": "/bullets/9", "meta": {"method": "DELETE"}}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn json_api_round_trip() {
+        let encoded = Representation::JsonApi.encode(&bullet()).unwrap();
+        let decoded: Hateoas<Bullet> = Representation::JsonApi.decode(&encoded).unwrap();
+
+        assert_eq!(decoded.spec(), bullet().spec());
+        assert_eq!(decoded.metadata().unwrap().id, Some("9".to_string()));
+    }
+
+    #[test]
+    fn json_api_metadata_id() {
+        let metadata = Metadata::new(Some("rick".to_string()), None, None, None, None);
+        let author = Hateoas::new(Some(Content::new("Rick".to_string())), Some(metadata), None);
+
+        let encoded = Representation::JsonApi.encode(&author).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({"data": {"type": "String", "id": "rick", "attributes": {"content": "Rick"}}})
+        );
+
+        let decoded: Hateoas<String> = Representation::JsonApi.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), author.spec());
+        assert_eq!(decoded.metadata(), author.metadata());
+    }
+
+    #[test]
+    fn json_api_metadata_id_keeps_content_id() {
+        let mut bullet = bullet();
+        *bullet.metadata_mut() =
+            Metadata::new(Some("rubber-9".to_string()), None, None, None, None);
+
+        let encoded = Representation::JsonApi.encode(&bullet).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["data"]["id"], "rubber-9");
+        assert_eq!(value["data"]["attributes"]["id"], 9);
+
+        let decoded: Hateoas<Bullet> = Representation::JsonApi.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), bullet.spec());
+        assert_eq!(decoded.metadata().unwrap().id, Some("rubber-9".to_string()));
+    }
+
+    #[test]
+    fn json_api_duplicate_rel() {
+        let mut bullet = bullet();
+        *bullet.spec_mut().rel() = RelLinkCollection::new(vec![
+            RelLink::GET("shell", "/shells/1"),
+            RelLink::GET("shell", "/shells/2"),
+        ]);
+
+        assert!(matches!(
+            Representation::JsonApi.encode(&bullet),
+            Err(crate::RepresentationError::InvalidDocument(_, _))
+        ));
+    }
+
+    #[test]
+    fn json_api_list_round_trip() {
+        let mut item = Content::new(9u16);
+        item.rel().add(RelLink::GET("self", "/numbers/9"));
+        let mut list = ResourceList::new(vec![item]);
+        list.paginate(1, 10, 0);
+        let mut list: HateoasList<u16> = HateoasList::OK(Some(list), None);
+        list.spec_mut()
+            .rel()
+            .add(RelLink::GET("next", "/numbers?offset=10"));
+
+        let encoded = Representation::JsonApi.encode(&list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["data"][0]["type"], "U16");
+        assert_eq!(value["data"][0]["attributes"]["content"], 9);
+        assert_eq!(value["links"]["next"], "/numbers?offset=10");
+        assert_eq!(
+            value["meta"],
+            json!({"total": 1, "pageSize": 10, "remainingItemCount": 0})
+        );
+
+        let decoded: HateoasList<u16> = Representation::JsonApi.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), list.spec());
+    }
+
+    #[test]
+    fn json_api_errors() {
+        let error: Hateoas<Bullet> =
+            Hateoas::NOT_FOUND(None, Some("Bullet 9 not found".to_string()));
+
+        let encoded = Representation::JsonApi.encode(&error).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({"errors": [{"status": "404", "detail": "Bullet 9 not found"}]})
+        );
+
+        let decoded: Hateoas<Bullet> = Representation::JsonApi.decode(&encoded).unwrap();
+        assert!(decoded.spec().is_none());
+        let status = decoded.status().unwrap();
+        assert_eq!(status.http_status_code(), &Some(StatusCode::NotFound));
+        assert_eq!(status.message(), &Some("Bullet 9 not found".to_string()));
+    }
+
+    #[test]
+    fn json_api_type_mismatch() {
+        let document = br#"{"data": {"type": "Shell", "attributes": {"caliber": 9}}}"#;
+
+        assert!(Representation::JsonApi.decode::<Bullet>(document).is_err());
+    }
+}
@@ -0,0 +1,566 @@
+//! JSON-LD with the Hydra vocabulary
+//!
+//! The `self` link is written as the `@id` and the kind as the `@type`, the content fields are
+//! written as the properties of the node. All other rel links are written as a property named by
+//! the rel, referencing the target by `@id` with a `hydra:operation` for the method of the link.
+//! The operations on the resource itself are also listed in the top level `hydra:operation`.
+//!
+//! The items of a [`crate::HateoasList`] are written as the `hydra:member` of a `hydra:Collection`,
+//! with the pagination links in the `hydra:view`. Responses with a 4xx/5xx status are written as a
+//! `hydra:Error`.
+//!
+//! The `@context` references the context document of the group version, see
+//! [`crate::ResourceRegistry::json_ld_context`]. Embedded resources are not written.
+use crate::representation::RepresentationError;
+use crate::{
+    Content, Hateoas, HateoasResource, HttpMethod, RelLink, RelLinkCollection, Status, StatusCode,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const JSON_LD: &str = "JSON-LD";
+const HYDRA: &str = "http://www.w3.org/ns/hydra/core#";
+const SELF: &str = "self";
+/// The rel links written to the `hydra:view` of a collection
+const VIEW: [(&str, &str); 4] = [
+    ("first", "hydra:first"),
+    ("last", "hydra:last"),
+    ("next", "hydra:next"),
+    ("prev", "hydra:previous"),
+];
+
+/// The content properties and the rel links of a node
+type Node = (Option<Map<String, Value>>, Vec<RelLink>);
+
+/// The url of the context document of a group version, the document is served by the discovery
+/// routes of the framework integrations.
+pub(crate) fn context_url(group: &str, version: &str) -> String {
+    format!("/apis/{}/{}/context", group, version)
+}
+
+/// The context document for the kinds of a group version.
+pub(crate) fn context<'a>(
+    group: &str,
+    version: &str,
+    kinds: impl Iterator<Item = &'a str>,
+) -> Value {
+    let vocab = format!("{}#", context_url(group, version));
+    let mut context = Map::from_iter([
+        ("hydra".to_string(), Value::from(HYDRA)),
+        ("@vocab".to_string(), Value::from(vocab.as_str())),
+    ]);
+    for kind in kinds {
+        let term = Map::from_iter([("@id".to_string(), Value::from(format!("{}{}", vocab, kind)))]);
+        context.insert(kind.to_string(), Value::Object(term));
+    }
+    Value::Object(Map::from_iter([(
+        "@context".to_string(),
+        Value::Object(context),
+    )]))
+}
+
+pub(crate) fn to_json_ld<T>(hateoas: &Hateoas<T>) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    let mut document = Map::from_iter([(
+        "@context".to_string(),
+        Value::from(context_url(T::GROUP, T::VERSION)),
+    )]);
+    if let Some(error) = hateoas.status().and_then(error_to_json_ld) {
+        document.extend(error);
+        return Ok(Value::Object(document));
+    }
+
+    let envelope = serde_json::to_value(hateoas)?;
+    let spec = &envelope["spec"];
+    let list = hateoas
+        .kind()
+        .strip_suffix("List")
+        .zip(spec["content"].as_object())
+        .filter(|(_, content)| content.get("items").map(Value::is_array) == Some(true));
+
+    match list {
+        Some((item_kind, content)) => {
+            let mut links = links_from_rel(&spec["rel"])?;
+            let href = self_href(&links);
+            if let Some(href) = &href {
+                document.insert("@id".to_string(), Value::from(href.as_str()));
+            }
+            document.insert("@type".to_string(), Value::from("hydra:Collection"));
+
+            let mut view = Map::new();
+            for (rel, property) in VIEW {
+                if let Some(index) = links.iter().position(|t| t.rel() == rel) {
+                    view.insert(
+                        property.to_string(),
+                        Value::from(links.remove(index).href()),
+                    );
+                }
+            }
+            node_links(&mut document, links, href.as_deref())?;
+            if !view.is_empty() {
+                if let Some(href) = href {
+                    view.insert("@id".to_string(), Value::from(href));
+                }
+                view.insert(
+                    "@type".to_string(),
+                    Value::from("hydra:PartialCollectionView"),
+                );
+                document.insert("hydra:view".to_string(), Value::Object(view));
+            }
+
+            let mut content = content.clone();
+            let items = match content.remove("items") {
+                Some(Value::Array(items)) => items,
+                _ => vec![],
+            };
+            if let Some(total) = content.remove("total") {
+                document.insert("hydra:totalItems".to_string(), total);
+            }
+            document.extend(content);
+            let members = items
+                .iter()
+                .map(|t| node_to_json_ld(item_kind, t).map(Value::Object))
+                .collect::<Result<Vec<Value>, RepresentationError>>()?;
+            document.insert("hydra:member".to_string(), Value::Array(members));
+        }
+        None => document.extend(node_to_json_ld(hateoas.kind(), spec)?),
+    }
+    Ok(Value::Object(document))
+}
+
+pub(crate) fn from_json_ld<T>(value: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    let mut document = match value {
+        Value::Object(document) => document,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_LD,
+                "expected an object".to_string(),
+            ))
+        }
+    };
+    document.remove("@context");
+
+    let kind = T::kind();
+    match document.get("@type").and_then(Value::as_str) {
+        Some("hydra:Error") => Ok(Hateoas::new(
+            None,
+            None,
+            Some(status_from_json_ld(document)),
+        )),
+        Some("hydra:Collection") => {
+            let item_kind = kind.strip_suffix("List").ok_or_else(|| {
+                RepresentationError::InvalidDocument(
+                    JSON_LD,
+                    format!("document is a collection but {} is not a list", kind),
+                )
+            })?;
+            document.remove("@type");
+            let mut rel = vec![];
+            if let Some(href) = document.remove("@id").as_ref().and_then(Value::as_str) {
+                rel.push(RelLink::GET(SELF, href));
+            }
+            if let Some(Value::Object(view)) = document.remove("hydra:view") {
+                for (rel_name, property) in VIEW {
+                    if let Some(href) = view.get(property).and_then(Value::as_str) {
+                        rel.push(RelLink::GET(rel_name, href));
+                    }
+                }
+            }
+            if let Some(total) = document.remove("hydra:totalItems") {
+                document.insert("total".to_string(), total);
+            }
+            let members = match document.remove("hydra:member") {
+                Some(Value::Array(members)) => members,
+                None => vec![],
+                Some(_) => {
+                    return Err(RepresentationError::InvalidDocument(
+                        JSON_LD,
+                        "hydra:member must be an array".to_string(),
+                    ))
+                }
+            };
+            let items = members
+                .into_iter()
+                .map(|member| match member {
+                    Value::Object(member) => {
+                        let (content, links) = node_from_json_ld(member, item_kind)?;
+                        let mut item = Map::new();
+                        if let Some(content) = content {
+                            item.insert("content".to_string(), unwrap_content(content));
+                        }
+                        if !links.is_empty() {
+                            item.insert(
+                                "rel".to_string(),
+                                serde_json::to_value(RelLinkCollection::new(links))?,
+                            );
+                        }
+                        Ok(Value::Object(item))
+                    }
+                    _ => Err(RepresentationError::InvalidDocument(
+                        JSON_LD,
+                        "members must be objects".to_string(),
+                    )),
+                })
+                .collect::<Result<Vec<Value>, RepresentationError>>()?;
+
+            let (list, links) = node_from_json_ld(document, &kind)?;
+            let mut list = list.unwrap_or_default();
+            list.insert("items".to_string(), Value::Array(items));
+            rel.extend(links);
+
+            let mut content = Content::new(serde_json::from_value(Value::Object(list))?);
+            *content.rel() = RelLinkCollection::new(rel);
+            Ok(Hateoas::new(Some(content), None, None))
+        }
+        _ => {
+            let (content, links) = node_from_json_ld(document, &kind)?;
+            let mut content = match content {
+                Some(content) => Content::new(deserialize_content(content)?),
+                None => Content::default(),
+            };
+            *content.rel() = RelLinkCollection::new(links);
+            Ok(Hateoas::new(Some(content), None, None))
+        }
+    }
+}
+
+fn node_to_json_ld(kind: &str, spec: &Value) -> Result<Map<String, Value>, RepresentationError> {
+    let mut node = Map::new();
+    let links = links_from_rel(&spec["rel"])?;
+    let href = self_href(&links);
+    if let Some(href) = &href {
+        node.insert("@id".to_string(), Value::from(href.as_str()));
+    }
+    node.insert("@type".to_string(), Value::from(kind));
+    match spec.get("content") {
+        Some(Value::Object(content)) => node.extend(content.clone()),
+        None | Some(Value::Null) => {}
+        Some(content) => {
+            node.insert("content".to_string(), content.clone());
+        }
+    }
+    node_links(&mut node, links, href.as_deref())?;
+    Ok(node)
+}
+
+/// Writing the links of a node as properties, except the self link written as the `@id`.
+fn node_links(
+    node: &mut Map<String, Value>,
+    links: Vec<RelLink>,
+    href: Option<&str>,
+) -> Result<(), RepresentationError> {
+    let mut operations = vec![];
+    for link in links {
+        if link.rel() == SELF && link.method() == &HttpMethod::Get {
+            continue;
+        }
+        let operation = operation_to_json_ld(&link);
+        if Some(link.href()) == href && link.method() != &HttpMethod::Get {
+            operations.push(operation.clone());
+        }
+        let property = Map::from_iter([
+            ("@id".to_string(), Value::from(link.href())),
+            ("hydra:operation".to_string(), Value::Array(vec![operation])),
+        ]);
+        match node.get_mut(link.rel()) {
+            Some(Value::Array(existing)) => existing.push(Value::Object(property)),
+            Some(existing) => {
+                *existing = Value::Array(vec![existing.take(), Value::Object(property)])
+            }
+            None => {
+                node.insert(link.rel().to_string(), Value::Object(property));
+            }
+        }
+    }
+    if !operations.is_empty() {
+        node.insert("hydra:operation".to_string(), Value::Array(operations));
+    }
+    Ok(())
+}
+
+fn operation_to_json_ld(link: &RelLink) -> Value {
+    let mut operation = Map::from_iter([
+        ("@type".to_string(), Value::from("hydra:Operation")),
+        (
+            "hydra:method".to_string(),
+            Value::from(link.method().as_str()),
+        ),
+    ]);
+    if let Some(name) = link.name() {
+        operation.insert("hydra:title".to_string(), Value::from(name.as_str()));
+    }
+    Value::Object(operation)
+}
+
+fn error_to_json_ld(status: &Status) -> Option<Map<String, Value>> {
+    let status_code = status.http_status_code().as_ref().map(u16::from)?;
+    if status_code < 400 {
+        return None;
+    }
+
+    let mut error = Map::from_iter([
+        ("@type".to_string(), Value::from("hydra:Error")),
+        ("hydra:statusCode".to_string(), Value::from(status_code)),
+    ]);
+    if let Some(message) = status.message() {
+        error.insert(
+            "hydra:description".to_string(),
+            Value::from(message.as_str()),
+        );
+    }
+    Some(error)
+}
+
+fn status_from_json_ld(document: Map<String, Value>) -> Status {
+    let status_code = document
+        .get("hydra:statusCode")
+        .and_then(Value::as_u64)
+        .and_then(|t| u16::try_from(t).ok())
+        .map(StatusCode::from);
+    let message = document
+        .get("hydra:description")
+        .or_else(|| document.get("hydra:title"))
+        .and_then(Value::as_str);
+    Status::new(message, None, status_code, None, None)
+}
+
+/// Reading the content and the rel links of a node, the properties referencing another node are
+/// read as rel links.
+fn node_from_json_ld(
+    mut node: Map<String, Value>,
+    kind: &str,
+) -> Result<Node, RepresentationError> {
+    match node.remove("@type") {
+        Some(Value::String(t)) if t != kind => {
+            return Err(RepresentationError::InvalidDocument(
+                JSON_LD,
+                format!("node is of type {} but {} was expected", t, kind),
+            ))
+        }
+        _ => {}
+    }
+    node.remove("hydra:operation");
+
+    let mut links = vec![];
+    if let Some(href) = node.remove("@id").as_ref().and_then(Value::as_str) {
+        links.push(RelLink::GET(SELF, href));
+    }
+    let rels = node
+        .iter()
+        .filter(|(_, t)| is_reference(t))
+        .map(|(rel, _)| rel.to_string())
+        .collect::<Vec<String>>();
+    for rel in rels {
+        let references = match node.remove(&rel) {
+            Some(Value::Array(references)) => references,
+            Some(reference) => vec![reference],
+            None => vec![],
+        };
+        for reference in references {
+            links.push(link_from_json_ld(&rel, &reference)?);
+        }
+    }
+
+    Ok(((!node.is_empty()).then_some(node), links))
+}
+
+/// References are objects with only an `@id` and the operations, or arrays of these.
+fn is_reference(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => {
+            object.contains_key("@id")
+                && object.keys().all(|t| t == "@id" || t == "hydra:operation")
+        }
+        Value::Array(values) => !values.is_empty() && values.iter().all(is_reference),
+        _ => false,
+    }
+}
+
+fn link_from_json_ld(rel: &str, reference: &Value) -> Result<RelLink, RepresentationError> {
+    let href = reference["@id"].as_str().ok_or_else(|| {
+        RepresentationError::InvalidDocument(JSON_LD, format!("{} must have an @id", rel))
+    })?;
+    let mut link = RelLink::GET(rel, href);
+    let operation = &reference["hydra:operation"][0];
+    if let Some(method) = operation["hydra:method"].as_str() {
+        *link.method_mut() = HttpMethod::from_name(method).ok_or_else(|| {
+            RepresentationError::InvalidDocument(
+                JSON_LD,
+                format!("operation {} has unknown method {}", rel, method),
+            )
+        })?;
+    }
+    *link.name_mut() = operation["hydra:title"].as_str().map(|t| t.to_string());
+    Ok(link)
+}
+
+fn links_from_rel(rel: &Value) -> Result<Vec<RelLink>, RepresentationError> {
+    if rel.is_null() {
+        return Ok(vec![]);
+    }
+    let links: RelLinkCollection = serde_json::from_value(rel.clone())?;
+    Ok(links.into())
+}
+
+fn self_href(links: &[RelLink]) -> Option<String> {
+    links
+        .iter()
+        .find(|t| t.rel() == SELF && t.method() == &HttpMethod::Get)
+        .map(|t| t.href().to_string())
+}
+
+/// Content that is not an object is written as the `content` property.
+fn unwrap_content(mut content: Map<String, Value>) -> Value {
+    match (content.len(), content.remove("content")) {
+        (1, Some(content)) => content,
+        (_, Some(value)) => {
+            content.insert("content".to_string(), value);
+            Value::Object(content)
+        }
+        (_, None) => Value::Object(content),
+    }
+}
+
+fn deserialize_content<U: DeserializeOwned>(
+    content: Map<String, Value>,
+) -> Result<U, RepresentationError> {
+    serde_json::from_value(Value::Object(content.clone())).or_else(|e| {
+        serde_json::from_value(unwrap_content(content)).map_err(|_| RepresentationError::from(e))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Content, Hateoas, HateoasList, RelLink, Representation, ResourceList, StatusCode};
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Bullet {
+        caliber: u32,
+        material: String,
+    }
+
+    impl crate::HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    fn bullet() -> Hateoas<Bullet> {
+        let mut content = Content::new(Bullet {
+            caliber: 9,
+            material: "rubber".to_string(),
+        });
+        content.rel().add(RelLink::GET("self", "/bullets/9"));
+        content.rel().add(RelLink::GET("author", "/authors/rick"));
+        let mut delete = RelLink::DELETE("delete", "/bullets/9");
+        *delete.name_mut() = Some("Delete bullet".to_string());
+        content.rel().add(delete);
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn json_ld_encoding() {
+        let encoded = Representation::JsonLd.encode(&bullet()).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+
+        let delete = json!({
+            "@type": "hydra:Operation",
+            "hydra:method": "DELETE",
+            "hydra:title": "Delete bullet"
+        });
+        assert_eq!(
+            value,
+            json!({
+                "@context": "/apis/ammo.hateoas.io/v1/context",
+                "@id": "/bullets/9",
+                "@type": "Bullet",
+                "caliber": 9,
+                "material": "rubber",
+                "author": {
+                    "@id": "/authors/rick",
+                    "hydra:operation": [{"@type": "hydra:Operation", "hydra:method": "GET"}]
+                },
+                "delete": {"@id": "/bullets/9", "hydra:operation": [delete]},
+                "hydra:operation": [delete]
+            })
+        );
+    }
+
+    #[test]
+    fn json_ld_round_trip() {
+        let encoded = Representation::JsonLd.encode(&bullet()).unwrap();
+        let decoded: Hateoas<Bullet> = Representation::JsonLd.decode(&encoded).unwrap();
+
+        assert_eq!(decoded.spec(), bullet().spec());
+    }
+
+    #[test]
+    fn json_ld_collection() {
+        let mut item = Content::new(9u16);
+        item.rel().add(RelLink::GET("self", "/numbers/9"));
+        let mut list = ResourceList::new(vec![item]);
+        list.paginate(20, 1, 0);
+        let mut list: HateoasList<u16> = HateoasList::OK(Some(list), None);
+        list.spec_mut()
+            .rel()
+            .add(RelLink::GET("self", "/numbers?offset=0"));
+        list.spec_mut()
+            .rel()
+            .add(RelLink::GET("next", "/numbers?offset=1"));
+
+        let encoded = Representation::JsonLd.encode(&list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "@context": "/apis/hateoas.io/0.0.1/context",
+                "@id": "/numbers?offset=0",
+                "@type": "hydra:Collection",
+                "hydra:totalItems": 20,
+                "pageSize": 1,
+                "remainingItemCount": 19,
+                "hydra:member": [{"@id": "/numbers/9", "@type": "U16", "content": 9}],
+                "hydra:view": {
+                    "@id": "/numbers?offset=0",
+                    "@type": "hydra:PartialCollectionView",
+                    "hydra:next": "/numbers?offset=1"
+                }
+            })
+        );
+
+        let decoded: HateoasList<u16> = Representation::JsonLd.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), list.spec());
+    }
+
+    #[test]
+    fn json_ld_error() {
+        let error: Hateoas<Bullet> =
+            Hateoas::NOT_FOUND(None, Some("Bullet 9 not found".to_string()));
+
+        let encoded = Representation::JsonLd.encode(&error).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "@context": "/apis/ammo.hateoas.io/v1/context",
+                "@type": "hydra:Error",
+                "hydra:statusCode": 404,
+                "hydra:description": "Bullet 9 not found"
+            })
+        );
+
+        let decoded: Hateoas<Bullet> = Representation::JsonLd.decode(&encoded).unwrap();
+        assert_eq!(
+            decoded.status().unwrap().http_status_code(),
+            &Some(StatusCode::NotFound)
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use crate::{Hateoas, HateoasResource};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+mod collection_json;
+mod hal;
+mod json_api;
+pub(crate) mod json_ld;
+mod problem;
+mod siren;
+
+pub use problem::ProblemDetails;
+
+/// # Representation
+/// The hypermedia representations a [`Hateoas`] can be rendered as beside the crate's own
+/// `apiVersion/kind/metadata/spec/status` shape. The framework integrations select the
+/// representation from the `Accept` header and read request payloads by the `Content-Type`.
+///
+/// ```
+/// use hateoas::Representation;
+///
+/// assert_eq!(Representation::from_media_type("application/hal+json; charset=utf-8"), Some(Representation::Hal));
+/// assert_eq!(Representation::from_media_type("application/json"), None);
+/// assert_eq!(Representation::Hal.media_type(), "application/hal+json");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Representation {
+    /// Hypertext Application Language, `application/hal+json`
+    Hal,
+    /// JSON:API, `application/vnd.api+json`
+    JsonApi,
+    /// Siren, `application/vnd.siren+json`
+    Siren,
+    /// Collection+JSON, `application/vnd.collection+json`
+    CollectionJson,
+    /// JSON-LD with the Hydra vocabulary, `application/ld+json`
+    JsonLd,
+    /// Problem Details, `application/problem+json`
+    Problem,
+    /// Problem Details as XML, `application/problem+xml`, this can only be encoded
+    ProblemXml,
+}
+
+#[derive(Debug, Display)]
+#[non_exhaustive]
+pub enum RepresentationError {
+    /// The document is not valid for the representation
+    #[display(fmt = "Invalid {} document: {}", _0, _1)]
+    InvalidDocument(&'static str, String),
+
+    #[display(fmt = "Serializer/Deserializer error: {}", _0)]
+    SerializationDeserializationError(serde_json::Error),
+}
+
+impl From<serde_json::Error> for RepresentationError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::SerializationDeserializationError(e)
+    }
+}
+
+impl<T> From<RepresentationError> for Hateoas<T>
+where
+    T: HateoasResource + Clone,
+{
+    fn from(e: RepresentationError) -> Self {
+        Hateoas::UNPROCESSABLE_ENTITY(None, Some(e.to_string()))
+    }
+}
+
+impl Representation {
+    pub const ALL: [Representation; 7] = [
+        Representation::Hal,
+        Representation::JsonApi,
+        Representation::Siren,
+        Representation::CollectionJson,
+        Representation::JsonLd,
+        Representation::Problem,
+        Representation::ProblemXml,
+    ];
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Representation::Hal => "application/hal+json",
+            Representation::JsonApi => "application/vnd.api+json",
+            Representation::Siren => "application/vnd.siren+json",
+            Representation::CollectionJson => "application/vnd.collection+json",
+            Representation::JsonLd => "application/ld+json",
+            Representation::Problem => "application/problem+json",
+            Representation::ProblemXml => "application/problem+xml",
+        }
+    }
+
+    /// ## Finding the representation of a media type
+    /// Parameters of the media type are ignored.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or(media_type).trim();
+        Self::ALL
+            .into_iter()
+            .find(|t| t.media_type().eq_ignore_ascii_case(essence))
+    }
+
+    /// ## Encoding into the representation
+    pub fn encode<T>(&self, hateoas: &Hateoas<T>) -> Result<Vec<u8>, RepresentationError>
+    where
+        T: HateoasResource + Clone + Serialize,
+    {
+        self.encode_with_type_template(hateoas, None)
+    }
+
+    /// ## Encoding into the representation with a problem type template
+    /// The template the `type` URI of problem details is generated from, see
+    /// [`ProblemDetails::type_uri`](crate::ProblemDetails::type_uri).
+    pub fn encode_with_type_template<T>(
+        &self,
+        hateoas: &Hateoas<T>,
+        type_template: Option<&str>,
+    ) -> Result<Vec<u8>, RepresentationError>
+    where
+        T: HateoasResource + Clone + Serialize,
+    {
+        let value = match self {
+            Representation::Hal => hal::to_hal(hateoas)?,
+            Representation::JsonApi => json_api::to_json_api(hateoas)?,
+            Representation::Siren => siren::to_siren(hateoas)?,
+            Representation::CollectionJson => collection_json::to_collection_json(hateoas)?,
+            Representation::JsonLd => json_ld::to_json_ld(hateoas)?,
+            Representation::Problem => problem::to_problem(hateoas, type_template)?,
+            Representation::ProblemXml => {
+                return Ok(problem::to_problem_xml(hateoas, type_template)?.into_bytes())
+            }
+        };
+        Ok(serde_json::to_vec(&value)?)
+    }
+
+    /// ## Decoding from the representation
+    pub fn decode<T>(&self, bytes: &[u8]) -> Result<Hateoas<T>, RepresentationError>
+    where
+        T: HateoasResource + Clone + DeserializeOwned,
+    {
+        self.decode_with_type_template(bytes, None)
+    }
+
+    /// ## Decoding from the representation with a problem type template
+    /// The code of problem details is read from the `type` URI when it matches the template.
+    pub fn decode_with_type_template<T>(
+        &self,
+        bytes: &[u8],
+        type_template: Option<&str>,
+    ) -> Result<Hateoas<T>, RepresentationError>
+    where
+        T: HateoasResource + Clone + DeserializeOwned,
+    {
+        if self == &Representation::ProblemXml {
+            return Err(RepresentationError::InvalidDocument(
+                "problem+xml",
+                "decoding is not supported".to_string(),
+            ));
+        }
+        let value = serde_json::from_slice(bytes)?;
+        match self {
+            Representation::Hal => hal::from_hal(value),
+            Representation::JsonApi => json_api::from_json_api(value),
+            Representation::Siren => siren::from_siren(value),
+            Representation::CollectionJson => collection_json::from_collection_json(value),
+            Representation::JsonLd => json_ld::from_json_ld(value),
+            Representation::Problem | Representation::ProblemXml => {
+                problem::from_problem(value, type_template)
+            }
+        }
+    }
+}
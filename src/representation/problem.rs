@@ -0,0 +1,419 @@
+//! Problem Details for HTTP APIs (RFC 9457)
+//!
+//! The status is written as a problem document, the http status code as `status`, the message as
+//! `detail`, the code as the `type` URI and the session as the `instance` URN. The fields of the
+//! content are written as extension members, which allows eg. validation errors to be returned
+//! along with the problem. The rel links and the metadata are not part of a problem document.
+//!
+//! The XML form follows appendix B of RFC 9457, problem+xml documents can only be encoded.
+use crate::representation::RepresentationError;
+use crate::{Content, Hateoas, HateoasResource, Status};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const PROBLEM: &str = "problem";
+const CODE_PLACEHOLDER: &str = "{code}";
+const INSTANCE_PREFIX: &str = "urn:uuid:";
+const MEMBERS: [&str; 5] = ["type", "title", "status", "detail", "instance"];
+
+/// # ProblemDetails
+/// A problem details document as described in
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457), this is the document written for the
+/// `application/problem+json` and `application/problem+xml` representations.
+///
+/// ```
+/// use hateoas::{Hateoas, ProblemDetails};
+///
+/// let response: Hateoas<()> = Hateoas::NOT_FOUND(None, Some("No bullet with id 9".to_string()));
+/// let problem = ProblemDetails::from_hateoas(&response, None).unwrap();
+///
+/// assert_eq!(problem.status(), Some(404));
+/// assert_eq!(problem.title(), Some(&"Not Found".to_string()));
+/// assert_eq!(problem.detail(), Some(&"No bullet with id 9".to_string()));
+/// ```
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub(crate) problem_type: Option<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) status: Option<u16>,
+    pub(crate) detail: Option<String>,
+    pub(crate) instance: Option<String>,
+    #[serde(flatten)]
+    pub(crate) extensions: Map<String, Value>,
+}
+
+impl ProblemDetails {
+    /// ## Generating the type URI
+    /// Generating the `type` URI from the template, `{code}` is replaced with the
+    /// [`Status::code`], if the template has no placeholder the code is appended. The template is
+    /// set on the config of the integrations.
+    ///
+    /// ```
+    /// use hateoas::ProblemDetails;
+    ///
+    /// let template = "https://errors.example.com/{code}";
+    /// assert_eq!(ProblemDetails::type_uri(template, 1042), "https://errors.example.com/1042");
+    /// assert_eq!(ProblemDetails::code_from_type_uri(template, "https://errors.example.com/1042"), Some(1042));
+    /// ```
+    pub fn type_uri(template: &str, code: u32) -> String {
+        if template.contains(CODE_PLACEHOLDER) {
+            template.replace(CODE_PLACEHOLDER, &code.to_string())
+        } else {
+            format!("{}{}", template, code)
+        }
+    }
+
+    /// ## Reading the code from a type URI
+    /// The reverse of [`ProblemDetails::type_uri`], the code is only read if the URI matches the
+    /// template.
+    pub fn code_from_type_uri(template: &str, uri: &str) -> Option<u32> {
+        let (prefix, suffix) = template
+            .split_once(CODE_PLACEHOLDER)
+            .unwrap_or((template, ""));
+        uri.strip_prefix(prefix)
+            .and_then(|t| t.strip_suffix(suffix))
+            .and_then(|t| t.parse().ok())
+    }
+
+    /// ## Creating the problem of a response
+    /// The `title` is the reason phrase of the http status code. Without a type template, or if
+    /// the status has no code, the `type` is left out, which is the same as `about:blank`.
+    pub fn from_hateoas<T>(
+        hateoas: &Hateoas<T>,
+        type_template: Option<&str>,
+    ) -> Result<Self, RepresentationError>
+    where
+        T: HateoasResource + Clone + Serialize,
+    {
+        let status = hateoas.status();
+        let http_status_code = status
+            .and_then(|t| t.http_status_code().as_ref())
+            .map(u16::from);
+        let extensions = match hateoas.spec().and_then(|t| t.content().as_ref()) {
+            Some(content) => match serde_json::to_value(content)? {
+                Value::Object(content) => content
+                    .into_iter()
+                    .filter(|(name, _)| !MEMBERS.contains(&name.as_str()))
+                    .collect(),
+                Value::Null => Map::new(),
+                content => Map::from_iter([("content".to_string(), content)]),
+            },
+            None => Map::new(),
+        };
+
+        Ok(ProblemDetails {
+            problem_type: type_template
+                .zip(status.and_then(|t| *t.code()))
+                .map(|(template, code)| Self::type_uri(template, code)),
+            title: http_status_code
+                .and_then(|t| http::StatusCode::from_u16(t).ok())
+                .and_then(|t| t.canonical_reason())
+                .map(|t| t.to_string()),
+            status: http_status_code,
+            detail: status.and_then(|t| t.message().clone()),
+            instance: status
+                .and_then(|t| *t.session())
+                .map(|t| format!("{}{}", INSTANCE_PREFIX, t)),
+            extensions,
+        })
+    }
+
+    /// ## Converting the problem into a response
+    /// The extension members are read as the content, if they can not be read as `T` the response
+    /// has no content. The code is read from the `type` when it matches the type template.
+    ///
+    /// ```
+    /// use hateoas::{Hateoas, ProblemDetails, Status};
+    ///
+    /// let template = Some("https://errors.example.com/{code}");
+    /// let response: Hateoas<()> = Hateoas::new(None, None, Some(Status::new(Some("Missing caliber"), Some(1042), Some(400), None, None)));
+    /// let problem = ProblemDetails::from_hateoas(&response, template).unwrap();
+    ///
+    /// assert_eq!(problem.problem_type(), Some(&"https://errors.example.com/1042".to_string()));
+    /// assert_eq!(problem.into_hateoas::<()>(template), response);
+    /// ```
+    pub fn into_hateoas<T>(self, type_template: Option<&str>) -> Hateoas<T>
+    where
+        T: HateoasResource + Clone + DeserializeOwned,
+    {
+        let content = if self.extensions.is_empty() {
+            None
+        } else {
+            serde_json::from_value(Value::Object(self.extensions))
+                .ok()
+                .map(Content::new)
+        };
+        let status = Status::new(
+            self.detail.as_deref(),
+            type_template
+                .zip(self.problem_type.as_deref())
+                .and_then(|(template, uri)| Self::code_from_type_uri(template, uri)),
+            self.status,
+            self.instance
+                .as_deref()
+                .and_then(|t| t.strip_prefix(INSTANCE_PREFIX))
+                .and_then(|t| uuid::Uuid::parse_str(t).ok()),
+            None,
+        );
+        Hateoas::new(content, None, Some(status))
+    }
+
+    pub fn problem_type(&self) -> Option<&String> {
+        self.problem_type.as_ref()
+    }
+
+    pub fn problem_type_mut(&mut self) -> &mut Option<String> {
+        &mut self.problem_type
+    }
+
+    pub fn title(&self) -> Option<&String> {
+        self.title.as_ref()
+    }
+
+    pub fn title_mut(&mut self) -> &mut Option<String> {
+        &mut self.title
+    }
+
+    pub fn status(&self) -> Option<u16> {
+        self.status
+    }
+
+    pub fn status_mut(&mut self) -> &mut Option<u16> {
+        &mut self.status
+    }
+
+    pub fn detail(&self) -> Option<&String> {
+        self.detail.as_ref()
+    }
+
+    pub fn detail_mut(&mut self) -> &mut Option<String> {
+        &mut self.detail
+    }
+
+    pub fn instance(&self) -> Option<&String> {
+        self.instance.as_ref()
+    }
+
+    pub fn instance_mut(&mut self) -> &mut Option<String> {
+        &mut self.instance
+    }
+
+    /// ## Getter for the extension members
+    /// The members of the problem beside the ones defined by RFC 9457.
+    pub fn extensions(&self) -> &Map<String, Value> {
+        &self.extensions
+    }
+
+    pub fn extensions_mut(&mut self) -> &mut Map<String, Value> {
+        &mut self.extensions
+    }
+
+    /// ## Adding an extension member
+    /// Adding an extension member, members defined by RFC 9457 can not be added as extensions. If
+    /// the member already existed the old value is returned.
+    ///
+    /// ```
+    /// use hateoas::ProblemDetails;
+    ///
+    /// let mut problem = ProblemDetails::default();
+    /// problem.add_extension("balance", 30.into());
+    ///
+    /// assert_eq!(problem.extensions().get("balance"), Some(&30.into()));
+    /// ```
+    pub fn add_extension(&mut self, name: &str, value: Value) -> Option<Value> {
+        if MEMBERS.contains(&name) {
+            return None;
+        }
+        self.extensions.insert(name.to_string(), value)
+    }
+}
+
+pub(crate) fn to_problem<T>(
+    hateoas: &Hateoas<T>,
+    type_template: Option<&str>,
+) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    Ok(serde_json::to_value(ProblemDetails::from_hateoas(
+        hateoas,
+        type_template,
+    )?)?)
+}
+
+pub(crate) fn from_problem<T>(
+    value: Value,
+    type_template: Option<&str>,
+) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    if !value.is_object() {
+        return Err(RepresentationError::InvalidDocument(
+            PROBLEM,
+            "expected an object".to_string(),
+        ));
+    }
+    let problem: ProblemDetails = serde_json::from_value(value)?;
+    Ok(problem.into_hateoas(type_template))
+}
+
+pub(crate) fn to_problem_xml<T>(
+    hateoas: &Hateoas<T>,
+    type_template: Option<&str>,
+) -> Result<String, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?><problem xmlns="urn:ietf:rfc:7807">"#,
+    );
+    if let Value::Object(problem) = to_problem(hateoas, type_template)? {
+        for (name, value) in problem.iter() {
+            write_xml_element(&mut xml, name, value);
+        }
+    }
+    xml.push_str("</problem>");
+    Ok(xml)
+}
+
+/// Writing a member as an element, arrays are written as `i` elements as described in appendix
+/// B of RFC 9457. Members that are not valid XML names are left out.
+fn write_xml_element(xml: &mut String, name: &str, value: &Value) {
+    if !is_xml_name(name) {
+        return;
+    }
+    xml.push_str(&format!("<{}>", name));
+    match value {
+        Value::Null => {}
+        Value::String(t) => xml.push_str(&escape_xml(t)),
+        Value::Array(items) => items.iter().for_each(|t| write_xml_element(xml, "i", t)),
+        Value::Object(members) => members
+            .iter()
+            .for_each(|(name, t)| write_xml_element(xml, name, t)),
+        t => xml.push_str(&t.to_string()),
+    }
+    xml.push_str(&format!("</{}>", name));
+}
+
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|t| t.is_alphabetic() || t == '_')
+        .unwrap_or(false)
+        && chars.all(|t| t.is_alphanumeric() || matches!(t, '_' | '-' | '.'))
+        && !name.to_ascii_lowercase().starts_with("xml")
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Hateoas, HateoasResource, ProblemDetails, Representation, Status};
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct OutOfCredit {
+        balance: u32,
+        accounts: Vec<String>,
+    }
+
+    impl HateoasResource for OutOfCredit {
+        const KIND: &'static str = "OutOfCredit";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "bank.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "/credit";
+    }
+
+    fn out_of_credit() -> Hateoas<OutOfCredit> {
+        let session = uuid::Uuid::parse_str("2b4f1c5e-7d3a-4e8a-9f0b-6c1d2e3f4a5b").unwrap();
+        Hateoas::new(
+            Some(crate::Content::new(OutOfCredit {
+                balance: 30,
+                accounts: vec!["/account/12345".to_string(), "/account/67890".to_string()],
+            })),
+            None,
+            Some(Status::new(
+                Some("Your current balance is 30, but that costs 50."),
+                None,
+                Some(403),
+                Some(session),
+                None,
+            )),
+        )
+    }
+
+    #[test]
+    fn problem_json_encoding() {
+        let encoded = Representation::Problem.encode(&out_of_credit()).unwrap();
+        let value = serde_json::from_slice::<Value>(&encoded).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "title": "Forbidden",
+                "status": 403,
+                "detail": "Your current balance is 30, but that costs 50.",
+                "instance": "urn:uuid:2b4f1c5e-7d3a-4e8a-9f0b-6c1d2e3f4a5b",
+                "balance": 30,
+                "accounts": ["/account/12345", "/account/67890"]
+            })
+        );
+
+        let decoded: Hateoas<OutOfCredit> = Representation::Problem.decode(&encoded).unwrap();
+        assert_eq!(decoded, out_of_credit());
+    }
+
+    #[test]
+    fn problem_xml_encoding() {
+        let encoded = Representation::ProblemXml.encode(&out_of_credit()).unwrap();
+
+        assert_eq!(
+            String::from_utf8(encoded).unwrap(),
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?><problem xmlns="urn:ietf:rfc:7807">"#,
+                "<title>Forbidden</title><status>403</status>",
+                "<detail>Your current balance is 30, but that costs 50.</detail>",
+                "<instance>urn:uuid:2b4f1c5e-7d3a-4e8a-9f0b-6c1d2e3f4a5b</instance>",
+                "<balance>30</balance>",
+                "<accounts><i>/account/12345</i><i>/account/67890</i></accounts>",
+                "</problem>"
+            )
+        );
+        assert!(Representation::ProblemXml
+            .decode::<OutOfCredit>(b"<problem/>")
+            .is_err());
+    }
+
+    #[test]
+    fn problem_extensions_do_not_replace_members() {
+        let response: Hateoas<HashMap<String, String>> = Hateoas::CONFLICT(
+            Some(HashMap::from([
+                ("status".to_string(), "taken".to_string()),
+                ("owner".to_string(), "rick".to_string()),
+            ])),
+            Some("Name is taken".to_string()),
+        );
+        let mut problem = ProblemDetails::from_hateoas(&response, None).unwrap();
+
+        assert_eq!(problem.status(), Some(409));
+        assert_eq!(problem.add_extension("detail", json!("other")), None);
+        assert_eq!(problem.detail(), Some(&"Name is taken".to_string()));
+        assert_eq!(problem.extensions().get("owner"), Some(&json!("rick")));
+        assert_eq!(problem.extensions().get("status"), None);
+    }
+}
@@ -0,0 +1,453 @@
+//! Siren
+//!
+//! The kind is written as the `class` of the entity and the content as the `properties`. The `GET`
+//! rel links are written to `links`, all other rel links are written as `actions` with the method,
+//! the `Content-Type` header of the link as the `type` and the [`crate::LinkField`]s as the
+//! `fields`. The embedded resources are written as sub-entities with the embedded name as `rel`,
+//! and the items of a [`crate::HateoasList`] as sub-entities with the `item` rel.
+use crate::header::HeaderKey;
+use crate::representation::RepresentationError;
+use crate::{Content, Embedded, Hateoas, HateoasResource, HttpMethod, RelLink, RelLinkCollection};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const SIREN: &str = "Siren";
+const ITEM: &str = "item";
+
+pub(crate) fn to_siren<T>(hateoas: &Hateoas<T>) -> Result<Value, RepresentationError>
+where
+    T: HateoasResource + Clone + Serialize,
+{
+    let envelope = serde_json::to_value(hateoas)?;
+    Ok(Value::Object(spec_to_siren(
+        envelope["kind"].as_str(),
+        &envelope["spec"],
+        T::IS_LIST,
+    )?))
+}
+
+pub(crate) fn from_siren<T>(value: Value) -> Result<Hateoas<T>, RepresentationError>
+where
+    T: HateoasResource + Clone + DeserializeOwned,
+{
+    let entity = match value {
+        Value::Object(entity) => entity,
+        _ => {
+            return Err(RepresentationError::InvalidDocument(
+                SIREN,
+                "expected an object".to_string(),
+            ))
+        }
+    };
+
+    let list = T::IS_LIST;
+    let content =
+        match serde_json::from_value::<Content<T>>(spec_from_siren(entity.clone(), list, false)?) {
+            Ok(content) => content,
+            Err(e) => {
+                serde_json::from_value(spec_from_siren(entity, list, true)?).map_err(|_| e)?
+            }
+        };
+    Ok(Hateoas::new(Some(content), None, None))
+}
+
+fn spec_to_siren(
+    kind: Option<&str>,
+    spec: &Value,
+    list: bool,
+) -> Result<Map<String, Value>, RepresentationError> {
+    let mut entity = Map::new();
+    if let Some(kind) = kind {
+        entity.insert("class".to_string(), Value::from(vec![kind]));
+    }
+    let mut properties = match spec.get("content") {
+        Some(Value::Object(content)) => content.clone(),
+        None | Some(Value::Null) => Map::new(),
+        Some(content) => Map::from_iter([("content".to_string(), content.clone())]),
+    };
+    let mut entities = vec![];
+
+    if list {
+        if let Some(Value::Array(items)) = properties.remove("items") {
+            let item_kind = kind.and_then(|t| t.strip_suffix("List"));
+            for item in items {
+                let mut item = spec_to_siren(item_kind, &item, false)?;
+                item.insert("rel".to_string(), Value::from(vec![ITEM]));
+                entities.push(Value::Object(item));
+            }
+        }
+    }
+    if let Some(resources) = spec.get("embedded").filter(|t| !t.is_null()) {
+        let resources: Embedded = serde_json::from_value(resources.clone())?;
+        for (name, resource, list) in resources.resources() {
+            let mut resource = spec_to_siren(resource["kind"].as_str(), &resource["spec"], list)?;
+            resource.insert("rel".to_string(), Value::from(vec![name]));
+            entities.push(Value::Object(resource));
+        }
+    }
+
+    if !properties.is_empty() {
+        entity.insert("properties".to_string(), Value::Object(properties));
+    }
+    if !entities.is_empty() {
+        entity.insert("entities".to_string(), Value::Array(entities));
+    }
+    if let Some(rel) = spec.get("rel").filter(|t| !t.is_null()) {
+        let links: RelLinkCollection = serde_json::from_value(rel.clone())?;
+        let (links, actions): (Vec<RelLink>, Vec<RelLink>) = Vec::from(links)
+            .into_iter()
+            .partition(|t| t.method() == &HttpMethod::Get);
+        if !links.is_empty() {
+            let links = links.iter().map(link_to_siren).collect();
+            entity.insert("links".to_string(), Value::Array(links));
+        }
+        if !actions.is_empty() {
+            let actions = actions
+                .iter()
+                .map(action_to_siren)
+                .collect::<Result<Vec<Value>, RepresentationError>>()?;
+            entity.insert("actions".to_string(), Value::Array(actions));
+        }
+    }
+
+    Ok(entity)
+}
+
+fn link_to_siren(link: &RelLink) -> Value {
+    let mut siren_link = Map::from_iter([
+        ("rel".to_string(), Value::from(vec![link.rel()])),
+        ("href".to_string(), Value::from(link.href())),
+    ]);
+    if let Some(content_type) = link.headers().get_first(&HeaderKey::ContentType) {
+        siren_link.insert("type".to_string(), Value::from(content_type.as_str()));
+    }
+    if let Some(name) = link.name() {
+        siren_link.insert("title".to_string(), Value::from(name.as_str()));
+    }
+    Value::Object(siren_link)
+}
+
+fn action_to_siren(link: &RelLink) -> Result<Value, RepresentationError> {
+    let mut action = Map::from_iter([
+        ("name".to_string(), Value::from(link.rel())),
+        ("method".to_string(), Value::from(link.method().as_str())),
+        ("href".to_string(), Value::from(link.href())),
+    ]);
+    if let Some(content_type) = link.headers().get_first(&HeaderKey::ContentType) {
+        action.insert("type".to_string(), Value::from(content_type.as_str()));
+    }
+    if let Some(name) = link.name() {
+        action.insert("title".to_string(), Value::from(name.as_str()));
+    }
+    if let Some(fields) = link.fields() {
+        action.insert("fields".to_string(), serde_json::to_value(fields)?);
+    }
+    Ok(Value::Object(action))
+}
+
+/// Converting an entity into the `Content` shape, properties written as `{"content": ..}` are
+/// unwrapped when `unwrap` is set.
+fn spec_from_siren(
+    mut entity: Map<String, Value>,
+    list: bool,
+    unwrap: bool,
+) -> Result<Value, RepresentationError> {
+    let mut spec = Map::new();
+    let mut properties = match entity.remove("properties") {
+        Some(Value::Object(properties)) => Some(properties),
+        None => None,
+        Some(_) => {
+            return Err(RepresentationError::InvalidDocument(
+                SIREN,
+                "properties must be an object".to_string(),
+            ))
+        }
+    };
+
+    let mut rel = vec![];
+    if let Some(links) = entity.remove("links") {
+        rel.extend(links_from_siren(links)?);
+    }
+    if let Some(actions) = entity.remove("actions") {
+        rel.extend(actions_from_siren(actions)?);
+    }
+
+    let mut items = vec![];
+    let mut embedded = Embedded::default();
+    for sub_entity in as_array(entity.remove("entities"), "entities")? {
+        let mut sub_entity = match sub_entity {
+            Value::Object(sub_entity) => sub_entity,
+            _ => {
+                return Err(RepresentationError::InvalidDocument(
+                    SIREN,
+                    "entities must be objects".to_string(),
+                ))
+            }
+        };
+        let rels = strings(sub_entity.remove("rel"), "rel")?;
+        if list && rels.iter().any(|t| t == ITEM) {
+            items.push(spec_from_siren(sub_entity, false, true)?);
+            continue;
+        }
+
+        let kind = sub_entity["class"][0].as_str().map(|t| t.to_string());
+        let sub_list = has_items(&sub_entity);
+        let mut envelope = Map::new();
+        if let Some(kind) = kind {
+            envelope.insert("kind".to_string(), Value::from(kind));
+        }
+        envelope.insert(
+            "spec".to_string(),
+            spec_from_siren(sub_entity, sub_list, true)?,
+        );
+        for name in rels {
+            embedded.insert_raw(&name, Value::Object(envelope.clone()), sub_list);
+        }
+    }
+
+    if list {
+        properties
+            .get_or_insert_with(Map::new)
+            .insert("items".to_string(), Value::Array(items));
+    }
+    if let Some(mut properties) = properties {
+        let content = match (unwrap, properties.len(), properties.remove("content")) {
+            (true, 1, Some(content)) => content,
+            (_, _, Some(content)) => {
+                properties.insert("content".to_string(), content);
+                Value::Object(properties)
+            }
+            (_, _, None) => Value::Object(properties),
+        };
+        spec.insert("content".to_string(), content);
+    }
+    if !rel.is_empty() {
+        spec.insert(
+            "rel".to_string(),
+            serde_json::to_value(RelLinkCollection::new(rel))?,
+        );
+    }
+    if !embedded.is_empty() {
+        spec.insert("embedded".to_string(), serde_json::to_value(embedded)?);
+    }
+    Ok(Value::Object(spec))
+}
+
+fn links_from_siren(links: Value) -> Result<Vec<RelLink>, RepresentationError> {
+    let mut rel_links = vec![];
+    for link in as_array(Some(links), "links")? {
+        let href = link["href"].as_str().ok_or_else(|| {
+            RepresentationError::InvalidDocument(SIREN, "link is missing href".to_string())
+        })?;
+        for rel in strings(link.get("rel").cloned(), "rel")? {
+            let mut rel_link = RelLink::GET(&rel, href);
+            if let Some(content_type) = link["type"].as_str() {
+                rel_link.headers_mut().set("Content-Type", content_type);
+            }
+            *rel_link.name_mut() = link["title"].as_str().map(|t| t.to_string());
+            rel_links.push(rel_link);
+        }
+    }
+    Ok(rel_links)
+}
+
+fn actions_from_siren(actions: Value) -> Result<Vec<RelLink>, RepresentationError> {
+    let mut rel_links = vec![];
+    for action in as_array(Some(actions), "actions")? {
+        let (name, href) = match (action["name"].as_str(), action["href"].as_str()) {
+            (Some(name), Some(href)) => (name, href),
+            _ => {
+                return Err(RepresentationError::InvalidDocument(
+                    SIREN,
+                    "action is missing name or href".to_string(),
+                ))
+            }
+        };
+        let mut rel_link = RelLink::GET(name, href);
+        // Siren defaults to GET when the method is left out
+        if let Some(method) = action["method"].as_str() {
+            *rel_link.method_mut() = HttpMethod::from_name(method).ok_or_else(|| {
+                RepresentationError::InvalidDocument(
+                    SIREN,
+                    format!("action {} has unknown method {}", name, method),
+                )
+            })?;
+        }
+        if let Some(content_type) = action["type"].as_str() {
+            rel_link.headers_mut().set("Content-Type", content_type);
+        }
+        *rel_link.name_mut() = action["title"].as_str().map(|t| t.to_string());
+        if let Some(fields) = action.get("fields") {
+            *rel_link.fields_mut() = Some(serde_json::from_value(fields.clone())?);
+        }
+        rel_links.push(rel_link);
+    }
+    Ok(rel_links)
+}
+
+/// Entities holding `item` entities are read as lists.
+fn has_items(entity: &Map<String, Value>) -> bool {
+    entity
+        .get("entities")
+        .and_then(Value::as_array)
+        .map(|t| {
+            t.iter().any(|t| {
+                t["rel"]
+                    .as_array()
+                    .map(|t| t.iter().any(|t| t == ITEM))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn as_array(value: Option<Value>, name: &str) -> Result<Vec<Value>, RepresentationError> {
+    match value {
+        None => Ok(vec![]),
+        Some(Value::Array(values)) => Ok(values),
+        Some(_) => Err(RepresentationError::InvalidDocument(
+            SIREN,
+            format!("{} must be an array", name),
+        )),
+    }
+}
+
+fn strings(value: Option<Value>, name: &str) -> Result<Vec<String>, RepresentationError> {
+    as_array(value, name)?
+        .into_iter()
+        .map(|t| match t {
+            Value::String(t) => Ok(t),
+            _ => Err(RepresentationError::InvalidDocument(
+                SIREN,
+                format!("{} must be an array of strings", name),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{Content, Hateoas, HateoasList, LinkField, RelLink, Representation, ResourceList};
+    use serde_json::{json, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Bullet {
+        caliber: u32,
+        material: String,
+    }
+
+    impl crate::HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "bullets";
+    }
+
+    fn bullet() -> Hateoas<Bullet> {
+        let mut author = Hateoas::OK(Some("Rick".to_string()), None);
+        author
+            .spec_mut()
+            .rel()
+            .add(RelLink::GET("self", "/authors/rick"));
+
+        let mut content = Content::new(Bullet {
+            caliber: 9,
+            material: "rubber".to_string(),
+        });
+        content.rel().add(RelLink::GET("self", "/bullets/9"));
+        let mut update = RelLink::PUT("update", "/bullets/9");
+        update.headers_mut().set("Content-Type", "application/json");
+        *update.name_mut() = Some("Update bullet".to_string());
+        let mut caliber = LinkField::new("caliber");
+        *caliber.field_type_mut() = Some("number".to_string());
+        *caliber.value_mut() = Some(json!(9));
+        update.add_field(caliber);
+        content.rel().add(update);
+        content.rel().add(RelLink::DELETE("delete", "/bullets/9"));
+        content.embed("author", author).unwrap();
+        Hateoas::new(Some(content), None, None)
+    }
+
+    #[test]
+    fn siren_encoding() {
+        let encoded = Representation::Siren.encode(&bullet()).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "class": ["Bullet"],
+                "properties": {"caliber": 9, "material": "rubber"},
+                "entities": [{
+                    "rel": ["author"],
+                    "class": ["String"],
+                    "properties": {"content": "Rick"},
+                    "links": [{"rel": ["self"], "href": "/authors/rick"}]
+                }],
+                "links": [{"rel": ["self"], "href": "/bullets/9"}],
+                "actions": [
+                    {
+                        "name": "update",
+                        "method": "PUT",
+                        "href": "/bullets/9",
+                        "type": "application/json",
+                        "title": "Update bullet",
+                        "fields": [{"name": "caliber", "type": "number", "value": 9}]
+                    },
+                    {"name": "delete", "method": "DELETE", "href": "/bullets/9"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn siren_round_trip() {
+        let encoded = Representation::Siren.encode(&bullet()).unwrap();
+        let decoded: Hateoas<Bullet> = Representation::Siren.decode(&encoded).unwrap();
+        let spec = decoded.spec().unwrap();
+
+        assert_eq!(spec.content(), bullet().spec().unwrap().content());
+        assert_eq!(spec.clone().rel(), bullet().spec_mut().rel());
+        let author: Hateoas<String> = spec.get_embedded("author").unwrap().unwrap();
+        assert_eq!(author.spec().unwrap().content(), &Some("Rick".to_string()));
+    }
+
+    #[test]
+    fn siren_list_round_trip() {
+        let mut item = Content::new(9u16);
+        item.rel().add(RelLink::GET("self", "/numbers/9"));
+        let mut list = ResourceList::new(vec![item]);
+        list.paginate(1, 10, 0);
+        let list: HateoasList<u16> = HateoasList::OK(Some(list), None);
+
+        let encoded = Representation::Siren.encode(&list).unwrap();
+        let value: Value = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(value["class"][0], "U16List");
+        assert_eq!(value["properties"]["total"], 1);
+        assert_eq!(
+            value["entities"][0],
+            json!({
+                "rel": ["item"],
+                "class": ["U16"],
+                "properties": {"content": 9},
+                "links": [{"rel": ["self"], "href": "/numbers/9"}]
+            })
+        );
+
+        let decoded: HateoasList<u16> = Representation::Siren.decode(&encoded).unwrap();
+        assert_eq!(decoded.spec(), list.spec());
+    }
+
+    #[test]
+    fn siren_action_without_method_is_get() {
+        let document = br#"{"properties": {"caliber": 9, "material": "lead"}, "actions": [{"name": "search", "href": "/bullets"}]}"#;
+
+        let mut decoded: Hateoas<Bullet> = Representation::Siren.decode(document).unwrap();
+
+        assert_eq!(
+            decoded.spec_mut().rel().get("search"),
+            Some(&RelLink::GET("search", "/bullets"))
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Hateoas;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -14,8 +15,71 @@ pub trait HateoasResource {
     /// an endpoint could be generated as follows:
     /// /[Group]/[Version]/[URL_PATH_SEGMENT]
     const URL_PATH_SEGMENT: &'static str;
+    /// Whether the resource is a list of resources, this is set by
+    /// [`ResourceList`](crate::ResourceList) and used by the representations for rendering the
+    /// items as a collection.
+    const IS_LIST: bool = false;
+
+    /// ## Kind of the resource
+    /// The kind written into the `kind` of the [`Hateoas`] envelope, by default this is `KIND`.
+    /// Wrapping resources can override this for deriving the kind from the wrapped resource, so
+    /// kinds are always compared against `kind()` and not `KIND`.
+    ///
+    /// ```
+    /// use hateoas::HateoasResource;
+    ///
+    /// assert_eq!(String::kind(), "String");
+    /// ```
+    fn kind() -> String {
+        Self::KIND.to_string()
+    }
+
+    /// ## Url of the resource collection
+    /// Generating the url of the collection from the base url, the group, version and url path
+    /// segment of the resource, empty parts are left out.
+    ///
+    /// ```
+    /// use hateoas::HateoasResource;
+    ///
+    /// assert_eq!(String::collection_url("https://example.com/"), "https://example.com/hateoas.io/0.0.1/string");
+    /// ```
+    fn collection_url(base_url: &str) -> String {
+        let mut url = base_url.trim_end_matches('/').to_string();
+        for segment in [Self::GROUP, Self::VERSION, Self::URL_PATH_SEGMENT]
+            .iter()
+            .map(|t| t.trim_matches('/'))
+            .filter(|t| !t.is_empty())
+        {
+            url.push('/');
+            url.push_str(segment);
+        }
+        url
+    }
+
+    /// ## Url of a single resource
+    /// Generating the url of a single resource in the collection, the id will be percent encoded.
+    ///
+    /// ```
+    /// use hateoas::HateoasResource;
+    ///
+    /// assert_eq!(String::resource_url("/api", "a b"), "/api/hateoas.io/0.0.1/string/a%20b");
+    /// ```
+    fn resource_url(base_url: &str, id: &str) -> String {
+        format!(
+            "{}/{}",
+            Self::collection_url(base_url),
+            utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET)
+        )
+    }
 }
 
+/// Everything but the unreserved characters of RFC 3986 are encoded in a path segment.
+const PATH_SEGMENT_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
 impl HateoasResource for String {
     const KIND: &'static str = "String";
     const VERSION: &'static str = "0.0.1";
@@ -0,0 +1,140 @@
+use crate::schema::{api_version, ErrorEnvelope, ERROR_ENVELOPE};
+use crate::{Content, Hateoas, HateoasResource, HeaderMap, Metadata, Status, StatusCode};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+impl<T: HateoasResource + Clone + JsonSchema> JsonSchema for Hateoas<T> {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Owned(format!("Hateoas_{}", T::schema_name()))
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Owned(format!("hateoas::Hateoas<{}>", T::schema_id()))
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "properties": {
+                "apiVersion": {"type": "string", "enum": [api_version::<T>()]},
+                "kind": {"type": "string", "enum": [T::kind()]},
+                "metadata": generator.subschema_for::<Option<Metadata>>(),
+                "spec": generator.subschema_for::<Option<Content<T>>>(),
+                "status": generator.subschema_for::<Option<Status>>(),
+            },
+            "required": ["apiVersion", "kind"],
+        })
+    }
+}
+
+impl JsonSchema for ErrorEnvelope {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed(ERROR_ENVELOPE)
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "properties": {
+                "apiVersion": {"type": "string"},
+                "kind": {"type": "string"},
+                "metadata": generator.subschema_for::<Option<Metadata>>(),
+                "spec": {"type": "null"},
+                "status": generator.subschema_for::<Status>(),
+            },
+            "required": ["apiVersion", "kind", "status"],
+        })
+    }
+}
+
+impl JsonSchema for StatusCode {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("StatusCode")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "integer",
+            "format": "uint16",
+            "minimum": 100,
+            "maximum": 999,
+        })
+    }
+}
+
+impl JsonSchema for HeaderMap {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("HeaderMap")
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "object",
+            "additionalProperties": {
+                "type": "array",
+                "items": {"type": "string"},
+                "uniqueItems": true,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ErrorEnvelope, Hateoas, HateoasResource};
+    use schemars::JsonSchema;
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize, Clone, JsonSchema)]
+    struct Bullet {
+        caliber: u32,
+    }
+
+    impl HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "/bullets";
+    }
+
+    #[test]
+    fn hateoas_schema() {
+        let schema = schemars::schema_for!(Hateoas<Bullet>);
+
+        assert_eq!(schema.get("title"), Some(&json!("Hateoas_Bullet")));
+        assert_eq!(
+            schema
+                .get("properties")
+                .map(|t| (&t["apiVersion"], &t["kind"])),
+            Some((
+                &json!({"type": "string", "enum": ["ammo.hateoas.io/v1"]}),
+                &json!({"type": "string", "enum": ["Bullet"]})
+            ))
+        );
+        let defs = schema.get("$defs").unwrap();
+        assert_eq!(
+            defs["Content_Bullet"]["properties"]["content"],
+            json!({"anyOf": [{"$ref": "#/$defs/Bullet"}, {"type": "null"}]})
+        );
+        assert_eq!(
+            defs["Status"]["properties"]["http_status_code"],
+            json!({"type": ["integer", "null"], "format": "uint16", "minimum": 100, "maximum": 999})
+        );
+        assert!(defs.get("HeaderMap").is_some());
+    }
+
+    #[test]
+    fn error_envelope_schema() {
+        let schema = schemars::schema_for!(ErrorEnvelope);
+
+        assert_eq!(schema.get("title"), Some(&json!("ErrorEnvelope")));
+        assert_eq!(
+            schema.get("properties").map(|t| &t["status"]),
+            Some(&json!({"$ref": "#/$defs/Status"}))
+        );
+    }
+}
@@ -0,0 +1,35 @@
+//! Schemas of the envelope
+//!
+//! With the `schemars` feature [`schemars::JsonSchema`], and with the `utoipa` feature
+//! [`utoipa::ToSchema`], is implemented for [`crate::Hateoas`] and the types it is made of.
+//! Each resource type gets its own `Hateoas_<T>` schema where `apiVersion` and `kind` are single
+//! value enums of the constants of the [`HateoasResource`], the generated schemas are JSON Schema
+//! 2020-12 and can be used as OpenAPI 3.1 components.
+use crate::HateoasResource;
+
+#[cfg(feature = "schemars")]
+mod json_schema;
+#[cfg(feature = "utoipa")]
+mod open_api;
+
+/// # ErrorEnvelope
+/// The schema of the envelope returned for errors, eg. the rejections of the extractors. This is
+/// a [`crate::Hateoas`] without a spec where the status is always set.
+///
+/// ```
+/// # #[cfg(feature = "schemars")]
+/// # {
+/// use hateoas::ErrorEnvelope;
+///
+/// let schema = schemars::schema_for!(ErrorEnvelope);
+///
+/// assert_eq!(schema.get("required"), Some(&serde_json::json!(["apiVersion", "kind", "status"])));
+/// # }
+/// ```
+pub struct ErrorEnvelope;
+
+pub(crate) const ERROR_ENVELOPE: &str = "ErrorEnvelope";
+
+pub(crate) fn api_version<T: HateoasResource>() -> String {
+    format!("{}/{}", T::GROUP, T::VERSION)
+}
@@ -0,0 +1,172 @@
+use crate::schema::{api_version, ErrorEnvelope, ERROR_ENVELOPE};
+use crate::{Content, Hateoas, HateoasResource, HeaderMap, Metadata, Status, StatusCode};
+use std::borrow::Cow;
+use utoipa::openapi::schema::{
+    ArrayBuilder, ObjectBuilder, OneOfBuilder, Schema, SchemaFormat, Type,
+};
+use utoipa::openapi::{Ref, RefOr};
+use utoipa::{PartialSchema, ToSchema};
+
+type Schemas = Vec<(String, RefOr<Schema>)>;
+
+impl<T: HateoasResource + Clone + ToSchema> PartialSchema for Hateoas<T> {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .property("apiVersion", constant(api_version::<T>()))
+            .required("apiVersion")
+            .property("kind", constant(T::kind()))
+            .required("kind")
+            .property("metadata", nullable(reference::<Metadata>()))
+            .property("spec", nullable(Content::<T>::schema()))
+            .property("status", nullable(reference::<Status>()))
+            .into()
+    }
+}
+
+impl<T: HateoasResource + Clone + ToSchema> ToSchema for Hateoas<T> {
+    fn name() -> Cow<'static, str> {
+        Cow::Owned(format!("Hateoas_{}", T::name()))
+    }
+
+    fn schemas(schemas: &mut Schemas) {
+        envelope_schemas(schemas);
+        <Content<T> as ToSchema>::schemas(schemas);
+        schemas.push((T::name().into(), T::schema()));
+        T::schemas(schemas);
+    }
+}
+
+impl PartialSchema for ErrorEnvelope {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .property("apiVersion", ObjectBuilder::new().schema_type(Type::String))
+            .required("apiVersion")
+            .property("kind", ObjectBuilder::new().schema_type(Type::String))
+            .required("kind")
+            .property("metadata", nullable(reference::<Metadata>()))
+            .property("spec", ObjectBuilder::new().schema_type(Type::Null))
+            .property("status", reference::<Status>())
+            .required("status")
+            .into()
+    }
+}
+
+impl ToSchema for ErrorEnvelope {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed(ERROR_ENVELOPE)
+    }
+
+    fn schemas(schemas: &mut Schemas) {
+        envelope_schemas(schemas);
+    }
+}
+
+impl PartialSchema for StatusCode {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Integer)
+            .format(Some(SchemaFormat::Custom("uint16".to_string())))
+            .minimum(Some(100))
+            .maximum(Some(999))
+            .into()
+    }
+}
+
+impl ToSchema for StatusCode {}
+
+impl PartialSchema for HeaderMap {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .additional_properties(Some(
+                ArrayBuilder::new()
+                    .items(ObjectBuilder::new().schema_type(Type::String))
+                    .unique_items(true),
+            ))
+            .into()
+    }
+}
+
+impl ToSchema for HeaderMap {}
+
+/// The components referenced by both the envelope and the error envelope.
+fn envelope_schemas(schemas: &mut Schemas) {
+    schemas.push((Metadata::name().into(), Metadata::schema()));
+    <Metadata as ToSchema>::schemas(schemas);
+    schemas.push((Status::name().into(), Status::schema()));
+    <Status as ToSchema>::schemas(schemas);
+}
+
+fn constant(value: String) -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .enum_values(Some([value]))
+}
+
+fn reference<T: ToSchema>() -> RefOr<Schema> {
+    RefOr::Ref(Ref::from_schema_name(T::name()))
+}
+
+fn nullable(schema: RefOr<Schema>) -> OneOfBuilder {
+    OneOfBuilder::new()
+        .item(schema)
+        .item(ObjectBuilder::new().schema_type(Type::Null))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{ErrorEnvelope, Hateoas, HateoasResource};
+    use serde_json::json;
+    use utoipa::{PartialSchema, ToSchema};
+
+    #[derive(Serialize, Deserialize, Clone, ToSchema)]
+    struct Bullet {
+        caliber: u32,
+    }
+
+    impl HateoasResource for Bullet {
+        const KIND: &'static str = "Bullet";
+        const VERSION: &'static str = "v1";
+        const GROUP: &'static str = "ammo.hateoas.io";
+        const URL_PATH_SEGMENT: &'static str = "/bullets";
+    }
+
+    #[test]
+    fn hateoas_schema() {
+        let schema = serde_json::to_value(Hateoas::<Bullet>::schema()).unwrap();
+        let mut schemas = vec![];
+        Hateoas::<Bullet>::schemas(&mut schemas);
+        let names = schemas
+            .iter()
+            .map(|(t, _)| t.as_str())
+            .collect::<Vec<&str>>();
+
+        assert_eq!(Hateoas::<Bullet>::name(), "Hateoas_Bullet");
+        assert_eq!(
+            schema["properties"]["apiVersion"],
+            json!({"type": "string", "enum": ["ammo.hateoas.io/v1"]})
+        );
+        assert_eq!(
+            schema["properties"]["kind"],
+            json!({"type": "string", "enum": ["Bullet"]})
+        );
+        assert_eq!(schema["required"], json!(["apiVersion", "kind"]));
+        assert!(names.contains(&"Metadata"));
+        assert!(names.contains(&"Status"));
+        assert!(names.contains(&"Bullet"));
+    }
+
+    #[test]
+    fn error_envelope_schema() {
+        let schema = serde_json::to_value(ErrorEnvelope::schema()).unwrap();
+
+        assert_eq!(schema["required"], json!(["apiVersion", "kind", "status"]));
+        assert_eq!(
+            schema["properties"]["status"],
+            json!({"$ref": "#/components/schemas/Status"})
+        );
+        assert_eq!(schema["properties"]["spec"], json!({"type": "null"}));
+    }
+}
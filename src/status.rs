@@ -2,6 +2,8 @@ use crate::{HeaderMap, StatusCode};
 
 #[skip_serializing_none]
 #[derive(Serialize, Deserialize, Debug, PartialEq, Default, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 pub struct Status {
     pub(crate) message: Option<String>,
     pub(crate) code: Option<u32>,
@@ -0,0 +1,179 @@
+#![cfg(feature = "actix")]
+
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use hateoas::{Hateoas, HeaderMap};
+
+fn with_header(mut hateoas: Hateoas<String>, key: &str, value: &str) -> Hateoas<String> {
+    hateoas
+        .status_mut()
+        .headers_mut()
+        .get_or_insert_with(HeaderMap::new)
+        .append(key, value);
+    hateoas
+}
+
+async fn accepted() -> Hateoas<String> {
+    Hateoas::ACCEPTED(None, None)
+}
+
+async fn created() -> Hateoas<String> {
+    with_header(
+        Hateoas::CREATED(Some("bullet".to_string()), None),
+        "Location",
+        "/bullets/1",
+    )
+}
+
+async fn no_content() -> Hateoas<String> {
+    Hateoas::NO_CONTENT(None, None)
+}
+
+async fn moved() -> Hateoas<String> {
+    with_header(
+        Hateoas::MOVED_PERMANENTLY(None, None),
+        "Location",
+        "/rubber-bullets/1",
+    )
+}
+
+async fn not_modified() -> Hateoas<String> {
+    with_header(Hateoas::NOT_MODIFIED(None, None), "ETag", "\"1\"")
+}
+
+async fn not_found() -> Hateoas<String> {
+    Hateoas::NOT_FOUND(None, Some("bullet 2 not found".to_string()))
+}
+
+async fn unavailable() -> Hateoas<String> {
+    let mut hateoas = with_header(
+        Hateoas::SERVICE_UNAVAILABLE(None, None),
+        "Retry-After",
+        "120",
+    );
+    hateoas
+        .metadata_mut()
+        .headers_mut()
+        .get_or_insert_with(HeaderMap::new)
+        .append("Cache-Control", "no-store");
+    hateoas
+}
+
+async fn managed_headers() -> Hateoas<String> {
+    with_header(
+        Hateoas::OK(Some("bullet".to_string()), None),
+        "Content-Type",
+        "text/plain",
+    )
+}
+
+fn app() -> App<
+    impl actix_web::dev::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        .route("/accepted", web::post().to(accepted))
+        .route("/created", web::post().to(created))
+        .route("/no-content", web::delete().to(no_content))
+        .route("/moved", web::get().to(moved))
+        .route("/not-modified", web::get().to(not_modified))
+        .route("/not-found", web::get().to(not_found))
+        .route("/unavailable", web::get().to(unavailable))
+        .route("/managed", web::get().to(managed_headers))
+}
+
+#[actix_web::test]
+async fn success() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::post().uri("/created").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers().get(header::LOCATION).unwrap(), "/bullets/1");
+    let body = test::read_body(res).await;
+    let hateoas = serde_json::from_slice::<Hateoas<String>>(&body).unwrap();
+    assert_eq!(
+        hateoas.spec().and_then(|t| t.content().as_ref()),
+        Some(&"bullet".to_string())
+    );
+
+    let req = test::TestRequest::post().uri("/accepted").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::ACCEPTED);
+
+    let req = test::TestRequest::delete().uri("/no-content").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert!(test::read_body(res).await.is_empty());
+}
+
+#[actix_web::test]
+async fn redirection() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::get().uri("/moved").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::MOVED_PERMANENTLY);
+    assert_eq!(
+        res.headers().get(header::LOCATION).unwrap(),
+        "/rubber-bullets/1"
+    );
+
+    let req = test::TestRequest::get().uri("/not-modified").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(res.headers().get(header::ETAG).unwrap(), "\"1\"");
+    assert!(res.headers().get(header::CONTENT_TYPE).is_none());
+    assert!(test::read_body(res).await.is_empty());
+}
+
+#[actix_web::test]
+async fn client_error() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::get().uri("/not-found").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let body = test::read_body(res).await;
+    let hateoas = serde_json::from_slice::<Hateoas<String>>(&body).unwrap();
+    assert_eq!(
+        hateoas.status().and_then(|t| t.message().as_ref()),
+        Some(&"bullet 2 not found".to_string())
+    );
+}
+
+#[actix_web::test]
+async fn server_error() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::get().uri("/unavailable").to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(res.headers().get(header::RETRY_AFTER).unwrap(), "120");
+    assert_eq!(
+        res.headers().get(header::CACHE_CONTROL).unwrap(),
+        "no-store"
+    );
+}
+
+#[actix_web::test]
+async fn body_headers_are_not_copied() {
+    let app = test::init_service(app()).await;
+
+    let req = test::TestRequest::get()
+        .uri("/managed")
+        .insert_header((header::ACCEPT, "application/yaml"))
+        .to_request();
+    let res = test::call_service(&app, req).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers().get_all(header::CONTENT_TYPE).count(), 1);
+    assert_eq!(
+        res.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/yaml"
+    );
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "derive")]
+
+use hateoas::{Hateoas, HateoasResource};
+use std::marker::PhantomData;
+
+#[derive(HateoasResource, Clone)]
+#[hateoas(group = "hateoas.io")]
+pub struct RubberBullet;
+
+#[derive(HateoasResource, Clone)]
+#[hateoas(
+    kind = "Bullet",
+    group = "ammo.hateoas.io",
+    version = "v2beta1",
+    path = "bullets"
+)]
+pub struct CustomBullet;
+
+#[derive(HateoasResource, Clone)]
+#[hateoas(group = "hateoas.io", version = "0.0.1")]
+pub struct Policy<T> {
+    _t: PhantomData<T>,
+}
+
+#[test]
+fn derive_defaults() {
+    assert_eq!(RubberBullet::KIND, "RubberBullet");
+    assert_eq!(RubberBullet::GROUP, "hateoas.io");
+    assert_eq!(RubberBullet::VERSION, "v1");
+    assert_eq!(RubberBullet::URL_PATH_SEGMENT, "rubber_bullets");
+}
+
+#[test]
+fn derive_with_attributes() {
+    assert_eq!(CustomBullet::KIND, "Bullet");
+    assert_eq!(CustomBullet::GROUP, "ammo.hateoas.io");
+    assert_eq!(CustomBullet::VERSION, "v2beta1");
+    assert_eq!(CustomBullet::URL_PATH_SEGMENT, "bullets");
+}
+
+#[test]
+fn derive_with_generics() {
+    assert_eq!(<Policy<String>>::KIND, "Policy");
+    assert_eq!(<Policy<String>>::URL_PATH_SEGMENT, "policies");
+}
+
+#[test]
+fn derived_resource_api_version() {
+    let response: Hateoas<CustomBullet> = Hateoas::default();
+
+    assert_eq!(response.api_version(), "ammo.hateoas.io/v2beta1");
+    assert_eq!(response.kind(), "Bullet");
+}
@@ -0,0 +1,308 @@
+#![cfg(feature = "rocket")]
+
+use hateoas::frameworks::rejection::{DefaultRejectionHandler, Rejection, RejectionHandler};
+use hateoas::frameworks::rocket::{catcher::catcher, config::HateoasConfig, LIMIT};
+use hateoas::{Hateoas, HateoasResource, HeaderMap};
+use rocket::data::{Limits, ToByteUnit};
+use rocket::http::{ContentType, Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::{Build, Config, Rocket};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+struct RubberBullet {
+    name: String,
+}
+
+impl HateoasResource for RubberBullet {
+    const KIND: &'static str = "RubberBullet";
+    const VERSION: &'static str = "v1";
+    const GROUP: &'static str = "ammo.hateoas.io";
+    const URL_PATH_SEGMENT: &'static str = "rubber_bullets";
+}
+
+const BULLET: &str = r#"{"name":"Strong bullet"}"#;
+const ENVELOPE: &str = r#"{"apiVersion":"ammo.hateoas.io/v1","kind":"RubberBullet","spec":{"content":{"name":"Strong bullet"}}}"#;
+
+fn with_header(mut hateoas: Hateoas<String>, key: &str, value: &str) -> Hateoas<String> {
+    hateoas
+        .status_mut()
+        .headers_mut()
+        .get_or_insert_with(HeaderMap::new)
+        .append(key, value);
+    hateoas
+}
+
+#[rocket::post("/bullets", data = "<bullet>")]
+fn echo(bullet: Hateoas<RubberBullet>) -> Hateoas<RubberBullet> {
+    bullet
+}
+
+#[rocket::post("/checked", data = "<bullet>")]
+fn checked(bullet: Result<Hateoas<RubberBullet>, Hateoas<()>>) -> Hateoas<String> {
+    match bullet {
+        Ok(_) => Hateoas::OK(Some("bullet".to_string()), None),
+        Err(e) => Hateoas::UNPROCESSABLE_ENTITY(None, e.status().and_then(|t| t.message().clone())),
+    }
+}
+
+#[rocket::post("/created")]
+fn created() -> Hateoas<String> {
+    with_header(
+        Hateoas::CREATED(Some("bullet".to_string()), None),
+        "Location",
+        "/bullets/1",
+    )
+}
+
+#[rocket::delete("/no-content")]
+fn no_content() -> Hateoas<String> {
+    Hateoas::NO_CONTENT(None, None)
+}
+
+#[rocket::get("/unavailable")]
+fn unavailable() -> Hateoas<String> {
+    let mut hateoas = with_header(
+        Hateoas::SERVICE_UNAVAILABLE(None, None),
+        "Retry-After",
+        "120",
+    );
+    hateoas
+        .metadata_mut()
+        .headers_mut()
+        .get_or_insert_with(HeaderMap::new)
+        .append("Cache-Control", "no-store");
+    hateoas
+}
+
+#[rocket::get("/managed")]
+fn managed_headers() -> Hateoas<String> {
+    with_header(
+        Hateoas::OK(Some("bullet".to_string()), None),
+        "Content-Type",
+        "text/plain",
+    )
+}
+
+fn app(limits: Limits) -> Rocket<Build> {
+    rocket::custom(Config {
+        limits,
+        ..Config::debug_default()
+    })
+    .mount(
+        "/",
+        rocket::routes![
+            echo,
+            checked,
+            created,
+            no_content,
+            unavailable,
+            managed_headers
+        ],
+    )
+    .register("/", vec![catcher()])
+}
+
+async fn client() -> Client {
+    Client::tracked(app(Limits::default())).await.unwrap()
+}
+
+#[rocket::async_test]
+async fn success() {
+    let client = client().await;
+
+    let res = client.post("/created").dispatch().await;
+    assert_eq!(res.status(), Status::Created);
+    assert_eq!(res.headers().get_one("Location"), Some("/bullets/1"));
+    let hateoas =
+        serde_json::from_slice::<Hateoas<String>>(&res.into_bytes().await.unwrap()).unwrap();
+    assert_eq!(
+        hateoas.spec().and_then(|t| t.content().as_ref()),
+        Some(&"bullet".to_string())
+    );
+
+    let res = client.delete("/no-content").dispatch().await;
+    assert_eq!(res.status(), Status::NoContent);
+    assert!(res.into_bytes().await.unwrap_or_default().is_empty());
+}
+
+#[rocket::async_test]
+async fn server_error() {
+    let client = client().await;
+
+    let res = client.get("/unavailable").dispatch().await;
+    assert_eq!(res.status(), Status::ServiceUnavailable);
+    assert_eq!(res.headers().get_one("Retry-After"), Some("120"));
+    assert_eq!(res.headers().get_one("Cache-Control"), Some("no-store"));
+}
+
+#[rocket::async_test]
+async fn negotiation() {
+    let client = client().await;
+
+    let res = client
+        .get("/managed")
+        .header(Header::new("Accept", "application/yaml"))
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.headers().get("Content-Type").count(), 1);
+    assert_eq!(
+        res.headers().get_one("Content-Type"),
+        Some("application/yaml")
+    );
+
+    let res = client
+        .get("/managed")
+        .header(Header::new("Accept", "text/html"))
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::NotAcceptable);
+}
+
+#[rocket::async_test]
+async fn decode() {
+    let client = client().await;
+
+    for body in [BULLET, ENVELOPE] {
+        let res = client
+            .post("/bullets")
+            .header(ContentType::JSON)
+            .body(body)
+            .dispatch()
+            .await;
+        assert_eq!(res.status(), Status::Ok);
+        let hateoas =
+            serde_json::from_slice::<Hateoas<RubberBullet>>(&res.into_bytes().await.unwrap())
+                .unwrap();
+        assert_eq!(
+            hateoas.spec().and_then(|t| t.content().as_ref()),
+            Some(&RubberBullet {
+                name: "Strong bullet".to_string()
+            })
+        );
+    }
+
+    let res = client
+        .post("/bullets")
+        .header(Header::new("Content-Type", "application/yaml"))
+        .body("name: Strong bullet")
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::Ok);
+}
+
+#[rocket::async_test]
+async fn limits() {
+    let limits = Limits::default().limit(LIMIT, 8.bytes());
+    let client = Client::tracked(app(limits)).await.unwrap();
+
+    let res = client
+        .post("/bullets")
+        .header(ContentType::JSON)
+        .body(BULLET)
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::PayloadTooLarge);
+    let hateoas = serde_json::from_slice::<Hateoas<()>>(&res.into_bytes().await.unwrap()).unwrap();
+    assert_eq!(
+        hateoas
+            .status()
+            .and_then(|t| t.http_status_code().clone())
+            .map(u16::from),
+        Some(413)
+    );
+}
+
+#[rocket::async_test]
+async fn rejections() {
+    let client = client().await;
+
+    let res = client.post("/bullets").body(BULLET).dispatch().await;
+    assert_eq!(res.status(), Status::UnsupportedMediaType);
+
+    let res = client
+        .post("/bullets")
+        .header(ContentType::JSON)
+        .body("{")
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::BadRequest);
+    let hateoas = serde_json::from_slice::<Hateoas<()>>(&res.into_bytes().await.unwrap()).unwrap();
+    assert!(hateoas
+        .status()
+        .and_then(|t| t.message().as_deref())
+        .unwrap_or_default()
+        .starts_with("JSON processing error"));
+
+    let res = client
+        .post("/checked")
+        .header(ContentType::JSON)
+        .body("{")
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::UnprocessableEntity);
+}
+
+#[rocket::async_test]
+async fn request_headers_are_not_echoed() {
+    let client = client().await;
+
+    let res = client
+        .post("/bullets")
+        .header(ContentType::JSON)
+        .header(Header::new("Authorization", "Bearer secret"))
+        .body(BULLET)
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.headers().get_one("Authorization"), None);
+}
+
+#[rocket::async_test]
+async fn config() {
+    let config = HateoasConfig::default()
+        .content_types(["application/json"])
+        .strict(true)
+        .rejection_handler(|rejection: Rejection| match rejection {
+            Rejection::MissingContentType => {
+                Hateoas::BAD_REQUEST(None, Some("Content-Type is required".to_string()))
+            }
+            rejection => DefaultRejectionHandler.reject(rejection),
+        });
+    let client = Client::tracked(app(Limits::default()).manage(config))
+        .await
+        .unwrap();
+
+    let res = client
+        .post("/bullets")
+        .header(ContentType::JSON)
+        .body(ENVELOPE)
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::Ok);
+
+    let res = client
+        .post("/bullets")
+        .header(ContentType::JSON)
+        .body(BULLET)
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::BadRequest);
+
+    let res = client
+        .post("/bullets")
+        .header(Header::new("Content-Type", "application/yaml"))
+        .body("name: Strong bullet")
+        .dispatch()
+        .await;
+    assert_eq!(res.status(), Status::UnsupportedMediaType);
+
+    let res = client.post("/bullets").body(BULLET).dispatch().await;
+    assert_eq!(res.status(), Status::BadRequest);
+    let hateoas = serde_json::from_slice::<Hateoas<()>>(&res.into_bytes().await.unwrap()).unwrap();
+    assert_eq!(
+        hateoas.status().and_then(|t| t.message().as_deref()),
+        Some("Content-Type is required")
+    );
+}